@@ -11,12 +11,14 @@ mod account_structs;
 mod account_validators;
 mod events;
 mod macros;
+mod params;
 pub mod proposal;
 mod state;
 
 use account_structs::*;
 
 pub use events::*;
+pub use params::*;
 pub use proposal::*;
 pub use state::*;
 
@@ -36,10 +38,7 @@ pub mod govern {
         electorate: Pubkey,
         params: GovernanceParameters,
     ) -> Result<()> {
-        invariant!(
-            params.timelock_delay_seconds >= 0,
-            "timelock delay must be at least 0 seconds"
-        );
+        params.validate()?;
 
         let governor = &mut ctx.accounts.governor;
         governor.base = ctx.accounts.base.key();
@@ -203,6 +202,7 @@ pub mod govern {
         ctx: Context<SetGovernanceParams>,
         params: GovernanceParameters,
     ) -> Result<()> {
+        params.validate()?;
         let prev_params = ctx.accounts.governor.params;
         ctx.accounts.governor.params = params;
 
@@ -267,4 +267,12 @@ pub enum ErrorCode {
     ProposalNotDraft,
     #[msg("The proposal must be active.")]
     ProposalNotActive,
+    #[msg("Voting delay exceeds the allowed bounds.")]
+    InvalidVotingDelay,
+    #[msg("Voting period is outside of the allowed bounds.")]
+    InvalidVotingPeriod,
+    #[msg("Timelock delay is outside of the allowed bounds.")]
+    InvalidTimelockDelay,
+    #[msg("Governance parameter bounds are inconsistent or looser than the defaults.")]
+    InvalidGovernanceParametersBounds,
 }
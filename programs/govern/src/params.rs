@@ -0,0 +1,140 @@
+//! Validation of [GovernanceParameters].
+
+use crate::*;
+
+/// Bounds on [GovernanceParameters].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GovernanceParametersBounds {
+    /// Longest allowed [GovernanceParameters::voting_delay].
+    pub max_voting_delay: u64,
+    /// Shortest allowed [GovernanceParameters::voting_period].
+    pub min_voting_period: u64,
+    /// Longest allowed [GovernanceParameters::voting_period].
+    pub max_voting_period: u64,
+    /// Shortest allowed [GovernanceParameters::timelock_delay_seconds].
+    pub min_timelock_delay_seconds: i64,
+    /// Longest allowed [GovernanceParameters::timelock_delay_seconds].
+    pub max_timelock_delay_seconds: i64,
+}
+
+impl GovernanceParametersBounds {
+    /// Bounds enforced by [govern::create_governor] and [govern::set_governance_params].
+    ///
+    /// Each delay is capped at one year, which keeps proposal timestamps from overflowing
+    /// and proposals from being locked up indefinitely. Voting and the timelock last at least
+    /// an hour, so a proposal cannot pass and execute before voters can react.
+    pub const DEFAULT: GovernanceParametersBounds = GovernanceParametersBounds {
+        max_voting_delay: 365 * 24 * 60 * 60,
+        min_voting_period: 60 * 60,
+        max_voting_period: 365 * 24 * 60 * 60,
+        min_timelock_delay_seconds: 60 * 60,
+        max_timelock_delay_seconds: 365 * 24 * 60 * 60,
+    };
+
+    /// Checks that the bounds are consistent and no looser than [GovernanceParametersBounds::DEFAULT].
+    pub fn validate(&self) -> Result<()> {
+        let default = GovernanceParametersBounds::DEFAULT;
+        invariant!(
+            self.max_voting_delay <= default.max_voting_delay
+                && self.min_voting_period >= default.min_voting_period
+                && self.min_voting_period <= self.max_voting_period
+                && self.max_voting_period <= default.max_voting_period
+                && self.min_timelock_delay_seconds >= default.min_timelock_delay_seconds
+                && self.min_timelock_delay_seconds <= self.max_timelock_delay_seconds
+                && self.max_timelock_delay_seconds <= default.max_timelock_delay_seconds,
+            InvalidGovernanceParametersBounds
+        );
+        Ok(())
+    }
+}
+
+impl GovernanceParameters {
+    /// Checks that the parameters are within [GovernanceParametersBounds::DEFAULT].
+    pub fn validate(&self) -> Result<()> {
+        self.validate_with_bounds(&GovernanceParametersBounds::DEFAULT)
+    }
+
+    /// Checks that the parameters are within `bounds`, which must themselves be valid.
+    pub fn validate_with_bounds(&self, bounds: &GovernanceParametersBounds) -> Result<()> {
+        bounds.validate()?;
+        invariant!(
+            self.voting_delay <= bounds.max_voting_delay,
+            InvalidVotingDelay
+        );
+        invariant!(
+            self.voting_period >= bounds.min_voting_period
+                && self.voting_period <= bounds.max_voting_period,
+            InvalidVotingPeriod
+        );
+        invariant!(
+            self.timelock_delay_seconds >= bounds.min_timelock_delay_seconds
+                && self.timelock_delay_seconds <= bounds.max_timelock_delay_seconds,
+            InvalidTimelockDelay
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE_DAY: u64 = 24 * 60 * 60;
+
+    #[test]
+    fn test_validate_governance_params() {
+        let params = GovernanceParameters {
+            voting_delay: ONE_DAY,
+            voting_period: 3 * ONE_DAY,
+            quorum_votes: 10,
+            timelock_delay_seconds: ONE_DAY as i64,
+        };
+        assert!(params.validate().is_ok());
+        assert!(GovernanceParameters::default().validate().is_err());
+
+        let invalid = [
+            GovernanceParameters {
+                voting_delay: u64::MAX,
+                ..params
+            },
+            GovernanceParameters {
+                voting_period: u64::MAX,
+                ..params
+            },
+            GovernanceParameters {
+                voting_period: 0,
+                ..params
+            },
+            GovernanceParameters {
+                timelock_delay_seconds: 0,
+                ..params
+            },
+            GovernanceParameters {
+                timelock_delay_seconds: -1,
+                ..params
+            },
+            GovernanceParameters {
+                timelock_delay_seconds: i64::MAX,
+                ..params
+            },
+        ];
+        for params in invalid.iter() {
+            assert!(params.validate().is_err(), "{:?}", params);
+        }
+
+        let bounds = GovernanceParametersBounds {
+            min_voting_period: 7 * ONE_DAY,
+            ..GovernanceParametersBounds::DEFAULT
+        };
+        assert!(params.validate_with_bounds(&bounds).is_err());
+
+        // bounds may not be looser than the defaults.
+        let loose = GovernanceParametersBounds {
+            min_timelock_delay_seconds: 0,
+            ..GovernanceParametersBounds::DEFAULT
+        };
+        assert!(GovernanceParametersBounds::DEFAULT.validate().is_ok());
+        assert!(loose.validate().is_err());
+        assert!(params.validate_with_bounds(&loose).is_err());
+    }
+}
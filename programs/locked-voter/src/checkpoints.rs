@@ -0,0 +1,595 @@
+//! Global voting power checkpoints of a [Locker].
+
+use crate::*;
+use num_traits::ToPrimitive;
+
+/// The contribution of a single [Escrow] to the [LockerCheckpoints] at a point in time.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EscrowCheckpoint {
+    /// Locked amount times the seconds until expiry.
+    pub bias: u128,
+    /// Decrease in [EscrowCheckpoint::bias] per second, i.e. the locked amount.
+    pub slope: u128,
+    /// Decreases of the [EscrowCheckpoint::slope] as the tokens unlock, sorted by [SlopeChange::time].
+    pub slope_changes: Vec<SlopeChange>,
+    /// Amount locked in max-lock mode.
+    pub max_locked_amount: u64,
+}
+
+impl EscrowCheckpoint {
+    /// Gets the voting power this [EscrowCheckpoint] contributes at its time under `params`.
+    pub fn voting_power(&self, params: &LockerParams) -> Option<u64> {
+        Point {
+            bias: self.bias,
+            max_locked_amount: self.max_locked_amount,
+            ..Point::default()
+        }
+        .voting_power_at(params, 0)
+    }
+}
+
+impl Point {
+    /// Gets the bias of this [Point] at `timestamp`, which must not be before [Point::ts].
+    pub fn bias_at(&self, timestamp: i64) -> Option<u128> {
+        let elapsed_seconds = timestamp.checked_sub(self.ts)?.to_u128()?;
+        Some(
+            self.bias
+                .saturating_sub(self.slope.checked_mul(elapsed_seconds)?),
+        )
+    }
+
+    /// Moves this [Point] forward to `timestamp`, then applies the [SlopeChange]s at that time.
+    fn advance(&self, timestamp: i64, slope_delta: u128) -> Option<Point> {
+        Some(Point {
+            bias: self.bias_at(timestamp)?,
+            slope: self.slope.saturating_sub(slope_delta),
+            ts: timestamp,
+            max_locked_amount: self.max_locked_amount,
+        })
+    }
+
+    /// Gets the total voting power of this [Point] at `timestamp` under `params`.
+    fn voting_power_at(&self, params: &LockerParams, timestamp: i64) -> Option<u64> {
+        let multiplier = u128::from(params.max_stake_vote_multiplier);
+        let decaying = self
+            .bias_at(timestamp)?
+            .checked_mul(multiplier)?
+            .checked_div(params.max_stake_duration.into())?;
+        let max_locked = u128::from(self.max_locked_amount).checked_mul(multiplier)?;
+        decaying.checked_add(max_locked)?.to_u64()
+    }
+}
+
+impl LockerCheckpoints {
+    /// Gets the most recent [Point], or an empty [Point] if none has been written.
+    pub fn last_point(&self) -> Point {
+        if self.epoch == 0 {
+            return Point::default();
+        }
+        self.points[self.point_index(self.epoch - 1)]
+    }
+
+    fn point_index(&self, epoch: u64) -> usize {
+        (epoch % LockerCheckpoints::MAX_POINTS as u64) as usize
+    }
+
+    /// Writes `point` to the history, replacing the last [Point] if it has the same timestamp.
+    fn push_point(&mut self, point: Point) {
+        if self.epoch > 0 && self.last_point().ts == point.ts {
+            let index = self.point_index(self.epoch - 1);
+            self.points[index] = point;
+            return;
+        }
+        let index = self.point_index(self.epoch);
+        if index < self.points.len() {
+            self.points[index] = point;
+        } else {
+            self.points.push(point);
+        }
+        self.epoch += 1;
+    }
+
+    /// Brings the history up to `now`, writing a [Point] at every [SlopeChange] that has passed.
+    pub fn checkpoint(&mut self, now: i64) -> Result<Point> {
+        let mut point = self.last_point();
+        invariant!(now >= point.ts, "checkpoint is in the future");
+
+        let passed = self
+            .slope_changes
+            .iter()
+            .take_while(|change| change.time <= now)
+            .count();
+        let passed_changes: Vec<SlopeChange> = self.slope_changes.drain(..passed).collect();
+        for change in passed_changes {
+            point = unwrap_int!(point.advance(change.time, change.slope_delta));
+            self.push_point(point);
+        }
+
+        point = unwrap_int!(point.advance(now, 0));
+        self.push_point(point);
+        Ok(point)
+    }
+
+    /// Records an [Escrow] changing from `prev` to `next` at `now`.
+    pub fn record_escrow_change(
+        &mut self,
+        prev: EscrowCheckpoint,
+        next: EscrowCheckpoint,
+        now: i64,
+    ) -> Result<()> {
+        let mut point = self.checkpoint(now)?;
+        point.bias = unwrap_int!(point.bias.saturating_sub(prev.bias).checked_add(next.bias));
+        point.slope = unwrap_int!(point
+            .slope
+            .saturating_sub(prev.slope)
+            .checked_add(next.slope));
+        point.max_locked_amount = unwrap_int!(point
+            .max_locked_amount
+            .saturating_sub(prev.max_locked_amount)
+            .checked_add(next.max_locked_amount));
+        self.push_point(point);
+
+        self.replace_slope_changes(&prev.slope_changes, &next.slope_changes, now)
+    }
+
+    /// Replaces the pending `prev` [SlopeChange]s of an [Escrow] with `next`,
+    /// merging the difference into [LockerCheckpoints::slope_changes] in a single pass.
+    fn replace_slope_changes(
+        &mut self,
+        prev: &[SlopeChange],
+        next: &[SlopeChange],
+        now: i64,
+    ) -> Result<()> {
+        let mut prev = prev.iter().filter(|change| change.time > now).peekable();
+        let mut next = next.iter().filter(|change| change.time > now).peekable();
+        // (time, removed slope, added slope), sorted by time.
+        let mut deltas: Vec<(i64, u128, u128)> = vec![];
+        loop {
+            let time = match (prev.peek(), next.peek()) {
+                (None, None) => break,
+                (Some(change), None) | (None, Some(change)) => change.time,
+                (Some(prev_change), Some(next_change)) => prev_change.time.min(next_change.time),
+            };
+            let removed = prev
+                .next_if(|change| change.time == time)
+                .map_or(0, |change| change.slope_delta);
+            let added = next
+                .next_if(|change| change.time == time)
+                .map_or(0, |change| change.slope_delta);
+            if removed != added {
+                deltas.push((time, removed, added));
+            }
+        }
+        if deltas.is_empty() {
+            return Ok(());
+        }
+
+        let mut existing = self.slope_changes.iter().copied().peekable();
+        let mut slope_changes = Vec::with_capacity(self.slope_changes.len() + deltas.len());
+        for (time, removed, added) in deltas {
+            while let Some(change) = existing.next_if(|change| change.time < time) {
+                slope_changes.push(change);
+            }
+            let slope_delta = existing
+                .next_if(|change| change.time == time)
+                .map_or(0, |change| change.slope_delta);
+            let slope_delta = unwrap_int!(slope_delta.saturating_sub(removed).checked_add(added));
+            if slope_delta > 0 {
+                slope_changes.push(SlopeChange { time, slope_delta });
+            }
+        }
+        slope_changes.extend(existing);
+        invariant!(
+            slope_changes.len() <= LockerCheckpoints::MAX_SLOPE_CHANGES,
+            TooManySlopeChanges
+        );
+        self.slope_changes = slope_changes;
+        Ok(())
+    }
+
+    /// Gets the total voting power of the [Locker] at `timestamp`, which is the sum of
+    /// [Escrow::voting_power_at_time] over all checkpointed escrows, up to rounding.
+    ///
+    /// Returns [None] if the total cannot be tracked with these `params`, i.e. without
+    /// [VotingPowerCurve::Linear] or with a [LockerParams::baseline_power_bps],
+    /// or if `timestamp` is older than the kept history.
+    pub fn total_voting_power_at_time(&self, params: &LockerParams, timestamp: i64) -> Option<u64> {
+        if !params.tracks_total_voting_power() {
+            return None;
+        }
+        self.point_at(timestamp)?.voting_power_at(params, timestamp)
+    }
+
+    /// Gets the voting power of the [Locker] at `timestamp` as tracked, i.e. as if
+    /// every lock followed [VotingPowerCurve::Linear] without a [LockerParams::baseline_power_bps].
+    ///
+    /// This is the sum of [Escrow::tracked_voting_power_as_of] over all escrows, up to rounding,
+    /// under any `params`. Returns [None] if `timestamp` is older than the kept history.
+    pub fn tracked_voting_power_at_time(
+        &self,
+        params: &LockerParams,
+        timestamp: i64,
+    ) -> Option<u64> {
+        self.point_at(timestamp)?.voting_power_at(params, timestamp)
+    }
+
+    /// Gets the [Point] in effect at `timestamp`, brought forward to `timestamp`.
+    /// Returns [None] if `timestamp` is older than the kept history.
+    fn point_at(&self, timestamp: i64) -> Option<Point> {
+        let last_point = self.last_point();
+        if timestamp >= last_point.ts {
+            let mut point = last_point;
+            for change in self
+                .slope_changes
+                .iter()
+                .take_while(|change| change.time <= timestamp)
+            {
+                point = point.advance(change.time, change.slope_delta)?;
+            }
+            return point.advance(timestamp, 0);
+        }
+        // there are no slope changes between two consecutive points.
+        match self
+            .points
+            .iter()
+            .filter(|point| point.ts <= timestamp)
+            .max_by_key(|point| point.ts)
+        {
+            Some(point) => point.advance(timestamp, 0),
+            // the history is complete, so nothing was locked yet.
+            None if self.epoch <= LockerCheckpoints::MAX_POINTS as u64 => Some(Point {
+                ts: timestamp,
+                ..Point::default()
+            }),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const ONE_YEAR: u64 = 365 * 24 * 60 * 60;
+
+    fn params() -> LockerParams {
+        LockerParams {
+            max_stake_duration: 4 * ONE_YEAR,
+            max_stake_vote_multiplier: 10,
+            ..LockerParams::default()
+        }
+    }
+
+    fn lock(
+        checkpoints: &mut LockerCheckpoints,
+        escrow: &mut Escrow,
+        amount: u64,
+        now: i64,
+        ends_at: i64,
+    ) {
+        let params = &params();
+        let prev = params.escrow_checkpoint(escrow, now).unwrap();
+        escrow.amount += amount;
+        escrow.escrow_started_at = now;
+        escrow.escrow_ends_at = ends_at;
+        escrow.checkpointed = true;
+        let next = params.escrow_checkpoint(escrow, now).unwrap();
+        checkpoints.record_escrow_change(prev, next, now).unwrap();
+    }
+
+    fn total_of(escrows: &[&Escrow], timestamp: i64) -> u64 {
+        escrows
+            .iter()
+            .map(|escrow| escrow.voting_power_at_time(&params(), timestamp).unwrap())
+            .sum()
+    }
+
+    #[test]
+    fn test_total_voting_power_matches_escrows() {
+        let checkpoints = &mut LockerCheckpoints::default();
+        let mut alice = Escrow::default();
+        let mut bob = Escrow::default();
+        let year = ONE_YEAR as i64;
+
+        lock(checkpoints, &mut alice, 1_000_000, 100, 100 + 4 * year);
+        lock(checkpoints, &mut bob, 3_000_000, 100 + year, 100 + 2 * year);
+
+        for timestamp in [
+            100,
+            100 + year,
+            100 + year + 7,
+            100 + 2 * year,
+            100 + 3 * year,
+            100 + 5 * year,
+        ] {
+            let total = checkpoints
+                .total_voting_power_at_time(&params(), timestamp)
+                .unwrap();
+            let expected = total_of(&[&alice, &bob], timestamp);
+            // the total is rounded once rather than per escrow.
+            assert!(
+                total >= expected && total <= expected + 1,
+                "at {}",
+                timestamp
+            );
+        }
+        assert_eq!(
+            checkpoints.total_voting_power_at_time(&params(), 0),
+            Some(0)
+        );
+
+        // relocking extends the lock and replaces the old slope change.
+        lock(checkpoints, &mut bob, 0, 100 + year + 3600, 100 + 5 * year);
+        assert_eq!(
+            checkpoints
+                .slope_changes
+                .iter()
+                .map(|change| change.time)
+                .collect::<Vec<_>>(),
+            vec![100 + 4 * year, 100 + 5 * year]
+        );
+        let timestamp = 100 + 4 * year;
+        assert_eq!(
+            checkpoints
+                .total_voting_power_at_time(&params(), timestamp)
+                .unwrap(),
+            total_of(&[&alice, &bob], timestamp)
+        );
+
+        // past totals are unchanged by later locks.
+        let timestamp = 100 + year + 7;
+        let total = checkpoints
+            .total_voting_power_at_time(&params(), timestamp)
+            .unwrap();
+        let prev_bob = Escrow {
+            escrow_ends_at: 100 + 2 * year,
+            escrow_started_at: 100 + year,
+            ..bob
+        };
+        let expected = total_of(&[&alice, &prev_bob], timestamp);
+        assert!(total >= expected && total <= expected + 1);
+    }
+
+    #[test]
+    fn test_checkpoint_escrow_removal() {
+        let checkpoints = &mut LockerCheckpoints::default();
+        let mut escrow = Escrow::default();
+        let year = ONE_YEAR as i64;
+        lock(checkpoints, &mut escrow, 1_000_000, 100, 100 + year);
+
+        let prev = params().escrow_checkpoint(&escrow, 100 + year / 2).unwrap();
+        checkpoints
+            .record_escrow_change(prev, EscrowCheckpoint::default(), 100 + year / 2)
+            .unwrap();
+        assert!(checkpoints.slope_changes.is_empty());
+        assert_eq!(
+            checkpoints.total_voting_power_at_time(&params(), 100 + year / 2),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_unchecked_escrow_is_not_removed() {
+        let checkpoints = &mut LockerCheckpoints::default();
+        let mut escrow = Escrow::default();
+        lock(
+            checkpoints,
+            &mut escrow,
+            1_000_000,
+            100,
+            100 + ONE_YEAR as i64,
+        );
+
+        let legacy = Escrow {
+            checkpointed: false,
+            ..escrow
+        };
+        assert_eq!(
+            params().escrow_checkpoint(&legacy, 200).unwrap(),
+            EscrowCheckpoint::default()
+        );
+    }
+
+    #[test]
+    fn test_total_voting_power_follows_params() {
+        let checkpoints = &mut LockerCheckpoints::default();
+        let mut alice = Escrow::default();
+        let mut bob = Escrow::default();
+        let year = ONE_YEAR as i64;
+        lock(checkpoints, &mut alice, 1_000_000, 100, 100 + 4 * year);
+        lock(checkpoints, &mut bob, 3_000_000, 100, 100 + year);
+
+        // a max lock keeps its full power.
+        let prev = params().escrow_checkpoint(&bob, 100 + year / 2).unwrap();
+        bob.max_lock = true;
+        let next = params().escrow_checkpoint(&bob, 100 + year / 2).unwrap();
+        checkpoints
+            .record_escrow_change(prev, next, 100 + year / 2)
+            .unwrap();
+
+        // the params apply to every lock when read, so changing them keeps the total exact.
+        let longer = LockerParams {
+            max_stake_duration: 5 * ONE_YEAR,
+            max_stake_vote_multiplier: 4,
+            ..params()
+        };
+        for params in [params(), longer] {
+            for timestamp in [100 + year / 2, 100 + year, 100 + 3 * year, 100 + 5 * year] {
+                let total = checkpoints
+                    .total_voting_power_at_time(&params, timestamp)
+                    .unwrap();
+                let expected: u64 = [&alice, &bob]
+                    .iter()
+                    .map(|escrow| escrow.voting_power_at_time(&params, timestamp).unwrap())
+                    .sum();
+                assert!(
+                    total >= expected && total <= expected + 1,
+                    "at {}",
+                    timestamp
+                );
+            }
+        }
+
+        // curves other than linear and a baseline cannot be tracked.
+        let exponential = LockerParams {
+            voting_power_curve: VotingPowerCurve::Exponential,
+            ..params()
+        };
+        assert_eq!(
+            checkpoints.total_voting_power_at_time(&exponential, 100 + year),
+            None
+        );
+        let baseline = LockerParams {
+            baseline_power_bps: 1_000,
+            ..params()
+        };
+        assert_eq!(
+            checkpoints.total_voting_power_at_time(&baseline, 100 + year),
+            None
+        );
+    }
+
+    #[test]
+    fn test_total_voting_power_follows_vesting() {
+        let checkpoints = &mut LockerCheckpoints::default();
+        let mut alice = Escrow::default();
+        let mut bob = Escrow::default();
+        let week = crate::locker::SECONDS_PER_WEEK as i64;
+        let now = 100 * week + 5;
+        lock(checkpoints, &mut alice, 1_000_000, now, 200 * week);
+
+        bob.vesting_cliff_at = 120 * week + 3;
+        bob.vesting_amount = 3_000_000;
+        lock(checkpoints, &mut bob, 3_000_000, now, 150 * week);
+        // one slope change per week of unlocking, ending with the lock.
+        assert_eq!(checkpoints.slope_changes.len(), 31);
+
+        let mut timestamps = vec![
+            now,
+            120 * week,
+            120 * week + 3,
+            133 * week + 100,
+            150 * week,
+        ];
+        for timestamp in timestamps.clone() {
+            let total = checkpoints
+                .total_voting_power_at_time(&params(), timestamp)
+                .unwrap();
+            let expected = total_of(&[&alice, &bob], timestamp);
+            assert!(
+                total >= expected && total <= expected + 1,
+                "at {}",
+                timestamp
+            );
+        }
+
+        // withdrawing unlocked tokens leaves the slope changes as they are.
+        let withdrawn_at = 140 * week + 17;
+        let prev = params().escrow_checkpoint(&bob, withdrawn_at).unwrap();
+        bob.amount = bob.locked_amount_at(withdrawn_at).unwrap();
+        let next = params().escrow_checkpoint(&bob, withdrawn_at).unwrap();
+        assert_eq!(prev, next);
+        checkpoints.checkpoint(withdrawn_at).unwrap();
+        let slope_changes = checkpoints.slope_changes.clone();
+        checkpoints
+            .record_escrow_change(prev, next, withdrawn_at)
+            .unwrap();
+        assert_eq!(checkpoints.slope_changes, slope_changes);
+
+        timestamps.push(withdrawn_at);
+        timestamps.push(145 * week + 1);
+        for timestamp in timestamps.into_iter().filter(|ts| *ts >= withdrawn_at) {
+            let total = checkpoints
+                .total_voting_power_at_time(&params(), timestamp)
+                .unwrap();
+            let expected = total_of(&[&alice, &bob], timestamp);
+            assert!(
+                total >= expected && total <= expected + 1,
+                "at {}",
+                timestamp
+            );
+        }
+    }
+
+    #[test]
+    fn test_slope_changes_are_weekly() {
+        let checkpoints = &mut LockerCheckpoints::default();
+        let week = crate::locker::SECONDS_PER_WEEK as i64;
+        let now = 100 * week + 1;
+        // many dust locks ending at different seconds share the weekly slope changes.
+        for i in 0..(3 * LockerCheckpoints::MAX_SLOPE_CHANGES as i64) {
+            let mut escrow = Escrow::default();
+            let ends_at = (now + week + i * 3 * week / 4 + i).min(now + 4 * ONE_YEAR as i64);
+            let ends_at = crate::locker::round_down_to_week(ends_at).unwrap();
+            lock(checkpoints, &mut escrow, 1, now, ends_at);
+        }
+        assert!(checkpoints.slope_changes.len() < LockerCheckpoints::MAX_SLOPE_CHANGES);
+        assert!(checkpoints
+            .slope_changes
+            .iter()
+            .all(|change| change.time % week == 0));
+    }
+
+    #[test]
+    fn test_points_ring_buffer() {
+        let checkpoints = &mut LockerCheckpoints::default();
+        for now in 1..=(LockerCheckpoints::MAX_POINTS as i64 * 2) {
+            checkpoints.checkpoint(now).unwrap();
+        }
+        assert_eq!(checkpoints.points.len(), LockerCheckpoints::MAX_POINTS);
+        assert_eq!(
+            checkpoints.last_point().ts,
+            LockerCheckpoints::MAX_POINTS as i64 * 2
+        );
+        assert_eq!(checkpoints.total_voting_power_at_time(&params(), 1), None);
+    }
+
+    #[test]
+    fn test_tracked_voting_power_splits_the_total() {
+        let checkpoints = &mut LockerCheckpoints::default();
+        let mut alice = Escrow::default();
+        let mut bob = Escrow::default();
+        let year = ONE_YEAR as i64;
+        lock(checkpoints, &mut alice, 1_000_000, 100, 100 + 4 * year);
+        alice.record_lock_checkpoint(100);
+        lock(checkpoints, &mut bob, 3_000_003, 200, 200 + year);
+        bob.record_lock_checkpoint(200);
+
+        // escrows are weighted as the checkpoints count them, whatever the curve,
+        // so their shares never add up to more than the total.
+        let exponential = LockerParams {
+            voting_power_curve: VotingPowerCurve::Exponential,
+            baseline_power_bps: 1_000,
+            ..params()
+        };
+        for params in [params(), exponential] {
+            for timestamp in [150, 200 + year / 3, 200 + 2 * year] {
+                let total = checkpoints
+                    .tracked_voting_power_at_time(&params, timestamp)
+                    .unwrap();
+                let sum: u64 = [&alice, &bob]
+                    .iter()
+                    .map(|escrow| {
+                        escrow
+                            .tracked_voting_power_as_of(&params, timestamp)
+                            .unwrap()
+                    })
+                    .sum();
+                assert!(sum <= total && sum + 2 >= total, "at {}", timestamp);
+            }
+        }
+
+        // locks not yet counted by the checkpoints get no share.
+        let legacy = Escrow {
+            amount: 1_000,
+            escrow_started_at: 100,
+            escrow_ends_at: 100 + year,
+            ..Escrow::default()
+        };
+        assert_eq!(
+            legacy.tracked_voting_power_as_of(&params(), 150).unwrap(),
+            0
+        );
+    }
+}
@@ -22,4 +22,16 @@ pub enum LockedVoterError {
     InvalidRedemptionRate,
     #[msg("Redemption rate must be different from the previous rate.")]
     RedemptionRateSameAsPrevious,
+    #[msg("Vesting escrows cannot be redeemed.")]
+    VestingEscrow,
+    #[msg("Redeem amount must be greater than 0.")]
+    RedeemAmountZero,
+    #[msg("Redeem amount exceeds the eligible amount of the escrow.")]
+    RedeemAmountTooLarge,
+    #[msg("Redemption would not pay out any receipt tokens.")]
+    ReceiptAmountZero,
+    #[msg("Redemption price must be positive and the mint decimals must be compatible.")]
+    InvalidRedemptionPrice,
+    #[msg("The redemption rate does not apply while a redemption price is set.")]
+    RedemptionPriceSet,
 }
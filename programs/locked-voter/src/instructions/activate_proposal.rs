@@ -11,8 +11,9 @@ pub struct ActivateProposal<'info> {
     #[account(mut)]
     pub proposal: Account<'info, Proposal>,
     /// The user's [Escrow].
-    pub escrow: Account<'info, Escrow>,
-    /// The [Escrow]'s owner.
+    pub escrow: Box<Account<'info, Escrow>>,
+    /// The [Escrow]'s owner, or its [Escrow::vote_delegate] while the delegation applies.
+    /// For NFT escrows, the holder of the position token takes the place of the owner.
     pub escrow_owner: Signer<'info>,
     /// The [govern] program.
     pub govern_program: Program<'info, govern::program::Govern>,
@@ -20,7 +21,17 @@ pub struct ActivateProposal<'info> {
 
 impl<'info> ActivateProposal<'info> {
     /// Activates the proposal.
-    pub fn activate_proposal(&mut self) -> Result<()> {
+    ///
+    /// Additional positions of the [Escrow] owner may be passed in as remaining accounts
+    /// to count towards [LockerParams::proposal_activation_min_votes].
+    pub fn activate_proposal(&mut self, positions: &[AccountInfo<'info>]) -> Result<()> {
+        let positions = positions::load_positions(&self.escrow, positions)?;
+        invariant!(
+            self.current_voting_power(&positions)?
+                >= self.locker.params.proposal_activation_min_votes,
+            "insufficient voting power to activate a proposal"
+        );
+
         let seeds: &[&[&[u8]]] = locker_seeds!(self.locker);
 
         govern::cpi::activate_proposal(
@@ -43,25 +54,44 @@ impl<'info> ActivateProposal<'info> {
         }
     }
 
-    /// The current voting power of the escrow.
-    fn current_voting_power(&self) -> Result<u64> {
-        self.escrow.voting_power(&self.locker.params)
+    /// The current voting power of the escrow and its positions.
+    fn current_voting_power(&self, positions: &[Account<Escrow>]) -> Result<u64> {
+        positions::total_voting_power_at_time(
+            &self.locker.params,
+            &self.escrow,
+            positions,
+            Clock::get()?.unix_timestamp,
+        )
     }
 }
 
-impl<'info> Validate<'info> for ActivateProposal<'info> {
-    fn validate(&self) -> Result<()> {
+impl<'info> ActivateProposal<'info> {
+    /// Validates the accounts. `position_tokens` proves who holds an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
         assert_keys_eq!(self.locker, self.governor.electorate);
         assert_keys_eq!(self.governor, self.locker.governor);
         assert_keys_eq!(self.proposal.governor, self.governor);
         assert_keys_eq!(self.escrow.locker, self.locker);
-        assert_keys_eq!(self.escrow.owner, self.escrow_owner);
-
-        invariant!(
-            self.current_voting_power()? >= self.locker.params.proposal_activation_min_votes,
-            "insufficient voting power to activate a proposal"
-        );
+        let authority = self.escrow.authority(position_tokens)?;
+        if self.escrow_owner.key() != authority {
+            let now = Clock::get()?.unix_timestamp;
+            assert_keys_eq!(
+                self.escrow
+                    .active_vote_delegate(authority, self.governor.key(), now),
+                self.escrow_owner,
+                DelegationInactive
+            );
+        }
 
         Ok(())
     }
 }
+
+impl<'info> Validate<'info> for ActivateProposal<'info> {
+    fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+}
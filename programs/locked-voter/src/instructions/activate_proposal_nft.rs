@@ -0,0 +1,25 @@
+//! Handler for [locked_voter::activate_proposal_nft].
+
+use crate::*;
+
+/// Accounts for [locked_voter::activate_proposal_nft].
+#[derive(Accounts)]
+pub struct ActivateProposalNft<'info> {
+    /// Activation accounts. [ActivateProposal::escrow_owner] is the holder of the position token or its delegate.
+    pub activate_proposal: ActivateProposal<'info>,
+    /// Token account holding the position token.
+    pub position_tokens: Account<'info, TokenAccount>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ActivateProposalNft<'info>>) -> Result<()> {
+    ctx.accounts
+        .activate_proposal
+        .activate_proposal(ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for ActivateProposalNft<'info> {
+    fn validate(&self) -> Result<()> {
+        self.activate_proposal
+            .validate_with_position_tokens(Some(&self.position_tokens))
+    }
+}
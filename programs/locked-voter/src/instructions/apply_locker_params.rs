@@ -0,0 +1,61 @@
+//! Handler for [locked_voter::apply_locker_params].
+
+use crate::*;
+
+/// Accounts for [locked_voter::apply_locker_params].
+#[derive(Accounts)]
+pub struct ApplyLockerParams<'info> {
+    /// The [Locker].
+    #[account(mut)]
+    pub locker: Account<'info, Locker>,
+}
+
+impl<'info> ApplyLockerParams<'info> {
+    /// Replaces the [Locker::params] with the [Locker::pending_params].
+    pub fn apply_locker_params(&mut self) -> Result<()> {
+        let locker = &mut self.locker;
+        let prev_params = locker.params;
+        let params = locker.pending_params;
+        locker.params = params;
+        locker.pending_params = LockerParams::default();
+        locker.pending_params_activates_at = 0;
+
+        emit!(ApplyLockerParamsEvent {
+            locker: locker.key(),
+            prev_params,
+            params,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for ApplyLockerParams<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.locker.has_pending_params(), NoPendingParams);
+        self.locker
+            .pending_params
+            .validate_update(&self.locker.params, &self.locker.params_bounds())?;
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            now >= self.locker.pending_params_activates_at,
+            PendingParamsNotActive
+        );
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::apply_locker_params].
+pub struct ApplyLockerParamsEvent {
+    /// The [Locker].
+    #[index]
+    pub locker: Pubkey,
+    /// Previous [LockerParams].
+    pub prev_params: LockerParams,
+    /// New [LockerParams].
+    pub params: LockerParams,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,58 @@
+//! Handler for [locked_voter::cancel_locker_params].
+
+use crate::*;
+
+/// Accounts for [locked_voter::cancel_locker_params].
+#[derive(Accounts)]
+pub struct CancelLockerParams<'info> {
+    /// The [Locker].
+    #[account(mut)]
+    pub locker: Account<'info, Locker>,
+    /// The [Governor].
+    pub governor: Account<'info, Governor>,
+    /// The smart wallet on the [Governor].
+    pub smart_wallet: Signer<'info>,
+}
+
+impl<'info> CancelLockerParams<'info> {
+    /// Discards the [Locker::pending_params].
+    pub fn cancel_locker_params(&mut self) -> Result<()> {
+        let locker = &mut self.locker;
+        let params = locker.pending_params;
+        let activates_at = locker.pending_params_activates_at;
+        locker.pending_params = LockerParams::default();
+        locker.pending_params_activates_at = 0;
+
+        emit!(CancelLockerParamsEvent {
+            locker: locker.key(),
+            params,
+            activates_at,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CancelLockerParams<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.governor, self.locker.governor, "governor mismatch");
+        assert_keys_eq!(self.smart_wallet, self.governor.smart_wallet);
+        invariant!(self.locker.has_pending_params(), NoPendingParams);
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::cancel_locker_params].
+pub struct CancelLockerParamsEvent {
+    /// The [Locker].
+    #[index]
+    pub locker: Pubkey,
+    /// The discarded [LockerParams].
+    pub params: LockerParams,
+    /// When the discarded [LockerParams] would have applied.
+    pub activates_at: i64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,124 @@
+//! Handler for [locked_voter::cast_delegate_votes].
+
+use crate::*;
+use govern::ProposalState;
+
+/// Accounts for [locked_voter::cast_delegate_votes].
+#[derive(Accounts)]
+pub struct CastDelegateVotes<'info> {
+    /// The [Locker].
+    pub locker: Account<'info, Locker>,
+    /// Vote delegate of every [Escrow] in the batch.
+    pub vote_delegate: Signer<'info>,
+
+    /// The [Proposal] being voted on.
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    /// The [Governor].
+    pub governor: Account<'info, Governor>,
+    /// The [govern] program.
+    pub govern_program: Program<'info, govern::program::Govern>,
+}
+
+impl<'info> CastDelegateVotes<'info> {
+    /// Casts a vote with every [Escrow] in `pairs`, a flat list of [Escrow] and [Vote] accounts.
+    ///
+    /// Each [Vote] is still set separately, since the [Vote] of an owner is what prevents
+    /// its voting power from being counted twice. An owner with several positions should be
+    /// voted through [locked_voter::cast_vote], as only one [Escrow] per owner may be in a batch.
+    pub fn cast_delegate_votes(&mut self, side: u8, pairs: &[AccountInfo<'info>]) -> Result<()> {
+        invariant!(
+            !pairs.is_empty() && pairs.len() % 2 == 0,
+            "must provide escrow and vote pairs"
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let governor = self.governor.key();
+        let seeds: &[&[&[u8]]] = locker_seeds!(self.locker);
+
+        let mut voters: Vec<Pubkey> = Vec::with_capacity(pairs.len() / 2);
+        let mut total_weight: u64 = 0;
+        for pair in pairs.chunks(2) {
+            let escrow: Account<Escrow> = Account::try_from(&pair[0])?;
+            let vote: Account<Vote> = Account::try_from(&pair[1])?;
+            assert_keys_eq!(escrow.locker, self.locker);
+            // NFT escrows are voted through cast_vote_nft, as their delegate depends on the holder.
+            assert_keys_eq!(
+                escrow.active_vote_delegate(escrow.authority(None)?, governor, now),
+                self.vote_delegate
+            );
+            assert_keys_eq!(vote.proposal, self.proposal);
+            assert_keys_eq!(vote.voter, escrow.owner);
+            invariant!(
+                !escrow.delegation_changed_since(self.proposal.activated_at),
+                DelegationChangedDuringVote
+            );
+            invariant!(!voters.contains(&vote.voter), DuplicatePosition);
+            voters.push(vote.voter);
+
+            // the share in a VoteDelegation is left to its delegates.
+            let voting_power = unwrap_int!(DelegateShare::apply_bps(
+                escrow.voting_power_as_of(&self.locker.params, self.proposal.activated_at)?,
+                unwrap_int!(escrow.undelegated_bps()),
+            ));
+            if voting_power == 0 {
+                continue;
+            }
+
+            govern::cpi::set_vote(
+                CpiContext::new(
+                    self.govern_program.to_account_info(),
+                    govern::cpi::accounts::SetVote {
+                        governor: self.governor.to_account_info(),
+                        proposal: self.proposal.to_account_info(),
+                        vote: pair[1].clone(),
+                        electorate: self.locker.to_account_info(),
+                    },
+                )
+                .with_signer(seeds),
+                side,
+                voting_power,
+            )?;
+            total_weight = unwrap_int!(total_weight.checked_add(voting_power));
+        }
+
+        emit!(CastDelegateVotesEvent {
+            vote_delegate: self.vote_delegate.key(),
+            proposal: self.proposal.key(),
+            side,
+            num_votes: unwrap_int!(u64::try_from(voters.len()).ok()),
+            total_weight,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CastDelegateVotes<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.locker.governor, self.governor);
+        assert_keys_eq!(self.proposal.governor, self.governor);
+        invariant!(
+            self.proposal.get_state()? == ProposalState::Active,
+            "proposal must be active"
+        );
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::cast_delegate_votes].
+pub struct CastDelegateVotesEvent {
+    /// The vote delegate.
+    #[index]
+    pub vote_delegate: Pubkey,
+    /// The [Proposal].
+    #[index]
+    pub proposal: Pubkey,
+    /// The side voted on.
+    pub side: u8,
+    /// Number of [Vote]s in the batch.
+    pub num_votes: u64,
+    /// Total weight set across the batch.
+    pub total_weight: u64,
+}
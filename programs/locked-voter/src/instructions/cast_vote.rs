@@ -7,9 +7,19 @@ pub struct CastVote<'info> {
     /// The [Locker].
     pub locker: Account<'info, Locker>,
     /// The [Escrow] that is voting.
-    pub escrow: Account<'info, Escrow>,
-    /// Vote delegate of the [Escrow].
+    pub escrow: Box<Account<'info, Escrow>>,
+    /// Vote delegate of the [Escrow], or a delegate in its [VoteDelegation].
     pub vote_delegate: Signer<'info>,
+    /// CHECK: The [VoteDelegation] of the [Escrow]. May be uninitialized if the [Escrow]
+    /// has not split its voting power.
+    #[account(
+        seeds = [
+            b"VoteDelegation".as_ref(),
+            escrow.key().to_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub vote_delegation: UncheckedAccount<'info>,
 
     /// The [Proposal] being voted on.
     #[account(mut)]
@@ -25,8 +35,74 @@ pub struct CastVote<'info> {
 }
 
 impl<'info> CastVote<'info> {
-    pub fn cast_vote(&mut self, side: u8) -> Result<()> {
-        let voting_power = self.future_voting_power()?;
+    /// Casts a vote with the [Escrow] and any additional positions of its owner,
+    /// passed in as remaining accounts.
+    ///
+    /// The [Escrow::vote_delegate] votes the undelegated share of every position.
+    /// Once its [Escrow::vote_delegate_terms] no longer apply, only the owner may vote.
+    /// A delegate in the [VoteDelegation] votes only its share of the [Escrow],
+    /// through its own [Vote], if the [VoteDelegation::terms] applied when the proposal was activated.
+    /// Escrows whose shares changed since then may not vote on the proposal.
+    ///
+    /// For NFT escrows, `position_tokens` is the token account holding the position token,
+    /// whose owner takes the place of the [Escrow::owner].
+    pub fn cast_vote(
+        &mut self,
+        side: u8,
+        positions: &[AccountInfo<'info>],
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let governor = self.governor.key();
+        let activated_at = self.proposal.activated_at;
+        let authority = self.escrow.authority(position_tokens)?;
+        invariant!(
+            !self.escrow.delegation_changed_since(activated_at),
+            DelegationChangedDuringVote
+        );
+        let voting_power = if self.vote_delegate.key()
+            == self.escrow.active_vote_delegate(authority, governor, now)
+        {
+            let positions = positions::load_positions(&self.escrow, positions)?;
+            for position in positions.iter() {
+                assert_keys_eq!(
+                    position.active_vote_delegate(position.authority(None)?, governor, now),
+                    self.vote_delegate
+                );
+                invariant!(
+                    !position.delegation_changed_since(activated_at),
+                    DelegationChangedDuringVote
+                );
+            }
+            assert_keys_eq!(self.vote.voter, self.escrow.owner);
+            self.snapshot_undelegated_voting_power(&positions)?
+        } else {
+            invariant!(
+                positions.is_empty(),
+                "delegates must vote each escrow separately"
+            );
+            invariant!(
+                self.escrow.delegated_bps > 0 && self.escrow.delegation_applies(authority),
+                DelegationInactive
+            );
+            let vote_delegation = self.load_vote_delegation()?;
+            invariant!(
+                vote_delegation.terms.covers(governor, activated_at),
+                DelegationInactive
+            );
+            let bps = unwrap_opt!(
+                vote_delegation.bps_of(self.vote_delegate.key()),
+                NotDelegate
+            );
+            assert_keys_eq!(
+                self.vote.voter,
+                VoteDelegation::delegate_voter(self.escrow.key(), self.vote_delegate.key())
+            );
+            let voting_power = self
+                .escrow
+                .voting_power_as_of(&self.locker.params, activated_at)?;
+            unwrap_int!(DelegateShare::apply_bps(voting_power, bps))
+        };
 
         // zero votes should short circuit.
         if voting_power == 0 {
@@ -49,23 +125,56 @@ impl<'info> CastVote<'info> {
         Ok(())
     }
 
-    /// The voting power of the escrow at the time the proposal's voting ends.
-    fn future_voting_power(&self) -> Result<u64> {
-        Ok(unwrap_int!(self.escrow.voting_power_at_time(
-            &self.locker.params,
-            self.proposal.voting_ends_at
-        )))
+    /// The undelegated voting power of the escrow and its positions when the proposal was activated.
+    /// Locks made after activation do not count towards the vote.
+    ///
+    /// The share of the escrow stays with its delegates if its [VoteDelegation] applied when
+    /// the proposal was activated, even once it expires or the position token changes hands,
+    /// so that votes already cast by delegates are not counted again.
+    /// Shares of positions stay delegated until they are voted separately.
+    fn snapshot_undelegated_voting_power(&self, positions: &[Account<Escrow>]) -> Result<u64> {
+        let activated_at = self.proposal.activated_at;
+        let params = &self.locker.params;
+        let undelegated_bps = if self.escrow.delegated_bps == 0
+            || !self
+                .load_vote_delegation()?
+                .terms
+                .covers(self.governor.key(), activated_at)
+        {
+            crate::locker::BPS_PER_UNIT as u16
+        } else {
+            unwrap_int!(self.escrow.undelegated_bps())
+        };
+        let mut total = unwrap_int!(DelegateShare::apply_bps(
+            self.escrow.voting_power_as_of(params, activated_at)?,
+            undelegated_bps,
+        ));
+        for position in positions {
+            let power = unwrap_int!(DelegateShare::apply_bps(
+                position.voting_power_as_of(params, activated_at)?,
+                unwrap_int!(position.undelegated_bps()),
+            ));
+            total = unwrap_int!(total.checked_add(power));
+        }
+        Ok(total)
+    }
+
+    /// Loads the [VoteDelegation] of the [Escrow].
+    fn load_vote_delegation(&self) -> Result<Account<'info, VoteDelegation>> {
+        let info = self.vote_delegation.to_account_info();
+        invariant!(info.owner == &crate::ID, NotDelegate);
+        let vote_delegation: Account<VoteDelegation> = Account::try_from(&info)?;
+        assert_keys_eq!(vote_delegation.escrow, self.escrow);
+        Ok(vote_delegation)
     }
 }
 
 impl<'info> Validate<'info> for CastVote<'info> {
     fn validate(&self) -> Result<()> {
         assert_keys_eq!(self.escrow.locker, self.locker);
-        assert_keys_eq!(self.escrow.vote_delegate, self.vote_delegate);
         assert_keys_eq!(self.locker.governor, self.governor);
         assert_keys_eq!(self.proposal.governor, self.governor);
         assert_keys_eq!(self.vote.proposal, self.proposal);
-        assert_keys_eq!(self.vote.voter, self.escrow.owner);
         invariant!(
             self.proposal.get_state()? == ProposalState::Active,
             "proposal must be active"
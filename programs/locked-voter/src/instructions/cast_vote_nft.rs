@@ -0,0 +1,26 @@
+//! Handler for [locked_voter::cast_vote_nft].
+
+use crate::*;
+
+/// Accounts for [locked_voter::cast_vote_nft].
+#[derive(Accounts)]
+pub struct CastVoteNft<'info> {
+    /// Vote accounts. [CastVote::vote_delegate] is the holder of the position token or its delegate.
+    pub cast_vote: CastVote<'info>,
+    /// Token account holding the position token.
+    pub position_tokens: Account<'info, TokenAccount>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CastVoteNft<'info>>, side: u8) -> Result<()> {
+    ctx.accounts.cast_vote.cast_vote(
+        side,
+        ctx.remaining_accounts,
+        Some(&ctx.accounts.position_tokens),
+    )
+}
+
+impl<'info> Validate<'info> for CastVoteNft<'info> {
+    fn validate(&self) -> Result<()> {
+        self.cast_vote.validate()
+    }
+}
@@ -0,0 +1,64 @@
+//! Handler for [locked_voter::checkpoint_escrow].
+
+use crate::*;
+
+/// Accounts for [locked_voter::checkpoint_escrow].
+#[derive(Accounts)]
+pub struct CheckpointEscrow<'info> {
+    /// The [Locker] of the [Escrow].
+    pub locker: Account<'info, Locker>,
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
+    /// The [Escrow] to add to the [LockerCheckpoints].
+    #[account(mut, has_one = locker)]
+    pub escrow: Box<Account<'info, Escrow>>,
+}
+
+impl<'info> CheckpointEscrow<'info> {
+    /// Adds the voting power of an [Escrow] created before the [LockerCheckpoints] to them.
+    pub fn checkpoint_escrow(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let escrow = &mut self.escrow;
+        escrow.track_lock_checkpoints();
+        escrow.checkpointed = true;
+        escrow.record_lock_checkpoint(now);
+        let next_checkpoint = unwrap_int!(self.locker.params.escrow_checkpoint(escrow, now));
+        self.checkpoints
+            .record_escrow_change(EscrowCheckpoint::default(), next_checkpoint, now)?;
+
+        emit!(CheckpointEscrowEvent {
+            locker: self.locker.key(),
+            escrow: escrow.key(),
+            escrow_owner: escrow.owner,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CheckpointEscrow<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.checkpoints.locker, self.locker);
+        assert_keys_eq!(self.escrow.locker, self.locker);
+        invariant!(!self.escrow.checkpointed, EscrowAlreadyCheckpointed);
+
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::checkpoint_escrow].
+pub struct CheckpointEscrowEvent {
+    /// The [Locker] of the [Escrow].
+    #[index]
+    pub locker: Pubkey,
+    /// The [Escrow] being checkpointed.
+    pub escrow: Pubkey,
+    /// The owner of the [Escrow].
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
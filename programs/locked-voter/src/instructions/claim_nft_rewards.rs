@@ -0,0 +1,23 @@
+//! Handler for [locked_voter::claim_nft_rewards].
+
+use crate::*;
+
+/// Accounts for [locked_voter::claim_nft_rewards].
+#[derive(Accounts)]
+pub struct ClaimNftRewards<'info> {
+    /// Claim accounts. [ClaimRewards::escrow_owner] is the holder of the position token.
+    pub claim_rewards: ClaimRewards<'info>,
+    /// Token account of the holder containing the position token.
+    pub position_tokens: Account<'info, TokenAccount>,
+}
+
+pub fn handler(ctx: Context<ClaimNftRewards>) -> Result<()> {
+    ctx.accounts.claim_rewards.claim_rewards()
+}
+
+impl<'info> Validate<'info> for ClaimNftRewards<'info> {
+    fn validate(&self) -> Result<()> {
+        self.claim_rewards
+            .validate_with_position_tokens(Some(&self.position_tokens))
+    }
+}
@@ -0,0 +1,118 @@
+//! Handler for [locked_voter::claim_penalty_rewards].
+
+use crate::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token;
+
+/// Accounts for [locked_voter::claim_penalty_rewards].
+#[derive(Accounts)]
+pub struct ClaimPenaltyRewards<'info> {
+    /// The [Locker].
+    #[account(mut)]
+    pub locker: Box<Account<'info, Locker>>,
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
+
+    /// The [Escrow] claiming its share of the penalty pool.
+    #[account(mut, has_one = locker)]
+    pub escrow: Box<Account<'info, Escrow>>,
+    /// Tokens locked up in the [Escrow].
+    #[account(mut, constraint = escrow.tokens == escrow_tokens.key())]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+
+    /// The penalty pool, i.e. the ATA of the [Locker].
+    #[account(mut)]
+    pub pool_tokens: Account<'info, TokenAccount>,
+
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimPenaltyRewards<'info> {
+    /// Adds the penalty rewards of the [Escrow] to its lock, keeping the lock times unchanged.
+    ///
+    /// Anyone may claim, so this only records a [LockCheckpoint] when the [Escrow] is first added
+    /// to the [LockerCheckpoints]; otherwise repeated claims could push out the [LockCheckpoint]s
+    /// that past voting power is read from. The claimed tokens are included from the next lock.
+    pub fn claim_penalty_rewards(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        self.escrow.settle_penalty_rewards(&self.locker)?;
+        let amount = self.escrow.penalty_rewards_owed;
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let seeds: &[&[&[u8]]] = locker_seeds!(self.locker);
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.pool_tokens.to_account_info(),
+                    to: self.escrow_tokens.to_account_info(),
+                    authority: self.locker.to_account_info(),
+                },
+            )
+            .with_signer(seeds),
+            amount,
+        )?;
+
+        let locker = &mut self.locker;
+        let escrow = &mut self.escrow;
+        escrow.penalty_rewards_owed = 0;
+        let prev_checkpoint = unwrap_int!(locker.params.escrow_checkpoint(escrow, now));
+        let escrow_started_at = escrow.escrow_started_at;
+        let escrow_ends_at = escrow.escrow_ends_at;
+        escrow.record_lock_event(locker, amount, escrow_started_at, escrow_ends_at)?;
+        if !escrow.checkpointed {
+            escrow.checkpointed = true;
+            escrow.record_lock_checkpoint(now);
+        }
+        let next_checkpoint = unwrap_int!(locker.params.escrow_checkpoint(escrow, now));
+        self.checkpoints
+            .record_escrow_change(prev_checkpoint, next_checkpoint, now)?;
+
+        emit!(ClaimPenaltyRewardsEvent {
+            locker: locker.key(),
+            escrow_owner: escrow.owner,
+            escrow: escrow.key(),
+            amount,
+            locker_supply: locker.locked_supply,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for ClaimPenaltyRewards<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.escrow.locker, self.locker);
+        assert_keys_eq!(self.checkpoints.locker, self.locker);
+        assert_keys_eq!(self.escrow.tokens, self.escrow_tokens);
+        assert_keys_eq!(
+            self.pool_tokens,
+            get_associated_token_address(&self.locker.key(), &self.locker.token_mint)
+        );
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::claim_penalty_rewards].
+pub struct ClaimPenaltyRewardsEvent {
+    /// The [Locker].
+    #[index]
+    pub locker: Pubkey,
+    /// The owner of the [Escrow].
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// The [Escrow] that claimed.
+    pub escrow: Pubkey,
+    /// Amount of tokens added to the [Escrow].
+    pub amount: u64,
+    /// The amount of tokens locked inside the [Locker].
+    pub locker_supply: u64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,170 @@
+//! Handler for [locked_voter::claim_rewards].
+
+use crate::*;
+use anchor_spl::token;
+
+/// Accounts for [locked_voter::claim_rewards].
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    /// The [Locker].
+    pub locker: Box<Account<'info, Locker>>,
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
+
+    /// The [RewardDistributor].
+    #[account(mut, has_one = locker)]
+    pub distributor: Box<Account<'info, RewardDistributor>>,
+    /// The [RewardEpoch] being claimed from.
+    #[account(mut, has_one = distributor)]
+    pub reward_epoch: Box<Account<'info, RewardEpoch>>,
+    /// Token account holding the rewards of the [RewardDistributor].
+    #[account(mut)]
+    pub distributor_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// The [Escrow] claiming.
+    #[account(has_one = locker)]
+    pub escrow: Box<Account<'info, Escrow>>,
+    /// Authority of the [Escrow].
+    /// For NFT escrows, this is the holder of the position token.
+    pub escrow_owner: Signer<'info>,
+
+    /// The [RewardClaim] of the [Escrow] for the [RewardEpoch].
+    #[account(
+        init,
+        seeds = [
+            b"RewardClaim".as_ref(),
+            reward_epoch.key().to_bytes().as_ref(),
+            escrow.key().to_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + RewardClaim::LEN
+    )]
+    pub claim: Box<Account<'info, RewardClaim>>,
+    /// Destination of the rewards.
+    #[account(mut)]
+    pub destination_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimRewards<'info> {
+    /// Claims the share of the [Escrow] in the rewards of the week,
+    /// in proportion to its voting power at the start of the week.
+    pub fn claim_rewards(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let params = &self.locker.params;
+        let reward_epoch = &mut self.reward_epoch;
+        let start_ts = unwrap_int!(reward_epoch.start_ts());
+        reward_epoch.record_total_voting_power(&self.checkpoints, params)?;
+        // the lock state at the start of the week is used, so locking later does not increase the share.
+        // shares follow the checkpointed voting power, so they add up to the total under any curve.
+        let voting_power = self.escrow.tracked_voting_power_as_of(params, start_ts)?;
+        let amount = unwrap_int!(reward_epoch.share_of(voting_power));
+
+        if amount > 0 {
+            let seeds: &[&[&[u8]]] = reward_distributor_seeds!(self.distributor);
+            token::transfer(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: self.distributor_tokens.to_account_info(),
+                        to: self.destination_tokens.to_account_info(),
+                        authority: self.distributor.to_account_info(),
+                    },
+                )
+                .with_signer(seeds),
+                amount,
+            )?;
+        }
+
+        reward_epoch.claimed_amount = unwrap_int!(reward_epoch.claimed_amount.checked_add(amount));
+        invariant!(
+            reward_epoch.claimed_amount <= reward_epoch.amount,
+            "claimed more than deposited"
+        );
+        let distributor = &mut self.distributor;
+        distributor.total_claimed = unwrap_int!(distributor.total_claimed.checked_add(amount));
+
+        let claim = &mut self.claim;
+        claim.reward_epoch = reward_epoch.key();
+        claim.escrow = self.escrow.key();
+        claim.amount = amount;
+        claim.claimed_at = now;
+
+        emit!(ClaimRewardsEvent {
+            distributor: distributor.key(),
+            reward_epoch: reward_epoch.key(),
+            escrow: self.escrow.key(),
+            escrow_owner: self.escrow.owner,
+            epoch: reward_epoch.epoch,
+            voting_power,
+            amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ClaimRewards<'info> {
+    /// Validates the accounts. `position_tokens` proves authority over an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        assert_keys_eq!(self.checkpoints.locker, self.locker);
+        assert_keys_eq!(self.distributor.locker, self.locker);
+        assert_keys_eq!(self.reward_epoch.distributor, self.distributor);
+        assert_keys_eq!(self.distributor.tokens, self.distributor_tokens);
+        assert_keys_eq!(self.escrow.locker, self.locker);
+        self.escrow
+            .assert_authority(self.escrow_owner.key(), position_tokens)?;
+        assert_keys_eq!(self.destination_tokens.mint, self.distributor.mint);
+
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            unwrap_int!(self.reward_epoch.end_ts()) <= now,
+            RewardEpochNotEnded
+        );
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for ClaimRewards<'info> {
+    fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::claim_rewards].
+pub struct ClaimRewardsEvent {
+    /// The [RewardDistributor].
+    #[index]
+    pub distributor: Pubkey,
+    /// The [RewardEpoch] claimed from.
+    pub reward_epoch: Pubkey,
+    /// The [Escrow] that claimed.
+    pub escrow: Pubkey,
+    /// The owner of the [Escrow].
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// Index of the week.
+    pub epoch: u64,
+    /// Voting power of the [Escrow] at the start of the week.
+    pub voting_power: u64,
+    /// Amount of rewards claimed.
+    pub amount: u64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,116 @@
+//! Handler for [locked_voter::commit_gauge_vote].
+
+use crate::*;
+
+/// Accounts for [locked_voter::commit_gauge_vote].
+#[derive(Accounts)]
+pub struct CommitGaugeVote<'info> {
+    /// The [Gauge].
+    pub gauge: Account<'info, Gauge>,
+    /// The [EpochGauge] receiving the voting power.
+    #[account(mut, has_one = gauge)]
+    pub epoch_gauge: Account<'info, EpochGauge>,
+
+    /// The [GaugeVoter].
+    pub gauge_voter: Account<'info, GaugeVoter>,
+    /// The [GaugeVote] being committed.
+    #[account(has_one = gauge_voter, has_one = gauge)]
+    pub gauge_vote: Account<'info, GaugeVote>,
+    /// The [EpochGaugeVoter] of the [GaugeVoter] for the week.
+    #[account(mut, has_one = gauge_voter)]
+    pub epoch_gauge_voter: Account<'info, EpochGaugeVoter>,
+
+    /// The [EpochGaugeVote] to create.
+    #[account(
+        init,
+        seeds = [
+            b"EpochGaugeVote".as_ref(),
+            gauge_vote.key().to_bytes().as_ref(),
+            epoch_gauge.epoch.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + EpochGaugeVote::LEN
+    )]
+    pub epoch_gauge_vote: Account<'info, EpochGaugeVote>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CommitGaugeVote<'info> {
+    /// Commits the voting power allocated by the [GaugeVote] to the [EpochGauge].
+    pub fn commit_gauge_vote(&mut self) -> Result<()> {
+        let epoch_gauge_voter = &mut self.epoch_gauge_voter;
+        let allocated_power = unwrap_int!(epoch_gauge_voter
+            .power_for_weight(self.gauge_vote.weight, self.gauge_voter.total_weight));
+        epoch_gauge_voter.allocated_power = unwrap_int!(epoch_gauge_voter
+            .allocated_power
+            .checked_add(allocated_power));
+
+        let epoch_gauge = &mut self.epoch_gauge;
+        epoch_gauge.total_power = unwrap_int!(epoch_gauge.total_power.checked_add(allocated_power));
+
+        let epoch_gauge_vote = &mut self.epoch_gauge_vote;
+        epoch_gauge_vote.gauge_vote = self.gauge_vote.key();
+        epoch_gauge_vote.epoch = epoch_gauge.epoch;
+        epoch_gauge_vote.allocated_power = allocated_power;
+
+        emit!(CommitGaugeVoteEvent {
+            gauge: epoch_gauge.gauge,
+            gauge_voter: self.gauge_voter.key(),
+            epoch: epoch_gauge.epoch,
+            allocated_power,
+            total_power: epoch_gauge.total_power,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CommitGaugeVote<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.epoch_gauge.gauge, self.gauge);
+        assert_keys_eq!(self.gauge_vote.gauge, self.gauge);
+        assert_keys_eq!(self.gauge_vote.gauge_voter, self.gauge_voter);
+        assert_keys_eq!(self.epoch_gauge_voter.gauge_voter, self.gauge_voter);
+        invariant!(
+            self.epoch_gauge_voter.epoch == self.epoch_gauge.epoch,
+            "epoch mismatch"
+        );
+        invariant!(!self.gauge.is_disabled, GaugeDisabled);
+
+        // weights are final once the week ends.
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            unwrap_int!(locker::week_end_ts(self.epoch_gauge.epoch)) > now,
+            GaugeEpochEnded
+        );
+
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::commit_gauge_vote].
+pub struct CommitGaugeVoteEvent {
+    /// The [Gauge].
+    #[index]
+    pub gauge: Pubkey,
+    /// The [GaugeVoter].
+    #[index]
+    pub gauge_voter: Pubkey,
+    /// Index of the week.
+    pub epoch: u64,
+    /// Voting power committed.
+    pub allocated_power: u64,
+    /// Total voting power committed to the [Gauge] for the week.
+    pub total_power: u64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
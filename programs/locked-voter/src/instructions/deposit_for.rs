@@ -0,0 +1,184 @@
+//! Handler for [locked_voter::deposit_for].
+
+use crate::*;
+use anchor_lang::{
+    solana_program::{sysvar, sysvar::instructions::get_instruction_relative},
+    system_program,
+};
+use anchor_spl::token;
+
+/// Accounts for [locked_voter::deposit_for].
+#[derive(Accounts)]
+pub struct DepositFor<'info> {
+    /// [Locker].
+    #[account(mut)]
+    pub locker: Account<'info, Locker>,
+
+    /// [Escrow] being funded.
+    #[account(mut, has_one = locker)]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
+
+    /// Token account held by the [Escrow].
+    #[account(mut, constraint = escrow.tokens == escrow_tokens.key())]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+
+    /// Authority of [Self::source_tokens]. Does not need to be related to the [Escrow].
+    pub depositor: Signer<'info>,
+
+    /// The source of deposited tokens.
+    #[account(mut)]
+    pub source_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: The instructions sysvar.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> DepositFor<'info> {
+    /// Adds `amount` tokens to the [Escrow] without changing when it unlocks.
+    ///
+    /// Anyone may deposit, so this only records a [LockCheckpoint] when the [Escrow] is first added
+    /// to the [LockerCheckpoints]; otherwise repeated deposits could push out the [LockCheckpoint]s
+    /// that past voting power is read from. The deposited tokens are included from the next lock.
+    pub fn deposit_for(&mut self, amount: u64) -> Result<()> {
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.source_tokens.to_account_info(),
+                    to: self.escrow_tokens.to_account_info(),
+                    authority: self.depositor.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let locker = &mut self.locker;
+        let escrow = &mut self.escrow;
+        let prev_checkpoint = unwrap_int!(locker.params.escrow_checkpoint(escrow, now));
+        let escrow_started_at = escrow.escrow_started_at;
+        let escrow_ends_at = escrow.escrow_ends_at;
+        escrow.record_lock_event(locker, amount, escrow_started_at, escrow_ends_at)?;
+        if !escrow.checkpointed {
+            escrow.checkpointed = true;
+            escrow.record_lock_checkpoint(now);
+        }
+        let next_checkpoint = unwrap_int!(locker.params.escrow_checkpoint(escrow, now));
+        self.checkpoints
+            .record_escrow_change(prev_checkpoint, next_checkpoint, now)?;
+
+        emit!(DepositForEvent {
+            locker: locker.key(),
+            escrow: escrow.key(),
+            escrow_owner: escrow.owner,
+            depositor: self.depositor.key(),
+            amount,
+            escrow_ends_at,
+            locker_supply: locker.locked_supply,
+        });
+
+        Ok(())
+    }
+
+    /// Checks the whitelist of the [Locker] if it is enabled.
+    ///
+    /// Deposits made via CPI must provide the [LockerWhitelistEntry] of the calling program
+    /// as the only remaining account. Its limits apply to the remaining lock time of the [Escrow].
+    pub fn check_whitelisted(&mut self, ra: &[AccountInfo], amount: u64) -> Result<()> {
+        if !self.locker.params.whitelist_enabled {
+            return Ok(());
+        }
+        let program_id = get_instruction_relative(0, &self.instructions_sysvar)?.program_id;
+        if program_id == crate::ID {
+            return Ok(());
+        }
+
+        invariant!(ra.len() == 1, MustProvideWhitelist);
+        let whitelist_entry_account_info = &ra[0];
+        invariant!(
+            !whitelist_entry_account_info.data_is_empty(),
+            ProgramNotWhitelisted
+        );
+        let mut whitelist_entry =
+            Account::<LockerWhitelistEntry>::try_from(whitelist_entry_account_info)?;
+        assert_keys_eq!(whitelist_entry.locker, self.locker);
+        assert_keys_eq!(
+            whitelist_entry.program_id,
+            program_id,
+            ProgramNotWhitelisted
+        );
+        if whitelist_entry.owner != system_program::ID {
+            assert_keys_eq!(
+                whitelist_entry.owner,
+                self.escrow.owner,
+                EscrowOwnerNotWhitelisted
+            );
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let remaining_duration = if self.escrow.max_lock {
+            unwrap_int!(i64::try_from(self.locker.params.max_stake_duration).ok())
+        } else {
+            unwrap_int!(self.escrow.escrow_ends_at.checked_sub(now))
+        };
+        whitelist_entry.check_stake_duration(&self.locker.params, remaining_duration)?;
+        whitelist_entry.record_lock(amount, now)?;
+        if whitelist_entry.tracks_locked_amount() {
+            self.escrow
+                .record_whitelist_lock(whitelist_entry.key(), amount)?;
+            whitelist_entry.exit(&crate::ID)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for DepositFor<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.locker, self.escrow.locker);
+        assert_keys_eq!(self.checkpoints.locker, self.locker);
+        assert_keys_eq!(self.escrow.tokens, self.escrow_tokens);
+        assert_keys_eq!(self.depositor, self.source_tokens.owner);
+        assert_keys_eq!(self.source_tokens.mint, self.locker.token_mint);
+        assert_keys_neq!(self.escrow_tokens, self.source_tokens);
+        invariant!(!self.escrow.is_vesting(), VestingEscrow);
+
+        // tokens deposited into an expired escrow would be unlocked right away.
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            self.escrow.max_lock || self.escrow.escrow_ends_at > now,
+            EscrowExpired
+        );
+
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::deposit_for].
+pub struct DepositForEvent {
+    /// The [Locker].
+    #[index]
+    pub locker: Pubkey,
+    /// The [Escrow] that was funded.
+    #[index]
+    pub escrow: Pubkey,
+    /// The owner of the [Escrow].
+    pub escrow_owner: Pubkey,
+    /// The account that provided the tokens.
+    pub depositor: Pubkey,
+    /// Amount of tokens added to the [Escrow].
+    pub amount: u64,
+    /// When the [Escrow] unlocks, which is unchanged.
+    pub escrow_ends_at: i64,
+    /// The amount of tokens locked inside the [Locker].
+    pub locker_supply: u64,
+}
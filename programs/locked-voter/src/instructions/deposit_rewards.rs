@@ -0,0 +1,101 @@
+//! Handler for [locked_voter::deposit_rewards].
+
+use crate::*;
+use anchor_spl::token;
+
+/// Accounts for [locked_voter::deposit_rewards].
+#[derive(Accounts)]
+pub struct DepositRewards<'info> {
+    /// The [RewardDistributor].
+    #[account(mut)]
+    pub distributor: Account<'info, RewardDistributor>,
+    /// The [RewardEpoch] being funded.
+    #[account(mut, has_one = distributor)]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    /// Token account holding the rewards of the [RewardDistributor].
+    #[account(mut)]
+    pub distributor_tokens: Account<'info, TokenAccount>,
+
+    /// Depositor of the rewards.
+    pub depositor: Signer<'info>,
+    /// The source of the rewards.
+    #[account(mut)]
+    pub source_tokens: Account<'info, TokenAccount>,
+
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> DepositRewards<'info> {
+    /// Deposits `amount` reward tokens for the week of the [RewardEpoch].
+    pub fn deposit_rewards(&mut self, amount: u64) -> Result<()> {
+        invariant!(amount > 0, "amount must be positive");
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.source_tokens.to_account_info(),
+                    to: self.distributor_tokens.to_account_info(),
+                    authority: self.depositor.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let distributor = &mut self.distributor;
+        let reward_epoch = &mut self.reward_epoch;
+        distributor.total_deposited = unwrap_int!(distributor.total_deposited.checked_add(amount));
+        reward_epoch.amount = unwrap_int!(reward_epoch.amount.checked_add(amount));
+
+        emit!(DepositRewardsEvent {
+            distributor: distributor.key(),
+            reward_epoch: reward_epoch.key(),
+            depositor: self.depositor.key(),
+            epoch: reward_epoch.epoch,
+            amount,
+            epoch_amount: reward_epoch.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for DepositRewards<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.reward_epoch.distributor, self.distributor);
+        assert_keys_eq!(self.distributor.tokens, self.distributor_tokens);
+        assert_keys_eq!(self.source_tokens.mint, self.distributor.mint);
+        assert_keys_eq!(self.source_tokens.owner, self.depositor);
+
+        // shares are only final once no more rewards can be added.
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            unwrap_int!(self.reward_epoch.end_ts()) > now,
+            RewardEpochEnded
+        );
+
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::deposit_rewards].
+pub struct DepositRewardsEvent {
+    /// The [RewardDistributor].
+    #[index]
+    pub distributor: Pubkey,
+    /// The [RewardEpoch] funded.
+    pub reward_epoch: Pubkey,
+    /// Depositor of the rewards.
+    #[index]
+    pub depositor: Pubkey,
+    /// Index of the week.
+    pub epoch: u64,
+    /// Amount of rewards deposited.
+    pub amount: u64,
+    /// Total rewards of the week.
+    pub epoch_amount: u64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,94 @@
+//! Handler for [locked_voter::disable_max_lock].
+
+use crate::*;
+use num_traits::ToPrimitive;
+
+/// Accounts for [locked_voter::disable_max_lock].
+#[derive(Accounts)]
+pub struct DisableMaxLock<'info> {
+    /// The [Locker] of the [Escrow].
+    pub locker: Account<'info, Locker>,
+    /// The [Escrow] in max-lock mode.
+    #[account(mut, has_one = locker)]
+    pub escrow: Box<Account<'info, Escrow>>,
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
+    /// Authority of the [Escrow].
+    pub escrow_owner: Signer<'info>,
+}
+
+impl<'info> DisableMaxLock<'info> {
+    /// Disables max-lock mode on the [Escrow].
+    ///
+    /// The lockup then decays normally, ending [LockerParams::max_stake_duration] from now.
+    pub fn disable_max_lock(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let params = &self.locker.params;
+        let escrow_ends_at = crate::locker::lock_ends_at(
+            now,
+            unwrap_int!(params.max_stake_duration.to_i64()),
+            (params.min_stake_duration, params.max_stake_duration),
+        )?;
+
+        let escrow = &mut self.escrow;
+        let prev_checkpoint = unwrap_int!(self.locker.params.escrow_checkpoint(escrow, now));
+        escrow.track_lock_checkpoints();
+        escrow.max_lock = false;
+        escrow.escrow_ends_at = escrow_ends_at;
+        escrow.checkpointed = true;
+        escrow.record_lock_checkpoint(now);
+        let next_checkpoint = unwrap_int!(self.locker.params.escrow_checkpoint(escrow, now));
+        self.checkpoints
+            .record_escrow_change(prev_checkpoint, next_checkpoint, now)?;
+
+        emit!(DisableMaxLockEvent {
+            locker: self.locker.key(),
+            escrow_owner: escrow.owner,
+            escrow: escrow.key(),
+            escrow_ends_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> DisableMaxLock<'info> {
+    /// Validates the accounts. `position_tokens` proves authority over an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        assert_keys_eq!(self.locker, self.escrow.locker);
+        assert_keys_eq!(self.checkpoints.locker, self.locker);
+        self.escrow
+            .assert_authority(self.escrow_owner.key(), position_tokens)?;
+        invariant!(self.escrow.max_lock, MaxLockNotEnabled);
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for DisableMaxLock<'info> {
+    fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::disable_max_lock].
+pub struct DisableMaxLockEvent {
+    /// The [Locker] of the [Escrow].
+    #[index]
+    pub locker: Pubkey,
+    /// The owner of the [Escrow].
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// The [Escrow].
+    pub escrow: Pubkey,
+    /// When the [Escrow] now unlocks.
+    pub escrow_ends_at: i64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
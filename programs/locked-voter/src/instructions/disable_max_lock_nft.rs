@@ -0,0 +1,23 @@
+//! Handler for [locked_voter::disable_max_lock_nft].
+
+use crate::*;
+
+/// Accounts for [locked_voter::disable_max_lock_nft].
+#[derive(Accounts)]
+pub struct DisableMaxLockNft<'info> {
+    /// Max lock accounts. [DisableMaxLock::escrow_owner] is the holder of the position token.
+    pub disable_max_lock: DisableMaxLock<'info>,
+    /// Token account of the holder containing the position token.
+    pub position_tokens: Account<'info, TokenAccount>,
+}
+
+pub fn handler(ctx: Context<DisableMaxLockNft>) -> Result<()> {
+    ctx.accounts.disable_max_lock.disable_max_lock()
+}
+
+impl<'info> Validate<'info> for DisableMaxLockNft<'info> {
+    fn validate(&self) -> Result<()> {
+        self.disable_max_lock
+            .validate_with_position_tokens(Some(&self.position_tokens))
+    }
+}
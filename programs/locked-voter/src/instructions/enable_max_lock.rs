@@ -0,0 +1,88 @@
+//! Handler for [locked_voter::enable_max_lock].
+
+use crate::*;
+
+/// Accounts for [locked_voter::enable_max_lock].
+#[derive(Accounts)]
+pub struct EnableMaxLock<'info> {
+    /// The [Locker] of the [Escrow].
+    pub locker: Account<'info, Locker>,
+    /// The [Escrow] to put into max-lock mode.
+    #[account(mut, has_one = locker)]
+    pub escrow: Box<Account<'info, Escrow>>,
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
+    /// Authority of the [Escrow].
+    pub escrow_owner: Signer<'info>,
+}
+
+impl<'info> EnableMaxLock<'info> {
+    /// Enables max-lock mode on the [Escrow].
+    pub fn enable_max_lock(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let escrow = &mut self.escrow;
+        let prev_checkpoint = unwrap_int!(self.locker.params.escrow_checkpoint(escrow, now));
+        escrow.track_lock_checkpoints();
+        escrow.max_lock = true;
+        // an expired lock is relocked starting now.
+        if escrow.escrow_ends_at <= now {
+            escrow.escrow_started_at = now;
+        }
+        escrow.checkpointed = true;
+        escrow.record_lock_checkpoint(now);
+        let next_checkpoint = unwrap_int!(self.locker.params.escrow_checkpoint(escrow, now));
+        self.checkpoints
+            .record_escrow_change(prev_checkpoint, next_checkpoint, now)?;
+
+        emit!(EnableMaxLockEvent {
+            locker: self.locker.key(),
+            escrow_owner: escrow.owner,
+            escrow: escrow.key(),
+            amount: escrow.amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> EnableMaxLock<'info> {
+    /// Validates the accounts. `position_tokens` proves authority over an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        assert_keys_eq!(self.locker, self.escrow.locker);
+        assert_keys_eq!(self.checkpoints.locker, self.locker);
+        self.escrow
+            .assert_authority(self.escrow_owner.key(), position_tokens)?;
+        invariant!(!self.escrow.max_lock, MaxLockEnabled);
+        invariant!(!self.escrow.is_vesting(), VestingEscrow);
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for EnableMaxLock<'info> {
+    fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::enable_max_lock].
+pub struct EnableMaxLockEvent {
+    /// The [Locker] of the [Escrow].
+    #[index]
+    pub locker: Pubkey,
+    /// The owner of the [Escrow].
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// The [Escrow].
+    pub escrow: Pubkey,
+    /// Amount of tokens in the [Escrow].
+    pub amount: u64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,23 @@
+//! Handler for [locked_voter::enable_max_lock_nft].
+
+use crate::*;
+
+/// Accounts for [locked_voter::enable_max_lock_nft].
+#[derive(Accounts)]
+pub struct EnableMaxLockNft<'info> {
+    /// Max lock accounts. [EnableMaxLock::escrow_owner] is the holder of the position token.
+    pub enable_max_lock: EnableMaxLock<'info>,
+    /// Token account of the holder containing the position token.
+    pub position_tokens: Account<'info, TokenAccount>,
+}
+
+pub fn handler(ctx: Context<EnableMaxLockNft>) -> Result<()> {
+    ctx.accounts.enable_max_lock.enable_max_lock()
+}
+
+impl<'info> Validate<'info> for EnableMaxLockNft<'info> {
+    fn validate(&self) -> Result<()> {
+        self.enable_max_lock
+            .validate_with_position_tokens(Some(&self.position_tokens))
+    }
+}
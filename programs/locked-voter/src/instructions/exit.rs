@@ -10,9 +10,14 @@ pub struct Exit<'info> {
 
     /// The [Escrow] that is being closed.
     #[account(mut, has_one = locker, close = payer)]
-    pub escrow: Account<'info, Escrow>,
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
 
     /// Authority of the [Escrow].
+    /// For NFT escrows, this is the holder of the position token.
     pub escrow_owner: Signer<'info>,
     /// Tokens locked up in the [Escrow].
     #[account(mut, constraint = escrow.tokens == escrow_tokens.key())]
@@ -30,7 +35,12 @@ pub struct Exit<'info> {
 }
 
 impl<'info> Exit<'info> {
-    pub fn exit(&mut self) -> Result<()> {
+    /// Withdraws all tokens and closes the [Escrow].
+    ///
+    /// If the [Escrow] has a [Escrow::whitelist_locked_amount], its [Escrow::whitelist_entry] must be in `ra`.
+    pub fn exit(&mut self, ra: &[AccountInfo]) -> Result<()> {
+        release_from_whitelist_entry(&mut self.escrow, ra, 0)?;
+
         let seeds: &[&[&[u8]]] = escrow_seeds!(self.escrow);
 
         // transfer tokens from the escrow
@@ -54,11 +64,17 @@ impl<'info> Exit<'info> {
         let locker = &mut self.locker;
         locker.locked_supply = unwrap_int!(locker.locked_supply.checked_sub(self.escrow.amount));
 
+        // the lock has expired, so this only brings the checkpoints up to date.
+        let now = Clock::get()?.unix_timestamp;
+        let prev_checkpoint = unwrap_int!(locker.params.escrow_checkpoint(&self.escrow, now));
+        self.checkpoints
+            .record_escrow_change(prev_checkpoint, EscrowCheckpoint::default(), now)?;
+
         emit!(ExitEscrowEvent {
             escrow_owner: self.escrow.owner,
             locker: locker.key(),
             locker_supply: locker.locked_supply,
-            timestamp: Clock::get()?.unix_timestamp,
+            timestamp: now,
             released_amount: self.escrow.amount,
         });
 
@@ -66,11 +82,13 @@ impl<'info> Exit<'info> {
     }
 }
 
-impl<'info> Validate<'info> for Exit<'info> {
-    fn validate(&self) -> Result<()> {
-        assert_keys_eq!(self.locker, self.escrow.locker);
-        assert_keys_eq!(self.escrow.owner, self.escrow_owner);
-        assert_keys_eq!(self.escrow.tokens, self.escrow_tokens);
+impl<'info> Exit<'info> {
+    /// Validates the accounts. `position_tokens` proves authority over an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        self.validate_accounts(position_tokens)?;
         let now = Clock::get()?.unix_timestamp;
         msg!(
             "now: {}; escrow_ends_at: {}",
@@ -79,12 +97,35 @@ impl<'info> Validate<'info> for Exit<'info> {
         );
         invariant!(self.escrow.escrow_ends_at < now, EscrowNotEnded);
 
+        Ok(())
+    }
+
+    /// Validates the accounts, regardless of whether the [Escrow] has unlocked.
+    pub fn validate_accounts(&self, position_tokens: Option<&TokenAccount>) -> Result<()> {
+        assert_keys_eq!(self.locker, self.escrow.locker);
+        assert_keys_eq!(self.checkpoints.locker, self.locker);
+        self.escrow
+            .assert_authority(self.escrow_owner.key(), position_tokens)?;
+        assert_keys_eq!(self.escrow.tokens, self.escrow_tokens);
+        invariant!(!self.escrow.max_lock, MaxLockEnabled);
+        // closing the escrow would forfeit its share of the penalty pool.
+        invariant!(
+            unwrap_int!(self.escrow.pending_penalty_rewards(&self.locker)) == 0,
+            MustClaimPenaltyRewards
+        );
+
         assert_keys_neq!(self.escrow_tokens, self.destination_tokens);
 
         Ok(())
     }
 }
 
+impl<'info> Validate<'info> for Exit<'info> {
+    fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+}
+
 #[event]
 /// Event called in [locked_voter::exit].
 pub struct ExitEscrowEvent {
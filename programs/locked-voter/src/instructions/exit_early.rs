@@ -0,0 +1,152 @@
+//! Handler for [locked_voter::exit_early].
+
+use crate::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token;
+
+/// Accounts for [locked_voter::exit_early].
+#[derive(Accounts)]
+pub struct ExitEarly<'info> {
+    /// Exit accounts.
+    pub exit: Exit<'info>,
+    /// Destination of the penalty. This is either the
+    /// [LockerParams::early_exit_penalty_recipient] or the penalty pool,
+    /// i.e. the ATA of the [Locker].
+    #[account(mut)]
+    pub penalty_tokens: Account<'info, TokenAccount>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ExitEarly<'info>>) -> Result<()> {
+    ctx.accounts.exit_early(ctx.remaining_accounts)
+}
+
+impl<'info> ExitEarly<'info> {
+    /// Exits the [Escrow] before it unlocks, paying the early exit penalty.
+    ///
+    /// If the [Escrow] has a [Escrow::whitelist_locked_amount], its [Escrow::whitelist_entry] must be in `ra`.
+    pub fn exit_early(&mut self, ra: &[AccountInfo]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let exit = &mut self.exit;
+        release_from_whitelist_entry(&mut exit.escrow, ra, 0)?;
+        let amount = exit.escrow.amount;
+        let penalty = unwrap_int!(exit
+            .locker
+            .params
+            .calculate_early_exit_penalty(&exit.escrow, now));
+        let released_amount = unwrap_int!(amount.checked_sub(penalty));
+
+        let seeds: &[&[&[u8]]] = escrow_seeds!(exit.escrow);
+        if released_amount > 0 {
+            token::transfer(
+                CpiContext::new(
+                    exit.token_program.to_account_info(),
+                    token::Transfer {
+                        from: exit.escrow_tokens.to_account_info(),
+                        to: exit.destination_tokens.to_account_info(),
+                        authority: exit.escrow.to_account_info(),
+                    },
+                )
+                .with_signer(seeds),
+                released_amount,
+            )?;
+        }
+        if penalty > 0 {
+            token::transfer(
+                CpiContext::new(
+                    exit.token_program.to_account_info(),
+                    token::Transfer {
+                        from: exit.escrow_tokens.to_account_info(),
+                        to: self.penalty_tokens.to_account_info(),
+                        authority: exit.escrow.to_account_info(),
+                    },
+                )
+                .with_signer(seeds),
+                penalty,
+            )?;
+        }
+
+        // update the escrow and locker
+        let prev_checkpoint = unwrap_int!(exit.locker.params.escrow_checkpoint(&exit.escrow, now));
+        exit.escrow.record_unlock_event(&mut exit.locker, amount)?;
+        exit.checkpoints
+            .record_escrow_change(prev_checkpoint, EscrowCheckpoint::default(), now)?;
+        // the exiting escrow does not share in its own penalty.
+        if exit.locker.penalties_go_to_pool() {
+            unwrap_int!(exit.locker.distribute_penalty_rewards(penalty));
+        }
+
+        emit!(ExitEarlyEvent {
+            escrow_owner: exit.escrow.owner,
+            locker: exit.locker.key(),
+            escrow: exit.escrow.key(),
+            timestamp: now,
+            locker_supply: exit.locker.locked_supply,
+            escrow_ends_at: exit.escrow.escrow_ends_at,
+            released_amount,
+            penalty,
+            penalty_tokens: self.penalty_tokens.key(),
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ExitEarly<'info> {
+    /// Validates the accounts. `position_tokens` proves authority over an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        let locker = &self.exit.locker;
+        invariant!(locker.params.early_exit_penalty_bps > 0, EarlyExitDisabled);
+        self.exit.validate_accounts(position_tokens)?;
+        invariant!(!self.exit.escrow.is_vesting(), VestingEscrow);
+
+        if locker.penalties_go_to_pool() {
+            assert_keys_eq!(
+                self.penalty_tokens,
+                get_associated_token_address(&locker.key(), &locker.token_mint)
+            );
+        } else {
+            assert_keys_eq!(
+                self.penalty_tokens,
+                locker.params.early_exit_penalty_recipient
+            );
+        }
+        assert_keys_eq!(self.penalty_tokens.mint, locker.token_mint);
+        assert_keys_neq!(self.penalty_tokens, self.exit.escrow_tokens);
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for ExitEarly<'info> {
+    fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::exit_early].
+pub struct ExitEarlyEvent {
+    /// The owner of the [Escrow].
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// The locker for the [Escrow].
+    #[index]
+    pub locker: Pubkey,
+    /// The [Escrow] that was exited.
+    pub escrow: Pubkey,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+    /// The amount of tokens locked inside the [Locker].
+    pub locker_supply: u64,
+    /// When the [Escrow] would have unlocked.
+    pub escrow_ends_at: i64,
+    /// The amount released to the owner of the [Escrow].
+    pub released_amount: u64,
+    /// The penalty paid.
+    pub penalty: u64,
+    /// The token account receiving the penalty.
+    pub penalty_tokens: Pubkey,
+}
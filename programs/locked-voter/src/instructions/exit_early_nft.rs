@@ -0,0 +1,47 @@
+//! Handler for [locked_voter::exit_early_nft].
+
+use crate::*;
+use anchor_spl::token;
+
+/// Accounts for [locked_voter::exit_early_nft].
+#[derive(Accounts)]
+pub struct ExitEarlyNft<'info> {
+    /// Early exit accounts. [Exit::escrow_owner] is the holder of the position token.
+    pub exit_early: ExitEarly<'info>,
+    /// Token account of the holder containing the position token.
+    #[account(mut)]
+    pub position_tokens: Account<'info, TokenAccount>,
+    /// Mint of the position token.
+    #[account(mut)]
+    pub position_mint: Account<'info, Mint>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ExitEarlyNft<'info>>) -> Result<()> {
+    ctx.accounts.exit_early.exit_early(ctx.remaining_accounts)?;
+
+    // the position no longer represents any tokens, so burn it.
+    let exit = &ctx.accounts.exit_early.exit;
+    token::burn(
+        CpiContext::new(
+            exit.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.position_mint.to_account_info(),
+                from: ctx.accounts.position_tokens.to_account_info(),
+                authority: exit.escrow_owner.to_account_info(),
+            },
+        ),
+        1,
+    )
+}
+
+impl<'info> Validate<'info> for ExitEarlyNft<'info> {
+    fn validate(&self) -> Result<()> {
+        self.exit_early
+            .validate_with_position_tokens(Some(&self.position_tokens))?;
+        assert_keys_eq!(
+            self.position_mint,
+            self.exit_early.exit.escrow.position_mint
+        );
+        Ok(())
+    }
+}
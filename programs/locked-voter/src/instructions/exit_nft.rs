@@ -0,0 +1,43 @@
+//! Handler for [locked_voter::exit_nft].
+
+use crate::*;
+use anchor_spl::token;
+
+/// Accounts for [locked_voter::exit_nft].
+#[derive(Accounts)]
+pub struct ExitNft<'info> {
+    /// Exit accounts. [Exit::escrow_owner] is the holder of the position token.
+    pub exit: Exit<'info>,
+    /// Token account of the holder containing the position token.
+    #[account(mut)]
+    pub position_tokens: Account<'info, TokenAccount>,
+    /// Mint of the position token.
+    #[account(mut)]
+    pub position_mint: Account<'info, Mint>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ExitNft<'info>>) -> Result<()> {
+    Exit::exit(&mut ctx.accounts.exit, ctx.remaining_accounts)?;
+
+    // the position no longer represents any tokens, so burn it.
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.exit.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.position_mint.to_account_info(),
+                from: ctx.accounts.position_tokens.to_account_info(),
+                authority: ctx.accounts.exit.escrow_owner.to_account_info(),
+            },
+        ),
+        1,
+    )
+}
+
+impl<'info> Validate<'info> for ExitNft<'info> {
+    fn validate(&self) -> Result<()> {
+        self.exit
+            .validate_with_position_tokens(Some(&self.position_tokens))?;
+        assert_keys_eq!(self.position_mint, self.exit.escrow.position_mint);
+        Ok(())
+    }
+}
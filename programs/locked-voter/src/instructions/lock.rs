@@ -4,7 +4,6 @@ use anchor_lang::solana_program::{
     sysvar::{self, instructions::get_instruction_relative},
 };
 use anchor_spl::token;
-use num_traits::ToPrimitive;
 
 /// Accounts for [locked_voter::lock].
 #[derive(Accounts)]
@@ -15,7 +14,11 @@ pub struct Lock<'info> {
 
     /// [Escrow].
     #[account(mut, has_one = locker)]
-    pub escrow: Account<'info, Escrow>,
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
 
     /// Token account held by the [Escrow].
     #[account(
@@ -25,6 +28,7 @@ pub struct Lock<'info> {
     pub escrow_tokens: Account<'info, TokenAccount>,
 
     /// Authority of the [Escrow] and [Self::source_tokens].
+    /// For NFT escrows, this is the holder of the position token.
     pub escrow_owner: Signer<'info>,
 
     /// The source of deposited tokens.
@@ -36,21 +40,34 @@ pub struct Lock<'info> {
 }
 
 impl<'info> Lock<'info> {
+    /// Locks `amount` tokens until `duration` seconds from now, rounded down to the start of the week.
+    /// The rounded duration must be within the stake duration bounds.
     pub fn lock(&mut self, amount: u64, duration: i64) -> Result<()> {
-        invariant!(
-            unwrap_int!(duration.to_u64()) >= self.locker.params.min_stake_duration,
-            LockupDurationTooShort
-        );
-        invariant!(
-            unwrap_int!(duration.to_u64()) <= self.locker.params.max_stake_duration,
-            LockupDurationTooLong
-        );
+        let params = &self.locker.params;
+        self.lock_with_stake_duration_bounds(
+            amount,
+            duration,
+            (params.min_stake_duration, params.max_stake_duration),
+        )
+    }
 
+    /// Locks `amount` tokens like [Lock::lock], with the `(min, max)` stake durations of
+    /// a [LockerWhitelistEntry] instead of those of the [LockerParams].
+    pub fn lock_with_stake_duration_bounds(
+        &mut self,
+        amount: u64,
+        duration: i64,
+        (min_stake_duration, max_stake_duration): (u64, u64),
+    ) -> Result<()> {
         // check that the escrow refresh is valid
         let escrow = &self.escrow;
         let prev_escrow_ends_at = escrow.escrow_ends_at;
         let next_escrow_started_at = Clock::get()?.unix_timestamp;
-        let next_escrow_ends_at = unwrap_int!(next_escrow_started_at.checked_add(duration));
+        let next_escrow_ends_at = crate::locker::lock_ends_at(
+            next_escrow_started_at,
+            duration,
+            (min_stake_duration, max_stake_duration),
+        )?;
         if prev_escrow_ends_at > next_escrow_ends_at {
             msg!(
                 "next_escrow_ends_at: {}; prev_escrow_ends_at: {}",
@@ -84,7 +101,20 @@ impl<'info> Lock<'info> {
 
         let locker = &mut self.locker;
         let escrow = &mut self.escrow;
+        let prev_checkpoint = unwrap_int!(locker
+            .params
+            .escrow_checkpoint(escrow, next_escrow_started_at));
         escrow.record_lock_event(locker, amount, next_escrow_started_at, next_escrow_ends_at)?;
+        escrow.checkpointed = true;
+        escrow.record_lock_checkpoint(next_escrow_started_at);
+        let next_checkpoint = unwrap_int!(locker
+            .params
+            .escrow_checkpoint(escrow, next_escrow_started_at));
+        self.checkpoints.record_escrow_change(
+            prev_checkpoint,
+            next_checkpoint,
+            next_escrow_started_at,
+        )?;
 
         emit!(LockEvent {
             locker: locker.key(),
@@ -101,14 +131,19 @@ impl<'info> Lock<'info> {
         Ok(())
     }
 
-    pub fn check_whitelisted(&self, ra: &[AccountInfo]) -> Result<()> {
+    /// Checks that the CPI caller is whitelisted and that the lock is within the limits of its
+    /// [LockerWhitelistEntry], which must be writable if it tracks the locked amount.
+    ///
+    /// Returns the `(min, max)` stake durations of the lock.
+    pub fn check_whitelisted(&mut self, ra: &[AccountInfo], amount: u64) -> Result<(u64, u64)> {
         invariant!(ra.len() == 2, MustProvideWhitelist);
         let accounts_iter = &mut ra.iter();
         let ix_sysvar_account_info = next_account_info(accounts_iter)?;
         assert_keys_eq!(ix_sysvar_account_info.key(), sysvar::instructions::ID);
         let program_id = get_instruction_relative(0, ix_sysvar_account_info)?.program_id;
         if program_id == crate::ID {
-            return Ok(());
+            let params = &self.locker.params;
+            return Ok((params.min_stake_duration, params.max_stake_duration));
         }
 
         let whitelist_entry_account_info = next_account_info(accounts_iter)?;
@@ -116,7 +151,7 @@ impl<'info> Lock<'info> {
             !whitelist_entry_account_info.data_is_empty(),
             ProgramNotWhitelisted
         );
-        let whitelist_entry =
+        let mut whitelist_entry =
             Account::<LockerWhitelistEntry>::try_from(whitelist_entry_account_info)?;
         assert_keys_eq!(whitelist_entry.locker, self.locker);
         assert_keys_eq!(whitelist_entry.program_id, program_id);
@@ -127,17 +162,30 @@ impl<'info> Lock<'info> {
                 EscrowOwnerNotWhitelisted
             );
         }
+        whitelist_entry.record_lock(amount, Clock::get()?.unix_timestamp)?;
+        if whitelist_entry.tracks_locked_amount() {
+            self.escrow
+                .record_whitelist_lock(whitelist_entry.key(), amount)?;
+            whitelist_entry.exit(&crate::ID)?;
+        }
 
-        Ok(())
+        Ok(whitelist_entry.stake_duration_bounds(&self.locker.params))
     }
 }
 
-impl<'info> Validate<'info> for Lock<'info> {
-    fn validate(&self) -> Result<()> {
+impl<'info> Lock<'info> {
+    /// Validates the accounts. `position_tokens` proves authority over an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
         assert_keys_eq!(self.locker, self.escrow.locker);
+        assert_keys_eq!(self.checkpoints.locker, self.locker);
         assert_keys_eq!(self.escrow.tokens, self.escrow_tokens);
-        assert_keys_eq!(self.escrow.owner, self.escrow_owner);
+        self.escrow
+            .assert_authority(self.escrow_owner.key(), position_tokens)?;
         assert_keys_eq!(self.escrow_owner, self.source_tokens.owner);
+        invariant!(!self.escrow.is_vesting(), VestingEscrow);
 
         assert_keys_eq!(self.source_tokens.mint, self.locker.token_mint);
         assert_keys_neq!(self.escrow_tokens, self.source_tokens);
@@ -146,6 +194,12 @@ impl<'info> Validate<'info> for Lock<'info> {
     }
 }
 
+impl<'info> Validate<'info> for Lock<'info> {
+    fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+}
+
 #[event]
 /// Event called in [locked_voter::lock].
 pub struct LockEvent {
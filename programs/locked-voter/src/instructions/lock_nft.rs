@@ -0,0 +1,41 @@
+//! Handler for [locked_voter::lock_nft].
+
+use crate::*;
+
+use anchor_lang::{
+    solana_program::sysvar, solana_program::sysvar::instructions::get_instruction_relative,
+};
+
+/// Accounts for [locked_voter::lock_nft].
+#[derive(Accounts)]
+pub struct LockNft<'info> {
+    /// Lock accounts. [Lock::escrow_owner] is the holder of the position token.
+    pub lock: Lock<'info>,
+    /// Token account of the holder containing the position token.
+    pub position_tokens: Account<'info, TokenAccount>,
+    /// CHECK: The instructions sysvar.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, LockNft<'info>>,
+    amount: u64,
+    duration: i64,
+) -> Result<()> {
+    ctx.accounts.lock.lock(amount, duration)
+}
+
+impl<'info> Validate<'info> for LockNft<'info> {
+    fn validate(&self) -> Result<()> {
+        self.lock
+            .validate_with_position_tokens(Some(&self.position_tokens))?;
+
+        // NFT escrows may not be locked into via CPI if there is a whitelist.
+        if self.lock.locker.params.whitelist_enabled {
+            let program_id = get_instruction_relative(0, &self.instructions_sysvar)?.program_id;
+            invariant!(program_id == crate::ID, ProgramNotWhitelisted);
+        }
+        Ok(())
+    }
+}
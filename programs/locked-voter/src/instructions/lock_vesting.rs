@@ -0,0 +1,111 @@
+//! Handler for [locked_voter::lock_vesting].
+
+use crate::*;
+use anchor_lang::solana_program::{sysvar, sysvar::instructions::get_instruction_relative};
+use anchor_spl::token;
+
+/// Accounts for [locked_voter::lock_vesting].
+#[derive(Accounts)]
+pub struct LockVesting<'info> {
+    /// Lock accounts. The [Escrow] must be empty.
+    pub lock: Lock<'info>,
+    /// CHECK: The instructions sysvar.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+impl<'info> LockVesting<'info> {
+    /// Locks `amount` tokens for `duration` seconds, unlocking linearly
+    /// from `cliff_duration` seconds after now until the end of the lock.
+    pub fn lock_vesting(&mut self, amount: u64, cliff_duration: i64, duration: i64) -> Result<()> {
+        invariant!(
+            cliff_duration >= 0 && cliff_duration <= duration,
+            InvalidVestingSchedule
+        );
+        invariant!(amount > 0, InvalidVestingSchedule);
+
+        token::transfer(
+            CpiContext::new(
+                self.lock.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.lock.source_tokens.to_account_info(),
+                    to: self.lock.escrow_tokens.to_account_info(),
+                    authority: self.lock.escrow_owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        let now = Clock::get()?.unix_timestamp;
+        let params = &self.lock.locker.params;
+        let escrow_ends_at = crate::locker::lock_ends_at(
+            now,
+            duration,
+            (params.min_stake_duration, params.max_stake_duration),
+        )?;
+        let vesting_cliff_at = unwrap_int!(now.checked_add(cliff_duration)).min(escrow_ends_at);
+
+        let locker = &mut self.lock.locker;
+        let escrow = &mut self.lock.escrow;
+        let prev_checkpoint = unwrap_int!(locker.params.escrow_checkpoint(escrow, now));
+        escrow.record_lock_event(locker, amount, now, escrow_ends_at)?;
+        escrow.vesting_cliff_at = vesting_cliff_at;
+        escrow.vesting_amount = amount;
+        escrow.checkpointed = true;
+        escrow.record_lock_checkpoint(now);
+        let next_checkpoint = unwrap_int!(locker.params.escrow_checkpoint(escrow, now));
+        self.lock
+            .checkpoints
+            .record_escrow_change(prev_checkpoint, next_checkpoint, now)?;
+
+        emit!(LockVestingEvent {
+            locker: locker.key(),
+            escrow_owner: escrow.owner,
+            amount,
+            locker_supply: locker.locked_supply,
+            escrow_started_at: now,
+            vesting_cliff_at,
+            escrow_ends_at,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for LockVesting<'info> {
+    fn validate(&self) -> Result<()> {
+        self.lock.validate()?;
+        let escrow = &self.lock.escrow;
+        invariant!(
+            escrow.amount == 0 && !escrow.max_lock,
+            VestingEscrowNotEmpty
+        );
+
+        // vesting locks may not be created via CPI if there is a whitelist.
+        if self.lock.locker.params.whitelist_enabled {
+            let program_id = get_instruction_relative(0, &self.instructions_sysvar)?.program_id;
+            invariant!(program_id == crate::ID, ProgramNotWhitelisted);
+        }
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::lock_vesting].
+pub struct LockVestingEvent {
+    /// The locker of the [Escrow].
+    #[index]
+    pub locker: Pubkey,
+    /// The owner of the [Escrow].
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// Amount of tokens locked into the vesting schedule.
+    pub amount: u64,
+    /// Amount of tokens locked inside the [Locker].
+    pub locker_supply: u64,
+    /// When the lock started.
+    pub escrow_started_at: i64,
+    /// When the tokens start to unlock.
+    pub vesting_cliff_at: i64,
+    /// When all tokens are unlocked.
+    pub escrow_ends_at: i64,
+}
@@ -11,6 +11,8 @@ pub struct LockWithWhitelistEntry<'info> {
     /// CHECK: The instructions sysvar.
     #[account(address = sysvar::instructions::ID)]
     pub instructions_sysvar: AccountInfo<'info>,
+    /// The [LockerWhitelistEntry].
+    #[account(mut)]
     pub whitelist_entry: Account<'info, LockerWhitelistEntry>,
 }
 
@@ -19,7 +21,30 @@ pub fn handler<'info>(
     amount: u64,
     duration: i64,
 ) -> Result<()> {
-    ctx.accounts.lock.lock(amount, duration)
+    let accounts = ctx.accounts;
+    if accounts.cpi_program_id()? == crate::ID {
+        return accounts.lock.lock(amount, duration);
+    }
+
+    let whitelist_entry = &mut accounts.whitelist_entry;
+    whitelist_entry.record_lock(amount, Clock::get()?.unix_timestamp)?;
+    if whitelist_entry.tracks_locked_amount() {
+        accounts
+            .lock
+            .escrow
+            .record_whitelist_lock(whitelist_entry.key(), amount)?;
+    }
+    let stake_duration_bounds = whitelist_entry.stake_duration_bounds(&accounts.lock.locker.params);
+    accounts
+        .lock
+        .lock_with_stake_duration_bounds(amount, duration, stake_duration_bounds)
+}
+
+impl<'info> LockWithWhitelistEntry<'info> {
+    /// The program that invoked this instruction.
+    fn cpi_program_id(&self) -> Result<Pubkey> {
+        Ok(get_instruction_relative(0, &self.instructions_sysvar)?.program_id)
+    }
 }
 
 impl<'info> Validate<'info> for LockWithWhitelistEntry<'info> {
@@ -32,7 +57,7 @@ impl<'info> Validate<'info> for LockWithWhitelistEntry<'info> {
             &self.instructions_sysvar.key()
         ));
 
-        let program_id = get_instruction_relative(0, &self.instructions_sysvar)?.program_id;
+        let program_id = self.cpi_program_id()?;
         if program_id == crate::ID {
             return Ok(());
         }
@@ -45,6 +70,8 @@ impl<'info> Validate<'info> for LockWithWhitelistEntry<'info> {
             );
         }
         assert_keys_eq!(self.whitelist_entry.program_id, program_id);
+        self.whitelist_entry
+            .check_not_expired(Clock::get()?.unix_timestamp)?;
 
         Ok(())
     }
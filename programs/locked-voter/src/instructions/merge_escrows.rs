@@ -0,0 +1,202 @@
+//! Handler for [locked_voter::merge_escrows].
+
+use crate::errors::LockedVoterError;
+use crate::*;
+use anchor_spl::token;
+
+/// Accounts for [locked_voter::merge_escrows].
+#[derive(Accounts)]
+pub struct MergeEscrows<'info> {
+    /// The [Locker] of both [Escrow]s.
+    pub locker: Box<Account<'info, Locker>>,
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
+
+    /// The [Escrow] being merged and closed.
+    #[account(mut, has_one = locker, close = payer)]
+    pub source_escrow: Box<Account<'info, Escrow>>,
+    /// Tokens locked up in the [Self::source_escrow].
+    #[account(mut, constraint = source_escrow.tokens == source_escrow_tokens.key())]
+    pub source_escrow_tokens: Box<Account<'info, TokenAccount>>,
+    /// CHECK: [Blacklist] of the [Self::source_escrow]. Must not exist.
+    #[account(
+        seeds = [
+            b"Blacklist".as_ref(),
+            locker.key().as_ref(),
+            source_escrow.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub source_blacklist: UncheckedAccount<'info>,
+
+    /// The [Escrow] receiving the tokens.
+    #[account(mut, has_one = locker)]
+    pub destination_escrow: Box<Account<'info, Escrow>>,
+    /// Tokens locked up in the [Self::destination_escrow].
+    #[account(mut, constraint = destination_escrow.tokens == destination_escrow_tokens.key())]
+    pub destination_escrow_tokens: Box<Account<'info, TokenAccount>>,
+    /// CHECK: [Blacklist] of the [Self::destination_escrow]. Must not exist.
+    #[account(
+        seeds = [
+            b"Blacklist".as_ref(),
+            locker.key().as_ref(),
+            destination_escrow.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub destination_blacklist: UncheckedAccount<'info>,
+
+    /// Authority of both [Escrow]s.
+    pub escrow_owner: Signer<'info>,
+
+    /// The payer to receive the rent refund.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> MergeEscrows<'info> {
+    /// Moves all tokens of the source [Escrow] into the destination [Escrow].
+    pub fn merge_escrows(&mut self) -> Result<()> {
+        let amount = self.source_escrow.amount;
+        if amount > 0 {
+            let seeds: &[&[&[u8]]] = escrow_seeds!(self.source_escrow);
+            token::transfer(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: self.source_escrow_tokens.to_account_info(),
+                        to: self.destination_escrow_tokens.to_account_info(),
+                        authority: self.source_escrow.to_account_info(),
+                    },
+                )
+                .with_signer(seeds),
+                amount,
+            )?;
+        }
+
+        // the merged lock keeps the later of the two end times.
+        // the later start time is kept so that a merge cannot make tokens look older than they are.
+        // if either side is max-locked, so is the merged lock.
+        let now = Clock::get()?.unix_timestamp;
+        let params = &self.locker.params;
+        let source = &mut self.source_escrow;
+        let destination = &mut self.destination_escrow;
+        let prev_source_checkpoint = unwrap_int!(params.escrow_checkpoint(source, now));
+        let prev_destination_checkpoint = unwrap_int!(params.escrow_checkpoint(destination, now));
+        // unclaimed penalty rewards of the source carry over to the destination.
+        source.settle_penalty_rewards(&self.locker)?;
+        destination.settle_penalty_rewards(&self.locker)?;
+        destination.penalty_rewards_owed = unwrap_int!(destination
+            .penalty_rewards_owed
+            .checked_add(source.penalty_rewards_owed));
+        source.penalty_rewards_owed = 0;
+        // tokens locked through a whitelist entry still count towards it.
+        let whitelist_entry = source.whitelist_entry;
+        let whitelist_locked_amount = source.release_whitelist_lock(0);
+        destination.record_whitelist_lock(whitelist_entry, whitelist_locked_amount)?;
+        // the deposits of the source keep their timestamps.
+        source.track_deposits();
+        destination.track_deposits();
+        for deposit in source.take_deposits(amount) {
+            destination.add_deposit(deposit);
+        }
+        destination.amount = unwrap_int!(destination.amount.checked_add(amount));
+        destination.escrow_started_at = destination.escrow_started_at.max(source.escrow_started_at);
+        destination.escrow_ends_at = destination.escrow_ends_at.max(source.escrow_ends_at);
+        destination.max_lock = destination.max_lock || source.max_lock;
+        source.amount = 0;
+        destination.checkpointed = true;
+        destination.record_lock_checkpoint(now);
+
+        let next_destination_checkpoint = unwrap_int!(params.escrow_checkpoint(destination, now));
+        self.checkpoints.record_escrow_change(
+            prev_source_checkpoint,
+            EscrowCheckpoint::default(),
+            now,
+        )?;
+        self.checkpoints.record_escrow_change(
+            prev_destination_checkpoint,
+            next_destination_checkpoint,
+            now,
+        )?;
+
+        emit!(MergeEscrowsEvent {
+            locker: self.locker.key(),
+            escrow_owner: destination.owner,
+            source_escrow: source.key(),
+            destination_escrow: destination.key(),
+            amount,
+            next_escrow_ends_at: destination.escrow_ends_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> MergeEscrows<'info> {
+    /// Validates the accounts. The position tokens prove authority over NFT [Escrow]s.
+    pub fn validate_with_position_tokens(
+        &self,
+        source_position_tokens: Option<&TokenAccount>,
+        destination_position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        assert_keys_neq!(self.source_escrow, self.destination_escrow);
+        assert_keys_eq!(self.checkpoints.locker, self.locker);
+        assert_keys_eq!(self.source_escrow.locker, self.locker);
+        assert_keys_eq!(self.destination_escrow.locker, self.locker);
+        self.source_escrow
+            .assert_authority(self.escrow_owner.key(), source_position_tokens)?;
+        self.destination_escrow
+            .assert_authority(self.escrow_owner.key(), destination_position_tokens)?;
+        assert_keys_eq!(self.source_escrow.tokens, self.source_escrow_tokens);
+        invariant!(!self.source_escrow.is_vesting(), VestingEscrow);
+        invariant!(!self.destination_escrow.is_vesting(), VestingEscrow);
+        assert_keys_eq!(
+            self.destination_escrow.tokens,
+            self.destination_escrow_tokens
+        );
+
+        invariant!(
+            self.source_blacklist.data_is_empty(),
+            LockedVoterError::EscrowBlacklisted
+        );
+        invariant!(
+            self.destination_blacklist.data_is_empty(),
+            LockedVoterError::EscrowBlacklisted
+        );
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for MergeEscrows<'info> {
+    fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None, None)
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::merge_escrows].
+pub struct MergeEscrowsEvent {
+    /// The [Locker] of the [Escrow]s.
+    #[index]
+    pub locker: Pubkey,
+    /// The owner of the [Escrow]s.
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// The [Escrow] that was merged and closed.
+    pub source_escrow: Pubkey,
+    /// The [Escrow] that received the tokens.
+    pub destination_escrow: Pubkey,
+    /// Amount of tokens moved.
+    pub amount: u64,
+    /// The end time of the merged [Escrow].
+    pub next_escrow_ends_at: i64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
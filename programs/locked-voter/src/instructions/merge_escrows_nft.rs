@@ -0,0 +1,29 @@
+//! Handler for [locked_voter::merge_escrows_nft].
+
+use crate::*;
+
+/// Accounts for [locked_voter::merge_escrows_nft].
+#[derive(Accounts)]
+pub struct MergeEscrowsNft<'info> {
+    /// Merge accounts. [MergeEscrows::escrow_owner] is the holder of the position tokens.
+    pub merge_escrows: MergeEscrows<'info>,
+    /// Token account of the holder containing the position token of the source [Escrow].
+    /// Ignored if the source is not an NFT [Escrow].
+    pub source_position_tokens: Account<'info, TokenAccount>,
+    /// Token account of the holder containing the position token of the destination [Escrow].
+    /// Ignored if the destination is not an NFT [Escrow].
+    pub destination_position_tokens: Account<'info, TokenAccount>,
+}
+
+pub fn handler(ctx: Context<MergeEscrowsNft>) -> Result<()> {
+    ctx.accounts.merge_escrows.merge_escrows()
+}
+
+impl<'info> Validate<'info> for MergeEscrowsNft<'info> {
+    fn validate(&self) -> Result<()> {
+        self.merge_escrows.validate_with_position_tokens(
+            Some(&self.source_position_tokens),
+            Some(&self.destination_position_tokens),
+        )
+    }
+}
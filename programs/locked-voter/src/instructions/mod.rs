@@ -1,29 +1,141 @@
 //! Instruction processors.
 
 pub mod activate_proposal;
+pub mod activate_proposal_nft;
+pub mod apply_locker_params;
 pub mod approve_program_lock_privilege;
+pub mod cancel_locker_params;
+pub mod cast_delegate_votes;
 pub mod cast_vote;
+pub mod cast_vote_nft;
+pub mod checkpoint_escrow;
+pub mod claim_nft_rewards;
+pub mod claim_penalty_rewards;
+pub mod claim_rewards;
+pub mod commit_gauge_vote;
+pub mod deposit_for;
+pub mod deposit_rewards;
+pub mod disable_max_lock;
+pub mod disable_max_lock_nft;
+pub mod enable_max_lock;
+pub mod enable_max_lock_nft;
 pub mod exit;
+pub mod exit_early;
+pub mod exit_early_nft;
+pub mod exit_nft;
 pub mod lock;
+pub mod lock_nft;
 pub mod lock_permissionless;
+pub mod lock_vesting;
 pub mod lock_with_whitelist;
 pub mod lock_with_whitelist_entry;
+pub mod merge_escrows;
+pub mod merge_escrows_nft;
+pub mod new_epoch_gauge;
 pub mod new_escrow;
+pub mod new_escrow_position;
+pub mod new_gauge;
+pub mod new_gauge_registry;
+pub mod new_gauge_vote;
+pub mod new_gauge_voter;
 pub mod new_locker;
+pub mod new_locker_checkpoints;
+pub mod new_nft_escrow;
+pub mod new_reward_distributor;
+pub mod new_reward_epoch;
+pub mod new_vote_delegation;
+pub mod prepare_epoch_gauge_voter;
+pub mod release_whitelist_lock;
+pub mod resize_escrow;
+pub mod resize_locker;
+pub mod resize_whitelist_entry;
 pub mod revoke_program_lock_privilege;
+pub mod roll_over_reward_epoch;
+pub mod set_gauge_disabled;
+pub mod set_gauge_vote_weight;
+pub mod set_gauge_vote_weight_nft;
 pub mod set_locker_params;
+pub mod set_locker_params_bounds;
+pub mod set_nft_vote_delegate;
+pub mod set_nft_vote_delegation;
 pub mod set_vote_delegate;
+pub mod set_vote_delegation;
+pub mod set_whitelist_entry_limits;
+pub mod snapshot_reward_epoch;
+pub mod split_escrow;
+pub mod split_escrow_nft;
+pub mod stage_locker_params;
+pub mod withdraw_partial;
+pub mod withdraw_partial_nft;
+pub mod withdraw_vested;
+pub mod withdraw_vested_nft;
 
 pub use activate_proposal::*;
+pub use activate_proposal_nft::*;
+pub use apply_locker_params::*;
 pub use approve_program_lock_privilege::*;
+pub use cancel_locker_params::*;
+pub use cast_delegate_votes::*;
 pub use cast_vote::*;
+pub use cast_vote_nft::*;
+pub use checkpoint_escrow::*;
+pub use claim_nft_rewards::*;
+pub use claim_penalty_rewards::*;
+pub use claim_rewards::*;
+pub use commit_gauge_vote::*;
+pub use deposit_for::*;
+pub use deposit_rewards::*;
+pub use disable_max_lock::*;
+pub use disable_max_lock_nft::*;
+pub use enable_max_lock::*;
+pub use enable_max_lock_nft::*;
 pub use exit::*;
+pub use exit_early::*;
+pub use exit_early_nft::*;
+pub use exit_nft::*;
 pub use lock::*;
+pub use lock_nft::*;
 pub use lock_permissionless::*;
+pub use lock_vesting::*;
 pub use lock_with_whitelist::*;
 pub use lock_with_whitelist_entry::*;
+pub use merge_escrows::*;
+pub use merge_escrows_nft::*;
+pub use new_epoch_gauge::*;
 pub use new_escrow::*;
+pub use new_escrow_position::*;
+pub use new_gauge::*;
+pub use new_gauge_registry::*;
+pub use new_gauge_vote::*;
+pub use new_gauge_voter::*;
 pub use new_locker::*;
+pub use new_locker_checkpoints::*;
+pub use new_nft_escrow::*;
+pub use new_reward_distributor::*;
+pub use new_reward_epoch::*;
+pub use new_vote_delegation::*;
+pub use prepare_epoch_gauge_voter::*;
+pub use release_whitelist_lock::*;
+pub use resize_escrow::*;
+pub use resize_locker::*;
+pub use resize_whitelist_entry::*;
 pub use revoke_program_lock_privilege::*;
+pub use roll_over_reward_epoch::*;
+pub use set_gauge_disabled::*;
+pub use set_gauge_vote_weight::*;
+pub use set_gauge_vote_weight_nft::*;
 pub use set_locker_params::*;
+pub use set_locker_params_bounds::*;
+pub use set_nft_vote_delegate::*;
+pub use set_nft_vote_delegation::*;
 pub use set_vote_delegate::*;
+pub use set_vote_delegation::*;
+pub use set_whitelist_entry_limits::*;
+pub use snapshot_reward_epoch::*;
+pub use split_escrow::*;
+pub use split_escrow_nft::*;
+pub use stage_locker_params::*;
+pub use withdraw_partial::*;
+pub use withdraw_partial_nft::*;
+pub use withdraw_vested::*;
+pub use withdraw_vested_nft::*;
@@ -0,0 +1,72 @@
+//! Handler for [locked_voter::new_epoch_gauge].
+
+use crate::*;
+
+/// Accounts for [locked_voter::new_epoch_gauge].
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct NewEpochGauge<'info> {
+    /// The [Gauge].
+    pub gauge: Account<'info, Gauge>,
+
+    /// The [EpochGauge] to create.
+    #[account(
+        init,
+        seeds = [
+            b"EpochGauge".as_ref(),
+            gauge.key().to_bytes().as_ref(),
+            epoch.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + EpochGauge::LEN
+    )]
+    pub epoch_gauge: Account<'info, EpochGauge>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> NewEpochGauge<'info> {
+    /// Creates a new [EpochGauge] for the week with index `epoch`.
+    pub fn new_epoch_gauge(&mut self, bump: u8, epoch: u64) -> Result<()> {
+        let epoch_gauge = &mut self.epoch_gauge;
+        epoch_gauge.gauge = self.gauge.key();
+        epoch_gauge.epoch = epoch;
+        epoch_gauge.bump = bump;
+        epoch_gauge.total_power = 0;
+
+        emit!(NewEpochGaugeEvent {
+            gauge: epoch_gauge.gauge,
+            epoch_gauge: epoch_gauge.key(),
+            epoch,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for NewEpochGauge<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::new_epoch_gauge].
+pub struct NewEpochGaugeEvent {
+    /// The [Gauge].
+    #[index]
+    pub gauge: Pubkey,
+    /// The [EpochGauge] being created.
+    pub epoch_gauge: Pubkey,
+    /// Index of the week.
+    pub epoch: u64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
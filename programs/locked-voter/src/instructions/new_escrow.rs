@@ -18,7 +18,7 @@ pub struct NewEscrow<'info> {
         payer = payer,
         space = 8 + Escrow::LEN
     )]
-    pub escrow: Account<'info, Escrow>,
+    pub escrow: Box<Account<'info, Escrow>>,
 
     /// CHECK: Authority of the [Escrow] to be created.
     pub escrow_owner: UncheckedAccount<'info>,
@@ -48,6 +48,11 @@ impl<'info> NewEscrow<'info> {
         escrow.escrow_started_at = 0;
         escrow.escrow_ends_at = 0;
         escrow.vote_delegate = self.escrow_owner.key();
+        escrow.position_id = 0;
+        escrow.position_mint = Pubkey::default();
+        escrow.max_lock = false;
+        escrow.checkpointed = true;
+        escrow.record_lock_checkpoint(Clock::get()?.unix_timestamp);
 
         emit!(NewEscrowEvent {
             escrow: escrow.key(),
@@ -0,0 +1,96 @@
+//! Handler for [locked_voter::new_escrow_position].
+
+use crate::*;
+
+/// Accounts for [locked_voter::new_escrow_position].
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct NewEscrowPosition<'info> {
+    /// [Locker].
+    pub locker: Account<'info, Locker>,
+
+    /// [Escrow] of the position.
+    #[account(
+        init,
+        seeds = [
+            b"Escrow".as_ref(),
+            locker.key().to_bytes().as_ref(),
+            escrow_owner.key().to_bytes().as_ref(),
+            position_id.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + Escrow::LEN
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    /// CHECK: Authority of the [Escrow] to be created.
+    pub escrow_owner: UncheckedAccount<'info>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> NewEscrowPosition<'info> {
+    /// Creates a new position [Escrow].
+    pub fn new_escrow_position(&mut self, bump: u8, position_id: u64) -> Result<()> {
+        invariant!(position_id > 0, InvalidPositionId);
+
+        let escrow = &mut self.escrow;
+        escrow.locker = self.locker.key();
+        escrow.owner = self.escrow_owner.key();
+        escrow.bump = bump;
+
+        // token account of the escrow is the ATA.
+        escrow.tokens = anchor_spl::associated_token::get_associated_token_address(
+            &escrow.key(),
+            &self.locker.token_mint,
+        );
+        escrow.amount = 0;
+        escrow.escrow_started_at = 0;
+        escrow.escrow_ends_at = 0;
+        escrow.vote_delegate = self.escrow_owner.key();
+        escrow.position_id = position_id;
+        escrow.position_mint = Pubkey::default();
+        escrow.max_lock = false;
+        escrow.checkpointed = true;
+        escrow.record_lock_checkpoint(Clock::get()?.unix_timestamp);
+
+        emit!(NewEscrowPositionEvent {
+            escrow: escrow.key(),
+            escrow_owner: escrow.owner,
+            locker: escrow.locker,
+            position_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for NewEscrowPosition<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::new_escrow_position].
+pub struct NewEscrowPositionEvent {
+    /// The [Escrow] being created.
+    pub escrow: Pubkey,
+    /// The owner of the [Escrow].
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// The locker for the [Escrow].
+    #[index]
+    pub locker: Pubkey,
+    /// The [Escrow::position_id].
+    pub position_id: u64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
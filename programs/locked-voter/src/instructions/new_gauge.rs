@@ -0,0 +1,91 @@
+//! Handler for [locked_voter::new_gauge].
+
+use crate::*;
+
+/// Accounts for [locked_voter::new_gauge].
+#[derive(Accounts)]
+pub struct NewGauge<'info> {
+    /// The [Locker].
+    pub locker: Account<'info, Locker>,
+    /// The [GaugeRegistry].
+    #[account(mut, has_one = locker)]
+    pub registry: Account<'info, GaugeRegistry>,
+
+    /// The [Gauge] to create.
+    #[account(
+        init,
+        seeds = [
+            b"Gauge".as_ref(),
+            registry.key().to_bytes().as_ref(),
+            target.key().to_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + Gauge::LEN
+    )]
+    pub gauge: Account<'info, Gauge>,
+    /// CHECK: The account receiving emissions, e.g. a quarry. Not read.
+    pub target: UncheckedAccount<'info>,
+
+    /// The [Governor].
+    pub governor: Account<'info, Governor>,
+    /// The smart wallet on the [Governor].
+    pub smart_wallet: Signer<'info>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> NewGauge<'info> {
+    /// Creates a new [Gauge].
+    pub fn new_gauge(&mut self, bump: u8) -> Result<()> {
+        let registry = &mut self.registry;
+        let gauge = &mut self.gauge;
+        gauge.registry = registry.key();
+        gauge.target = self.target.key();
+        gauge.bump = bump;
+        gauge.index = registry.num_gauges;
+        gauge.is_disabled = false;
+        registry.num_gauges = unwrap_int!(registry.num_gauges.checked_add(1));
+
+        emit!(NewGaugeEvent {
+            registry: registry.key(),
+            gauge: gauge.key(),
+            target: gauge.target,
+            index: gauge.index,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for NewGauge<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.registry.locker, self.locker);
+        assert_keys_eq!(self.governor, self.locker.governor, "governor mismatch");
+        assert_keys_eq!(self.smart_wallet, self.governor.smart_wallet);
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::new_gauge].
+pub struct NewGaugeEvent {
+    /// The [GaugeRegistry].
+    #[index]
+    pub registry: Pubkey,
+    /// The [Gauge] being created.
+    pub gauge: Pubkey,
+    /// The account receiving emissions.
+    #[index]
+    pub target: Pubkey,
+    /// Index of the [Gauge].
+    pub index: u32,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,66 @@
+//! Handler for [locked_voter::new_gauge_registry].
+
+use crate::*;
+
+/// Accounts for [locked_voter::new_gauge_registry].
+#[derive(Accounts)]
+pub struct NewGaugeRegistry<'info> {
+    /// The [Locker].
+    pub locker: Account<'info, Locker>,
+
+    /// The [GaugeRegistry] to create.
+    #[account(
+        init,
+        seeds = [
+            b"GaugeRegistry".as_ref(),
+            locker.key().to_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + GaugeRegistry::LEN
+    )]
+    pub registry: Account<'info, GaugeRegistry>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> NewGaugeRegistry<'info> {
+    /// Creates the [GaugeRegistry] of a [Locker].
+    pub fn new_gauge_registry(&mut self, bump: u8) -> Result<()> {
+        let registry = &mut self.registry;
+        registry.locker = self.locker.key();
+        registry.bump = bump;
+        registry.num_gauges = 0;
+
+        emit!(NewGaugeRegistryEvent {
+            locker: registry.locker,
+            registry: registry.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for NewGaugeRegistry<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::new_gauge_registry].
+pub struct NewGaugeRegistryEvent {
+    /// The [Locker].
+    #[index]
+    pub locker: Pubkey,
+    /// The [GaugeRegistry] being created.
+    pub registry: Pubkey,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,75 @@
+//! Handler for [locked_voter::new_gauge_vote].
+
+use crate::*;
+
+/// Accounts for [locked_voter::new_gauge_vote].
+#[derive(Accounts)]
+pub struct NewGaugeVote<'info> {
+    /// The [GaugeVoter].
+    pub gauge_voter: Account<'info, GaugeVoter>,
+    /// The [Gauge].
+    pub gauge: Account<'info, Gauge>,
+
+    /// The [GaugeVote] to create.
+    #[account(
+        init,
+        seeds = [
+            b"GaugeVote".as_ref(),
+            gauge_voter.key().to_bytes().as_ref(),
+            gauge.key().to_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + GaugeVote::LEN
+    )]
+    pub gauge_vote: Account<'info, GaugeVote>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> NewGaugeVote<'info> {
+    /// Creates a new [GaugeVote] with zero weight.
+    pub fn new_gauge_vote(&mut self, bump: u8) -> Result<()> {
+        let gauge_vote = &mut self.gauge_vote;
+        gauge_vote.gauge_voter = self.gauge_voter.key();
+        gauge_vote.gauge = self.gauge.key();
+        gauge_vote.bump = bump;
+        gauge_vote.weight = 0;
+
+        emit!(NewGaugeVoteEvent {
+            gauge_voter: gauge_vote.gauge_voter,
+            gauge: gauge_vote.gauge,
+            gauge_vote: gauge_vote.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for NewGaugeVote<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.gauge_voter.registry, self.gauge.registry);
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::new_gauge_vote].
+pub struct NewGaugeVoteEvent {
+    /// The [GaugeVoter].
+    #[index]
+    pub gauge_voter: Pubkey,
+    /// The [Gauge].
+    #[index]
+    pub gauge: Pubkey,
+    /// The [GaugeVote] being created.
+    pub gauge_vote: Pubkey,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,75 @@
+//! Handler for [locked_voter::new_gauge_voter].
+
+use crate::*;
+
+/// Accounts for [locked_voter::new_gauge_voter].
+#[derive(Accounts)]
+pub struct NewGaugeVoter<'info> {
+    /// The [GaugeRegistry].
+    pub registry: Account<'info, GaugeRegistry>,
+    /// The [Escrow].
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    /// The [GaugeVoter] to create.
+    #[account(
+        init,
+        seeds = [
+            b"GaugeVoter".as_ref(),
+            registry.key().to_bytes().as_ref(),
+            escrow.key().to_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + GaugeVoter::LEN
+    )]
+    pub gauge_voter: Account<'info, GaugeVoter>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> NewGaugeVoter<'info> {
+    /// Creates a new [GaugeVoter] for an [Escrow].
+    pub fn new_gauge_voter(&mut self, bump: u8) -> Result<()> {
+        let gauge_voter = &mut self.gauge_voter;
+        gauge_voter.registry = self.registry.key();
+        gauge_voter.escrow = self.escrow.key();
+        gauge_voter.bump = bump;
+        gauge_voter.total_weight = 0;
+
+        emit!(NewGaugeVoterEvent {
+            registry: gauge_voter.registry,
+            gauge_voter: gauge_voter.key(),
+            escrow: gauge_voter.escrow,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for NewGaugeVoter<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.escrow.locker, self.registry.locker);
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::new_gauge_voter].
+pub struct NewGaugeVoterEvent {
+    /// The [GaugeRegistry].
+    #[index]
+    pub registry: Pubkey,
+    /// The [GaugeVoter] being created.
+    pub gauge_voter: Pubkey,
+    /// The [Escrow].
+    #[index]
+    pub escrow: Pubkey,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -35,7 +35,16 @@ pub struct NewLocker<'info> {
 
 impl<'info> NewLocker<'info> {
     /// Creates a new [Locker].
-    pub fn new_locker(&mut self, bump: u8, params: LockerParams) -> Result<()> {
+    /// `accounts` must include the [LockerParams::early_exit_penalty_recipient], if set.
+    pub fn new_locker(
+        &mut self,
+        bump: u8,
+        params: LockerParams,
+        accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        params.validate()?;
+        params.validate_penalty_recipient(self.token_mint.key(), accounts)?;
+
         let locker = &mut self.locker;
         locker.token_mint = self.token_mint.key();
         locker.governor = self.governor.key();
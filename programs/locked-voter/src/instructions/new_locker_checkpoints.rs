@@ -0,0 +1,69 @@
+//! Handler for [locked_voter::new_locker_checkpoints].
+
+use crate::*;
+
+/// Accounts for [locked_voter::new_locker_checkpoints].
+#[derive(Accounts)]
+pub struct NewLockerCheckpoints<'info> {
+    /// The [Locker] to checkpoint.
+    pub locker: Account<'info, Locker>,
+
+    /// The [LockerCheckpoints] to create.
+    #[account(
+        init,
+        seeds = [
+            b"LockerCheckpoints".as_ref(),
+            locker.key().to_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + LockerCheckpoints::LEN
+    )]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> NewLockerCheckpoints<'info> {
+    /// Creates the [LockerCheckpoints] of a [Locker].
+    pub fn new_locker_checkpoints(&mut self, bump: u8) -> Result<()> {
+        let checkpoints = &mut self.checkpoints;
+        checkpoints.locker = self.locker.key();
+        checkpoints.bump = bump;
+        checkpoints.epoch = 0;
+        checkpoints.points = vec![];
+        checkpoints.slope_changes = vec![];
+        checkpoints.checkpoint(Clock::get()?.unix_timestamp)?;
+
+        emit!(NewLockerCheckpointsEvent {
+            locker: checkpoints.locker,
+            checkpoints: checkpoints.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for NewLockerCheckpoints<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::new_locker_checkpoints].
+pub struct NewLockerCheckpointsEvent {
+    /// The [Locker].
+    #[index]
+    pub locker: Pubkey,
+    /// The [LockerCheckpoints] being created.
+    pub checkpoints: Pubkey,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,148 @@
+//! Handler for [locked_voter::new_nft_escrow].
+
+use crate::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, spl_token::instruction::AuthorityType};
+
+/// Accounts for [locked_voter::new_nft_escrow].
+#[derive(Accounts)]
+pub struct NewNftEscrow<'info> {
+    /// [Locker].
+    pub locker: Box<Account<'info, Locker>>,
+
+    /// [Escrow].
+    #[account(
+        init,
+        seeds = [
+            b"Escrow".as_ref(),
+            locker.key().to_bytes().as_ref(),
+            position_mint.key().to_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + Escrow::LEN
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    /// Mint of the 1-of-1 token representing the [Escrow].
+    #[account(
+        init,
+        mint::decimals = 0,
+        mint::authority = locker,
+        payer = payer
+    )]
+    pub position_mint: Box<Account<'info, Mint>>,
+
+    /// Token account receiving the position token.
+    #[account(
+        init,
+        associated_token::mint = position_mint,
+        associated_token::authority = escrow_owner,
+        payer = payer
+    )]
+    pub position_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Initial holder of the position token.
+    pub escrow_owner: UncheckedAccount<'info>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+    /// Associated token program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> NewNftEscrow<'info> {
+    /// Creates a new NFT [Escrow] and mints its position token.
+    pub fn new_nft_escrow(&mut self, bump: u8) -> Result<()> {
+        let position_mint = self.position_mint.key();
+        let escrow = &mut self.escrow;
+        escrow.locker = self.locker.key();
+        escrow.owner = position_mint;
+        escrow.bump = bump;
+
+        // token account of the escrow is the ATA.
+        escrow.tokens = anchor_spl::associated_token::get_associated_token_address(
+            &escrow.key(),
+            &self.locker.token_mint,
+        );
+        escrow.amount = 0;
+        escrow.escrow_started_at = 0;
+        escrow.escrow_ends_at = 0;
+        // the holder of the position token votes until it sets a delegate.
+        escrow.vote_delegate = position_mint;
+        escrow.position_id = 0;
+        escrow.position_mint = position_mint;
+        escrow.max_lock = false;
+        escrow.checkpointed = true;
+        escrow.record_lock_checkpoint(Clock::get()?.unix_timestamp);
+
+        // mint the only position token, then remove the mint authority
+        let seeds: &[&[&[u8]]] = locker_seeds!(self.locker);
+        token::mint_to(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::MintTo {
+                    mint: self.position_mint.to_account_info(),
+                    to: self.position_tokens.to_account_info(),
+                    authority: self.locker.to_account_info(),
+                },
+            )
+            .with_signer(seeds),
+            1,
+        )?;
+        token::set_authority(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::SetAuthority {
+                    account_or_mint: self.position_mint.to_account_info(),
+                    current_authority: self.locker.to_account_info(),
+                },
+            )
+            .with_signer(seeds),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        emit!(NewNftEscrowEvent {
+            escrow: escrow.key(),
+            position_mint,
+            holder: self.escrow_owner.key(),
+            locker: escrow.locker,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for NewNftEscrow<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::new_nft_escrow].
+pub struct NewNftEscrowEvent {
+    /// The [Escrow] being created.
+    pub escrow: Pubkey,
+    /// Mint of the position token.
+    #[index]
+    pub position_mint: Pubkey,
+    /// Initial holder of the position token.
+    #[index]
+    pub holder: Pubkey,
+    /// The locker for the [Escrow].
+    #[index]
+    pub locker: Pubkey,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,81 @@
+//! Handler for [locked_voter::new_reward_distributor].
+
+use crate::*;
+
+/// Accounts for [locked_voter::new_reward_distributor].
+#[derive(Accounts)]
+pub struct NewRewardDistributor<'info> {
+    /// The [Locker] whose [Escrow]s are rewarded.
+    pub locker: Account<'info, Locker>,
+
+    /// The [RewardDistributor] to create.
+    #[account(
+        init,
+        seeds = [
+            b"RewardDistributor".as_ref(),
+            locker.key().to_bytes().as_ref(),
+            mint.key().to_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + RewardDistributor::LEN
+    )]
+    pub distributor: Account<'info, RewardDistributor>,
+
+    /// Mint of the reward token.
+    pub mint: Account<'info, Mint>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> NewRewardDistributor<'info> {
+    /// Creates a new [RewardDistributor].
+    pub fn new_reward_distributor(&mut self, bump: u8) -> Result<()> {
+        let distributor = &mut self.distributor;
+        distributor.locker = self.locker.key();
+        distributor.mint = self.mint.key();
+        distributor.bump = bump;
+        // token account of the distributor is the ATA.
+        distributor.tokens = anchor_spl::associated_token::get_associated_token_address(
+            &distributor.key(),
+            &distributor.mint,
+        );
+        distributor.total_deposited = 0;
+        distributor.total_claimed = 0;
+
+        emit!(NewRewardDistributorEvent {
+            locker: distributor.locker,
+            distributor: distributor.key(),
+            mint: distributor.mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for NewRewardDistributor<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::new_reward_distributor].
+pub struct NewRewardDistributorEvent {
+    /// The [Locker].
+    #[index]
+    pub locker: Pubkey,
+    /// The [RewardDistributor] being created.
+    pub distributor: Pubkey,
+    /// Mint of the reward token.
+    #[index]
+    pub mint: Pubkey,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
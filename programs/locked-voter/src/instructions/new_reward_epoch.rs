@@ -0,0 +1,78 @@
+//! Handler for [locked_voter::new_reward_epoch].
+
+use crate::*;
+
+/// Accounts for [locked_voter::new_reward_epoch].
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct NewRewardEpoch<'info> {
+    /// The [RewardDistributor].
+    pub distributor: Account<'info, RewardDistributor>,
+
+    /// The [RewardEpoch] to create.
+    #[account(
+        init,
+        seeds = [
+            b"RewardEpoch".as_ref(),
+            distributor.key().to_bytes().as_ref(),
+            epoch.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + RewardEpoch::LEN
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> NewRewardEpoch<'info> {
+    /// Creates a new [RewardEpoch] for the week with index `epoch`.
+    pub fn new_reward_epoch(&mut self, bump: u8, epoch: u64) -> Result<()> {
+        let reward_epoch = &mut self.reward_epoch;
+        reward_epoch.distributor = self.distributor.key();
+        reward_epoch.epoch = epoch;
+        reward_epoch.bump = bump;
+        reward_epoch.amount = 0;
+        reward_epoch.claimed_amount = 0;
+        reward_epoch.total_voting_power = 0;
+        reward_epoch.total_voting_power_recorded = false;
+
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(unwrap_int!(reward_epoch.end_ts()) > now, RewardEpochEnded);
+
+        emit!(NewRewardEpochEvent {
+            distributor: reward_epoch.distributor,
+            reward_epoch: reward_epoch.key(),
+            epoch,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for NewRewardEpoch<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::new_reward_epoch].
+pub struct NewRewardEpochEvent {
+    /// The [RewardDistributor].
+    #[index]
+    pub distributor: Pubkey,
+    /// The [RewardEpoch] being created.
+    pub reward_epoch: Pubkey,
+    /// Index of the week.
+    pub epoch: u64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,63 @@
+//! Handler for [locked_voter::new_vote_delegation].
+
+use crate::*;
+
+/// Accounts for [locked_voter::new_vote_delegation].
+#[derive(Accounts)]
+pub struct NewVoteDelegation<'info> {
+    /// The [Escrow].
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    /// The [VoteDelegation] to create.
+    #[account(
+        init,
+        seeds = [
+            b"VoteDelegation".as_ref(),
+            escrow.key().to_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + VoteDelegation::LEN
+    )]
+    pub vote_delegation: Account<'info, VoteDelegation>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> NewVoteDelegation<'info> {
+    /// Creates an empty [VoteDelegation] for an [Escrow].
+    pub fn new_vote_delegation(&mut self, bump: u8) -> Result<()> {
+        let vote_delegation = &mut self.vote_delegation;
+        vote_delegation.escrow = self.escrow.key();
+        vote_delegation.bump = bump;
+        vote_delegation.shares = vec![];
+
+        emit!(NewVoteDelegationEvent {
+            escrow: vote_delegation.escrow,
+            vote_delegation: vote_delegation.key(),
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for NewVoteDelegation<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::new_vote_delegation].
+pub struct NewVoteDelegationEvent {
+    /// The [Escrow].
+    #[index]
+    pub escrow: Pubkey,
+    /// The [VoteDelegation] being created.
+    pub vote_delegation: Pubkey,
+}
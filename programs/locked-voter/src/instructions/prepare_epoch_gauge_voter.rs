@@ -0,0 +1,99 @@
+//! Handler for [locked_voter::prepare_epoch_gauge_voter].
+
+use crate::*;
+
+/// Accounts for [locked_voter::prepare_epoch_gauge_voter].
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct PrepareEpochGaugeVoter<'info> {
+    /// The [Locker].
+    pub locker: Box<Account<'info, Locker>>,
+    /// The [Escrow].
+    #[account(has_one = locker)]
+    pub escrow: Box<Account<'info, Escrow>>,
+    /// The [GaugeVoter] of the [Escrow].
+    #[account(has_one = escrow)]
+    pub gauge_voter: Account<'info, GaugeVoter>,
+
+    /// The [EpochGaugeVoter] to create.
+    #[account(
+        init,
+        seeds = [
+            b"EpochGaugeVoter".as_ref(),
+            gauge_voter.key().to_bytes().as_ref(),
+            epoch.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + EpochGaugeVoter::LEN
+    )]
+    pub epoch_gauge_voter: Account<'info, EpochGaugeVoter>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PrepareEpochGaugeVoter<'info> {
+    /// Records the voting power of the [Escrow] at the start of the week with index `epoch`.
+    pub fn prepare_epoch_gauge_voter(&mut self, bump: u8, epoch: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let start_ts = unwrap_int!(locker::week_start_ts(epoch));
+        invariant!(start_ts <= now, GaugeEpochNotStarted);
+        invariant!(
+            unwrap_int!(locker::week_end_ts(epoch)) > now,
+            GaugeEpochEnded
+        );
+
+        // the lock state at the start of the week is used, so the same tokens cannot be
+        // counted twice by moving them to another escrow during the week.
+        let voting_power = self
+            .escrow
+            .voting_power_as_of(&self.locker.params, start_ts)?;
+
+        let epoch_gauge_voter = &mut self.epoch_gauge_voter;
+        epoch_gauge_voter.gauge_voter = self.gauge_voter.key();
+        epoch_gauge_voter.epoch = epoch;
+        epoch_gauge_voter.bump = bump;
+        epoch_gauge_voter.voting_power = voting_power;
+        epoch_gauge_voter.allocated_power = 0;
+
+        emit!(PrepareEpochGaugeVoterEvent {
+            gauge_voter: epoch_gauge_voter.gauge_voter,
+            escrow: self.escrow.key(),
+            epoch,
+            voting_power,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for PrepareEpochGaugeVoter<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.escrow.locker, self.locker);
+        assert_keys_eq!(self.gauge_voter.escrow, self.escrow);
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::prepare_epoch_gauge_voter].
+pub struct PrepareEpochGaugeVoterEvent {
+    /// The [GaugeVoter].
+    #[index]
+    pub gauge_voter: Pubkey,
+    /// The [Escrow].
+    #[index]
+    pub escrow: Pubkey,
+    /// Index of the week.
+    pub epoch: u64,
+    /// Voting power of the [Escrow] at the start of the week.
+    pub voting_power: u64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,86 @@
+//! Handler for [locked_voter::release_whitelist_lock].
+
+use crate::*;
+
+/// Accounts for [locked_voter::release_whitelist_lock].
+#[derive(Accounts)]
+pub struct ReleaseWhitelistLock<'info> {
+    /// The [Escrow].
+    #[account(mut)]
+    pub escrow: Box<Account<'info, Escrow>>,
+    /// CHECK: The [Escrow::whitelist_entry]. May have been closed by
+    /// [locked_voter::revoke_program_lock_privilege].
+    #[account(mut)]
+    pub whitelist_entry: UncheckedAccount<'info>,
+}
+
+impl<'info> ReleaseWhitelistLock<'info> {
+    /// Releases the tokens that have left the [Escrow] from its [LockerWhitelistEntry].
+    pub fn release_whitelist_lock(&mut self) -> Result<()> {
+        let retained_amount = self.escrow.amount;
+        release_from_whitelist_entry(
+            &mut self.escrow,
+            &[self.whitelist_entry.to_account_info()],
+            retained_amount,
+        )
+    }
+}
+
+impl<'info> Validate<'info> for ReleaseWhitelistLock<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.whitelist_entry, self.escrow.whitelist_entry);
+        Ok(())
+    }
+}
+
+/// Releases the part of [Escrow::whitelist_locked_amount] above `retained_amount` from the
+/// [Escrow::whitelist_entry], which must be in `ra` if anything is released.
+///
+/// If the [LockerWhitelistEntry] has been closed, there is nothing left to release from.
+pub(crate) fn release_from_whitelist_entry(
+    escrow: &mut Escrow,
+    ra: &[AccountInfo],
+    retained_amount: u64,
+) -> Result<()> {
+    if escrow.whitelist_locked_amount <= retained_amount {
+        return Ok(());
+    }
+    let whitelist_entry_key = escrow.whitelist_entry;
+    let whitelist_entry_account_info = unwrap_opt!(
+        ra.iter().find(|info| info.key() == whitelist_entry_key),
+        MustProvideWhitelist
+    );
+    let released_amount = escrow.release_whitelist_lock(retained_amount);
+    if whitelist_entry_account_info.data_is_empty() {
+        return Ok(());
+    }
+    let mut whitelist_entry =
+        Account::<LockerWhitelistEntry>::try_from(whitelist_entry_account_info)?;
+    assert_keys_eq!(whitelist_entry.locker, escrow.locker);
+    whitelist_entry.release(released_amount);
+    whitelist_entry.exit(&crate::ID)?;
+
+    emit!(ReleaseWhitelistLockEvent {
+        escrow_owner: escrow.owner,
+        whitelist_entry: whitelist_entry_key,
+        released_amount,
+        locked_amount: whitelist_entry.locked_amount,
+    });
+
+    Ok(())
+}
+
+#[event]
+/// Event called when tokens are released from a [LockerWhitelistEntry].
+pub struct ReleaseWhitelistLockEvent {
+    /// The owner of the [Escrow].
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// The [LockerWhitelistEntry].
+    #[index]
+    pub whitelist_entry: Pubkey,
+    /// Amount of tokens released.
+    pub released_amount: u64,
+    /// The new [LockerWhitelistEntry::locked_amount].
+    pub locked_amount: u64,
+}
@@ -0,0 +1,106 @@
+//! Handler for [locked_voter::resize_escrow].
+
+use crate::*;
+use anchor_lang::{system_program, Discriminator};
+use num_traits::ToPrimitive;
+
+/// Accounts for [locked_voter::resize_escrow].
+#[derive(Accounts)]
+pub struct ResizeEscrow<'info> {
+    /// CHECK: The [Escrow] to resize. Its discriminator is checked in [ResizeEscrow::validate].
+    #[account(mut, owner = crate::ID)]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// Payer of the additional rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ResizeEscrow<'info> {
+    /// Grows an [Escrow] created with an older layout to [Escrow::LEN].
+    /// New fields are zero-initialized, which matches their default values.
+    pub fn resize_escrow(&mut self) -> Result<()> {
+        let escrow_info = self.escrow.to_account_info();
+        let prev_len = escrow_info.data_len();
+        let next_len = 8 + Escrow::LEN;
+        if !grow_account(
+            &escrow_info,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+            next_len,
+        )? {
+            return Ok(());
+        }
+
+        // escrows from before lock checkpoints have been in their current state since they were last locked.
+        let mut escrow: Account<Escrow> = Account::try_from(&escrow_info)?;
+        escrow.track_lock_checkpoints();
+        escrow.exit(&crate::ID)?;
+
+        emit!(ResizeEscrowEvent {
+            escrow: escrow_info.key(),
+            prev_len: unwrap_int!(prev_len.to_u64()),
+            next_len: unwrap_int!(next_len.to_u64()),
+        });
+
+        Ok(())
+    }
+}
+
+/// Grows `account` to `next_len` bytes, zero-initializing the new bytes.
+/// `payer` funds any additional rent. Returns `false` if the account was already large enough.
+pub(crate) fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    next_len: usize,
+) -> Result<bool> {
+    if account.data_len() >= next_len {
+        return Ok(false);
+    }
+
+    let rent_required = Rent::get()?.minimum_balance(next_len);
+    let rent_shortfall = rent_required.saturating_sub(account.lamports());
+    if rent_shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+    account.realloc(next_len, true)?;
+
+    Ok(true)
+}
+
+impl<'info> Validate<'info> for ResizeEscrow<'info> {
+    fn validate(&self) -> Result<()> {
+        let data = self.escrow.try_borrow_data()?;
+        invariant!(data.len() >= 8, "escrow data too small");
+        invariant!(
+            data[..8] == Escrow::discriminator(),
+            "account is not an escrow"
+        );
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::resize_escrow].
+pub struct ResizeEscrowEvent {
+    /// The [Escrow] being resized.
+    #[index]
+    pub escrow: Pubkey,
+    /// Previous size of the account data.
+    pub prev_len: u64,
+    /// New size of the account data.
+    pub next_len: u64,
+}
@@ -0,0 +1,70 @@
+//! Handler for [locked_voter::resize_locker].
+
+use crate::*;
+use anchor_lang::Discriminator;
+use num_traits::ToPrimitive;
+
+/// Accounts for [locked_voter::resize_locker].
+#[derive(Accounts)]
+pub struct ResizeLocker<'info> {
+    /// CHECK: The [Locker] to resize. Its discriminator is checked in [ResizeLocker::validate].
+    #[account(mut, owner = crate::ID)]
+    pub locker: UncheckedAccount<'info>,
+
+    /// Payer of the additional rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ResizeLocker<'info> {
+    /// Grows a [Locker] created with an older layout to [Locker::LEN].
+    /// New fields are zero-initialized, which matches their default values.
+    pub fn resize_locker(&mut self) -> Result<()> {
+        let locker_info = self.locker.to_account_info();
+        let prev_len = locker_info.data_len();
+        let next_len = 8 + Locker::LEN;
+        if !resize_escrow::grow_account(
+            &locker_info,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+            next_len,
+        )? {
+            return Ok(());
+        }
+
+        emit!(ResizeLockerEvent {
+            locker: locker_info.key(),
+            prev_len: unwrap_int!(prev_len.to_u64()),
+            next_len: unwrap_int!(next_len.to_u64()),
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for ResizeLocker<'info> {
+    fn validate(&self) -> Result<()> {
+        let data = self.locker.try_borrow_data()?;
+        invariant!(data.len() >= 8, "locker data too small");
+        invariant!(
+            data[..8] == Locker::discriminator(),
+            "account is not a locker"
+        );
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::resize_locker].
+pub struct ResizeLockerEvent {
+    /// The [Locker] being resized.
+    #[index]
+    pub locker: Pubkey,
+    /// Previous size of the account data.
+    pub prev_len: u64,
+    /// New size of the account data.
+    pub next_len: u64,
+}
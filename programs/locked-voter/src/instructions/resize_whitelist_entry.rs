@@ -0,0 +1,70 @@
+//! Handler for [locked_voter::resize_whitelist_entry].
+
+use crate::*;
+use anchor_lang::Discriminator;
+use num_traits::ToPrimitive;
+
+/// Accounts for [locked_voter::resize_whitelist_entry].
+#[derive(Accounts)]
+pub struct ResizeWhitelistEntry<'info> {
+    /// CHECK: The [LockerWhitelistEntry] to resize. Its discriminator is checked in [ResizeWhitelistEntry::validate].
+    #[account(mut, owner = crate::ID)]
+    pub whitelist_entry: UncheckedAccount<'info>,
+
+    /// Payer of the additional rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ResizeWhitelistEntry<'info> {
+    /// Grows a [LockerWhitelistEntry] created with an older layout to [LockerWhitelistEntry::LEN].
+    /// New fields are zero-initialized, which leaves the entry without limits.
+    pub fn resize_whitelist_entry(&mut self) -> Result<()> {
+        let whitelist_entry_info = self.whitelist_entry.to_account_info();
+        let prev_len = whitelist_entry_info.data_len();
+        let next_len = 8 + LockerWhitelistEntry::LEN;
+        if !resize_escrow::grow_account(
+            &whitelist_entry_info,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+            next_len,
+        )? {
+            return Ok(());
+        }
+
+        emit!(ResizeWhitelistEntryEvent {
+            whitelist_entry: whitelist_entry_info.key(),
+            prev_len: unwrap_int!(prev_len.to_u64()),
+            next_len: unwrap_int!(next_len.to_u64()),
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for ResizeWhitelistEntry<'info> {
+    fn validate(&self) -> Result<()> {
+        let data = self.whitelist_entry.try_borrow_data()?;
+        invariant!(data.len() >= 8, "whitelist entry data too small");
+        invariant!(
+            data[..8] == LockerWhitelistEntry::discriminator(),
+            "account is not a whitelist entry"
+        );
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::resize_whitelist_entry].
+pub struct ResizeWhitelistEntryEvent {
+    /// The [LockerWhitelistEntry] being resized.
+    #[index]
+    pub whitelist_entry: Pubkey,
+    /// Previous size of the account data.
+    pub prev_len: u64,
+    /// New size of the account data.
+    pub next_len: u64,
+}
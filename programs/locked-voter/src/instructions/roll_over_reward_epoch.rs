@@ -0,0 +1,91 @@
+//! Handler for [locked_voter::roll_over_reward_epoch].
+
+use crate::*;
+
+/// Accounts for [locked_voter::roll_over_reward_epoch].
+#[derive(Accounts)]
+pub struct RollOverRewardEpoch<'info> {
+    /// The [Locker].
+    pub locker: Box<Account<'info, Locker>>,
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
+    /// The [RewardDistributor].
+    #[account(has_one = locker)]
+    pub distributor: Account<'info, RewardDistributor>,
+    /// The ended [RewardEpoch] whose rewards cannot be claimed.
+    #[account(mut, has_one = distributor)]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    /// The later [RewardEpoch] receiving the rewards.
+    #[account(mut, has_one = distributor)]
+    pub next_reward_epoch: Account<'info, RewardEpoch>,
+}
+
+impl<'info> RollOverRewardEpoch<'info> {
+    /// Moves the rewards left in the [RewardEpoch] to the next [RewardEpoch].
+    pub fn roll_over_reward_epoch(&mut self) -> Result<()> {
+        let amount = self
+            .reward_epoch
+            .roll_over(&self.checkpoints, &self.locker.params)?;
+        let next_reward_epoch = &mut self.next_reward_epoch;
+        next_reward_epoch.amount = unwrap_int!(next_reward_epoch.amount.checked_add(amount));
+
+        emit!(RollOverRewardEpochEvent {
+            distributor: self.distributor.key(),
+            reward_epoch: self.reward_epoch.key(),
+            next_reward_epoch: next_reward_epoch.key(),
+            epoch: self.reward_epoch.epoch,
+            next_epoch: next_reward_epoch.epoch,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for RollOverRewardEpoch<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.checkpoints.locker, self.locker);
+        assert_keys_eq!(self.distributor.locker, self.locker);
+        assert_keys_eq!(self.reward_epoch.distributor, self.distributor);
+        assert_keys_eq!(self.next_reward_epoch.distributor, self.distributor);
+        invariant!(
+            self.next_reward_epoch.epoch > self.reward_epoch.epoch,
+            "rewards must roll over to a later epoch"
+        );
+
+        // the rewards may only move once no more can be deposited, to a week still open for deposits.
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            unwrap_int!(self.reward_epoch.end_ts()) <= now,
+            RewardEpochNotEnded
+        );
+        invariant!(
+            unwrap_int!(self.next_reward_epoch.end_ts()) > now,
+            RewardEpochEnded
+        );
+
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::roll_over_reward_epoch].
+pub struct RollOverRewardEpochEvent {
+    /// The [RewardDistributor].
+    #[index]
+    pub distributor: Pubkey,
+    /// The [RewardEpoch] rolled over.
+    pub reward_epoch: Pubkey,
+    /// The [RewardEpoch] receiving the rewards.
+    pub next_reward_epoch: Pubkey,
+    /// Index of the week rolled over.
+    pub epoch: u64,
+    /// Index of the week receiving the rewards.
+    pub next_epoch: u64,
+    /// Amount of rewards rolled over.
+    pub amount: u64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
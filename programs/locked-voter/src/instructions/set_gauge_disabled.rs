@@ -0,0 +1,62 @@
+//! Handler for [locked_voter::set_gauge_disabled].
+
+use crate::*;
+
+/// Accounts for [locked_voter::set_gauge_disabled].
+#[derive(Accounts)]
+pub struct SetGaugeDisabled<'info> {
+    /// The [Locker].
+    pub locker: Account<'info, Locker>,
+    /// The [GaugeRegistry].
+    #[account(has_one = locker)]
+    pub registry: Account<'info, GaugeRegistry>,
+    /// The [Gauge].
+    #[account(mut, has_one = registry)]
+    pub gauge: Account<'info, Gauge>,
+
+    /// The [Governor].
+    pub governor: Account<'info, Governor>,
+    /// The smart wallet on the [Governor].
+    pub smart_wallet: Signer<'info>,
+}
+
+impl<'info> SetGaugeDisabled<'info> {
+    /// Enables or disables the [Gauge].
+    pub fn set_gauge_disabled(&mut self, is_disabled: bool) -> Result<()> {
+        self.gauge.is_disabled = is_disabled;
+
+        emit!(SetGaugeDisabledEvent {
+            registry: self.registry.key(),
+            gauge: self.gauge.key(),
+            is_disabled,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetGaugeDisabled<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.registry.locker, self.locker);
+        assert_keys_eq!(self.gauge.registry, self.registry);
+        assert_keys_eq!(self.governor, self.locker.governor, "governor mismatch");
+        assert_keys_eq!(self.smart_wallet, self.governor.smart_wallet);
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::set_gauge_disabled].
+pub struct SetGaugeDisabledEvent {
+    /// The [GaugeRegistry].
+    #[index]
+    pub registry: Pubkey,
+    /// The [Gauge].
+    #[index]
+    pub gauge: Pubkey,
+    /// Whether the [Gauge] is now disabled.
+    pub is_disabled: bool,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,94 @@
+//! Handler for [locked_voter::set_gauge_vote_weight].
+
+use crate::*;
+
+/// Accounts for [locked_voter::set_gauge_vote_weight].
+#[derive(Accounts)]
+pub struct SetGaugeVoteWeight<'info> {
+    /// The [Escrow] allocating voting power.
+    pub escrow: Box<Account<'info, Escrow>>,
+    /// The [GaugeVoter] of the [Escrow].
+    #[account(mut, has_one = escrow)]
+    pub gauge_voter: Account<'info, GaugeVoter>,
+    /// The [GaugeVote] to update.
+    #[account(mut, has_one = gauge_voter)]
+    pub gauge_vote: Account<'info, GaugeVote>,
+
+    /// The [Escrow::vote_delegate] while its delegation is active, or the authority of the [Escrow].
+    pub vote_delegate: Signer<'info>,
+}
+
+impl<'info> SetGaugeVoteWeight<'info> {
+    /// Sets the weight of the [GaugeVote]. The new weight applies to commits made afterwards.
+    pub fn set_gauge_vote_weight(&mut self, weight: u32) -> Result<()> {
+        let gauge_voter = &mut self.gauge_voter;
+        let gauge_vote = &mut self.gauge_vote;
+        let prev_weight = gauge_vote.weight;
+        gauge_voter.total_weight = unwrap_int!(unwrap_int!(gauge_voter
+            .total_weight
+            .checked_sub(prev_weight))
+        .checked_add(weight));
+        gauge_vote.weight = weight;
+
+        emit!(SetGaugeVoteWeightEvent {
+            escrow: self.escrow.key(),
+            gauge: gauge_vote.gauge,
+            vote_delegate: self.vote_delegate.key(),
+            prev_weight,
+            weight,
+            total_weight: gauge_voter.total_weight,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> SetGaugeVoteWeight<'info> {
+    /// Validates the accounts. `position_tokens` proves who holds an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        assert_keys_eq!(self.gauge_voter.escrow, self.escrow);
+        assert_keys_eq!(self.gauge_vote.gauge_voter, self.gauge_voter);
+        // delegations scoped to a governor do not extend to gauges.
+        let now = Clock::get()?.unix_timestamp;
+        let authority = self.escrow.authority(position_tokens)?;
+        if self
+            .escrow
+            .active_vote_delegate(authority, Pubkey::default(), now)
+            != self.vote_delegate.key()
+        {
+            assert_keys_eq!(authority, self.vote_delegate);
+        }
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetGaugeVoteWeight<'info> {
+    fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::set_gauge_vote_weight].
+pub struct SetGaugeVoteWeightEvent {
+    /// The [Escrow].
+    #[index]
+    pub escrow: Pubkey,
+    /// The [Gauge].
+    #[index]
+    pub gauge: Pubkey,
+    /// The signer that set the weight.
+    pub vote_delegate: Pubkey,
+    /// The previous weight.
+    pub prev_weight: u32,
+    /// The new weight.
+    pub weight: u32,
+    /// The new [GaugeVoter::total_weight].
+    pub total_weight: u32,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
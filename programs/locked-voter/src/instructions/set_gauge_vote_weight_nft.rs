@@ -0,0 +1,25 @@
+//! Handler for [locked_voter::set_gauge_vote_weight_nft].
+
+use crate::*;
+
+/// Accounts for [locked_voter::set_gauge_vote_weight_nft].
+#[derive(Accounts)]
+pub struct SetGaugeVoteWeightNft<'info> {
+    /// Gauge vote accounts. [SetGaugeVoteWeight::vote_delegate] is the holder of the position token or its delegate.
+    pub set_gauge_vote_weight: SetGaugeVoteWeight<'info>,
+    /// Token account of the holder containing the position token.
+    pub position_tokens: Account<'info, TokenAccount>,
+}
+
+pub fn handler(ctx: Context<SetGaugeVoteWeightNft>, weight: u32) -> Result<()> {
+    ctx.accounts
+        .set_gauge_vote_weight
+        .set_gauge_vote_weight(weight)
+}
+
+impl<'info> Validate<'info> for SetGaugeVoteWeightNft<'info> {
+    fn validate(&self) -> Result<()> {
+        self.set_gauge_vote_weight
+            .validate_with_position_tokens(Some(&self.position_tokens))
+    }
+}
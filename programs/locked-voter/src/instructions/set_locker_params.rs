@@ -15,7 +15,19 @@ pub struct SetLockerParams<'info> {
 }
 
 impl<'info> SetLockerParams<'info> {
-    pub fn set_locker_params(&mut self, params: LockerParams) -> Result<()> {
+    /// Replaces the [Locker::params] immediately.
+    ///
+    /// Only possible while nothing is locked, e.g. while setting up the [Locker].
+    /// Afterwards, params must go through [locked_voter::stage_locker_params].
+    /// `accounts` must include the [LockerParams::early_exit_penalty_recipient], if set.
+    pub fn set_locker_params(
+        &mut self,
+        params: LockerParams,
+        accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        params.validate_update(&self.locker.params, &self.locker.params_bounds())?;
+        params.validate_penalty_recipient(self.locker.token_mint, accounts)?;
+
         let prev_params = self.locker.params;
         self.locker.params = params;
 
@@ -33,6 +45,7 @@ impl<'info> Validate<'info> for SetLockerParams<'info> {
     fn validate(&self) -> Result<()> {
         assert_keys_eq!(self.governor, self.locker.governor, "governor mismatch");
         assert_keys_eq!(self.smart_wallet, self.governor.smart_wallet);
+        invariant!(self.locker.locked_supply == 0, LockerParamsMustBeStaged);
         Ok(())
     }
 }
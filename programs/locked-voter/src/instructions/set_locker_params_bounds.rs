@@ -0,0 +1,59 @@
+//! Handler for [locked_voter::set_locker_params_bounds].
+
+use crate::*;
+
+/// Accounts for [locked_voter::set_locker_params_bounds].
+#[derive(Accounts)]
+pub struct SetLockerParamsBounds<'info> {
+    /// The [Locker].
+    #[account(mut)]
+    pub locker: Account<'info, Locker>,
+    /// The [Governor].
+    pub governor: Account<'info, Governor>,
+    /// The smart wallet on the [Governor].
+    pub smart_wallet: Signer<'info>,
+}
+
+impl<'info> SetLockerParamsBounds<'info> {
+    /// Replaces the [Locker::params_bounds].
+    ///
+    /// The current and staged [LockerParams] must be within the new bounds.
+    pub fn set_locker_params_bounds(&mut self, bounds: LockerParamsBounds) -> Result<()> {
+        let locker = &mut self.locker;
+        locker.params.validate_with_bounds(&bounds)?;
+        if locker.has_pending_params() {
+            locker.pending_params.validate_with_bounds(&bounds)?;
+        }
+
+        let prev_bounds = locker.params_bounds();
+        locker.params_bounds = bounds;
+
+        emit!(SetLockerParamsBoundsEvent {
+            locker: locker.key(),
+            prev_bounds,
+            bounds,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetLockerParamsBounds<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.governor, self.locker.governor, "governor mismatch");
+        assert_keys_eq!(self.smart_wallet, self.governor.smart_wallet);
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::set_locker_params_bounds].
+pub struct SetLockerParamsBoundsEvent {
+    /// The [Locker].
+    #[index]
+    pub locker: Pubkey,
+    /// Previous bounds.
+    pub prev_bounds: LockerParamsBounds,
+    /// New bounds.
+    pub bounds: LockerParamsBounds,
+}
@@ -0,0 +1,29 @@
+//! Handler for [locked_voter::set_nft_vote_delegate].
+
+use crate::*;
+
+/// Accounts for [locked_voter::set_nft_vote_delegate].
+#[derive(Accounts)]
+pub struct SetNftVoteDelegate<'info> {
+    /// Delegate accounts. [SetVoteDelegate::escrow_owner] is the holder of the position token.
+    pub set_vote_delegate: SetVoteDelegate<'info>,
+    /// Token account of the holder containing the position token.
+    pub position_tokens: Account<'info, TokenAccount>,
+}
+
+pub fn handler(
+    ctx: Context<SetNftVoteDelegate>,
+    new_delegate: Pubkey,
+    terms: DelegationTerms,
+) -> Result<()> {
+    ctx.accounts
+        .set_vote_delegate
+        .set_vote_delegate(new_delegate, terms)
+}
+
+impl<'info> Validate<'info> for SetNftVoteDelegate<'info> {
+    fn validate(&self) -> Result<()> {
+        self.set_vote_delegate
+            .validate_with_position_tokens(Some(&self.position_tokens))
+    }
+}
@@ -0,0 +1,29 @@
+//! Handler for [locked_voter::set_nft_vote_delegation].
+
+use crate::*;
+
+/// Accounts for [locked_voter::set_nft_vote_delegation].
+#[derive(Accounts)]
+pub struct SetNftVoteDelegation<'info> {
+    /// Delegation accounts. [SetVoteDelegation::escrow_owner] is the holder of the position token.
+    pub set_vote_delegation: SetVoteDelegation<'info>,
+    /// Token account of the holder containing the position token.
+    pub position_tokens: Account<'info, TokenAccount>,
+}
+
+pub fn handler(
+    ctx: Context<SetNftVoteDelegation>,
+    shares: Vec<DelegateShare>,
+    terms: DelegationTerms,
+) -> Result<()> {
+    ctx.accounts
+        .set_vote_delegation
+        .set_vote_delegation(shares, terms)
+}
+
+impl<'info> Validate<'info> for SetNftVoteDelegation<'info> {
+    fn validate(&self) -> Result<()> {
+        self.set_vote_delegation
+            .validate_with_position_tokens(Some(&self.position_tokens))
+    }
+}
@@ -5,34 +5,62 @@ use crate::*;
 pub struct SetVoteDelegate<'info> {
     /// The [Escrow].
     #[account(mut)]
-    pub escrow: Account<'info, Escrow>,
+    pub escrow: Box<Account<'info, Escrow>>,
     /// The owner of the [Escrow].
+    /// For NFT escrows, this is the holder of the position token.
     pub escrow_owner: Signer<'info>,
 }
 
 impl<'info> SetVoteDelegate<'info> {
-    pub fn set_vote_delegate(&mut self, new_delegate: Pubkey) -> Result<()> {
-        let old_delegate = self.escrow.vote_delegate;
-        self.escrow.vote_delegate = new_delegate;
+    pub fn set_vote_delegate(
+        &mut self,
+        new_delegate: Pubkey,
+        terms: DelegationTerms,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        terms.validate(now)?;
+        let authority = self.escrow_owner.key();
+        let escrow = &mut self.escrow;
+        let old_delegate = escrow.vote_delegate;
+        if !escrow.delegation_applies(authority) {
+            // the shares were split by a previous holder of the position token.
+            escrow.delegated_bps = 0;
+            escrow.delegation_updated_at = now;
+        }
+        escrow.vote_delegate = new_delegate;
+        escrow.vote_delegate_terms = terms;
+        escrow.delegated_by = authority;
 
         emit!(SetVoteDelegateEvent {
             escrow_owner: self.escrow.owner,
             old_delegate,
             new_delegate,
+            terms,
         });
 
         Ok(())
     }
 }
 
-impl<'info> Validate<'info> for SetVoteDelegate<'info> {
-    fn validate(&self) -> Result<()> {
-        assert_keys_eq!(self.escrow.owner, self.escrow_owner);
+impl<'info> SetVoteDelegate<'info> {
+    /// Validates the accounts. `position_tokens` proves authority over an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        self.escrow
+            .assert_authority(self.escrow_owner.key(), position_tokens)?;
 
         Ok(())
     }
 }
 
+impl<'info> Validate<'info> for SetVoteDelegate<'info> {
+    fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+}
+
 #[event]
 /// Event called in [locked_voter::set_vote_delegate].
 pub struct SetVoteDelegateEvent {
@@ -43,4 +71,6 @@ pub struct SetVoteDelegateEvent {
     pub old_delegate: Pubkey,
     /// The new escrow delegate.
     pub new_delegate: Pubkey,
+    /// Terms of the new delegation.
+    pub terms: DelegationTerms,
 }
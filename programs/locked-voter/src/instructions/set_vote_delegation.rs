@@ -0,0 +1,97 @@
+//! Handler for [locked_voter::set_vote_delegation].
+
+use crate::*;
+
+/// Accounts for [locked_voter::set_vote_delegation].
+#[derive(Accounts)]
+pub struct SetVoteDelegation<'info> {
+    /// The [Escrow].
+    #[account(mut)]
+    pub escrow: Box<Account<'info, Escrow>>,
+    /// The [VoteDelegation] of the [Escrow].
+    #[account(mut, has_one = escrow)]
+    pub vote_delegation: Account<'info, VoteDelegation>,
+    /// The owner of the [Escrow].
+    /// For NFT escrows, this is the holder of the position token.
+    pub escrow_owner: Signer<'info>,
+}
+
+impl<'info> SetVoteDelegation<'info> {
+    /// Replaces the [DelegateShare]s of the [VoteDelegation].
+    ///
+    /// The remaining share stays with the [Escrow::vote_delegate].
+    /// Votes already cast keep their weight, so the [Escrow] may not vote on
+    /// proposals activated before the change.
+    pub fn set_vote_delegation(
+        &mut self,
+        shares: Vec<DelegateShare>,
+        terms: DelegationTerms,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let delegated_bps = DelegateShare::total_bps(&shares)?;
+        terms.validate(now)?;
+        let authority = self.escrow_owner.key();
+        let escrow = &mut self.escrow;
+        let prev_delegated_bps = escrow.delegated_bps;
+        if !escrow.delegation_applies(authority) {
+            // the vote delegate was set by a previous holder of the position token.
+            escrow.vote_delegate = escrow.owner;
+            escrow.vote_delegate_terms = DelegationTerms::default();
+        }
+        escrow.delegated_bps = delegated_bps;
+        escrow.delegated_by = authority;
+        escrow.delegation_updated_at = now;
+        self.vote_delegation.terms = terms;
+        self.vote_delegation.shares = shares;
+
+        emit!(SetVoteDelegationEvent {
+            escrow_owner: self.escrow.owner,
+            escrow: self.escrow.key(),
+            shares: self.vote_delegation.shares.clone(),
+            terms,
+            prev_delegated_bps,
+            delegated_bps,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> SetVoteDelegation<'info> {
+    /// Validates the accounts. `position_tokens` proves authority over an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        assert_keys_eq!(self.vote_delegation.escrow, self.escrow);
+        self.escrow
+            .assert_authority(self.escrow_owner.key(), position_tokens)?;
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetVoteDelegation<'info> {
+    fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::set_vote_delegation].
+pub struct SetVoteDelegationEvent {
+    /// The owner of the [Escrow].
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// The [Escrow].
+    #[index]
+    pub escrow: Pubkey,
+    /// The new delegate shares.
+    pub shares: Vec<DelegateShare>,
+    /// The new terms of the delegation.
+    pub terms: DelegationTerms,
+    /// The previous [Escrow::delegated_bps].
+    pub prev_delegated_bps: u16,
+    /// The new [Escrow::delegated_bps].
+    pub delegated_bps: u16,
+}
@@ -0,0 +1,72 @@
+//! Handler for [locked_voter::set_whitelist_entry_limits].
+
+use crate::*;
+
+/// Accounts for [locked_voter::set_whitelist_entry_limits].
+#[derive(Accounts)]
+pub struct SetWhitelistEntryLimits<'info> {
+    /// The [Locker].
+    pub locker: Account<'info, Locker>,
+    /// The [LockerWhitelistEntry].
+    #[account(mut, has_one = locker)]
+    pub whitelist_entry: Account<'info, LockerWhitelistEntry>,
+
+    /// The [Governor].
+    pub governor: Account<'info, Governor>,
+    /// The smart wallet on the [Governor].
+    pub smart_wallet: Signer<'info>,
+}
+
+impl<'info> SetWhitelistEntryLimits<'info> {
+    /// Sets the [WhitelistEntryLimits] of the [LockerWhitelistEntry].
+    ///
+    /// The [LockerWhitelistEntry::locked_amount] is kept, so a cap may be raised without resetting it.
+    pub fn set_whitelist_entry_limits(&mut self, limits: WhitelistEntryLimits) -> Result<()> {
+        limits.validate()?;
+        invariant!(
+            limits.min_stake_duration <= self.locker.params.max_stake_duration,
+            InvalidWhitelistEntryLimits
+        );
+        let whitelist_entry = &mut self.whitelist_entry;
+        let prev_limits = whitelist_entry.limits;
+        whitelist_entry.limits = limits;
+
+        emit!(SetWhitelistEntryLimitsEvent {
+            locker: whitelist_entry.locker,
+            program_id: whitelist_entry.program_id,
+            owner: whitelist_entry.owner,
+            prev_limits,
+            limits,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetWhitelistEntryLimits<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.whitelist_entry.locker, self.locker);
+        assert_keys_eq!(self.governor, self.locker.governor, "governor mismatch");
+        assert_keys_eq!(self.smart_wallet, self.governor.smart_wallet);
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::set_whitelist_entry_limits].
+pub struct SetWhitelistEntryLimitsEvent {
+    /// The [Locker].
+    #[index]
+    pub locker: Pubkey,
+    /// ProgramId of the [LockerWhitelistEntry].
+    pub program_id: Pubkey,
+    /// Owner of the [LockerWhitelistEntry].
+    pub owner: Pubkey,
+    /// Previous [WhitelistEntryLimits].
+    pub prev_limits: WhitelistEntryLimits,
+    /// New [WhitelistEntryLimits].
+    pub limits: WhitelistEntryLimits,
+    /// Timestamp of the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,69 @@
+//! Handler for [locked_voter::snapshot_reward_epoch].
+
+use crate::*;
+
+/// Accounts for [locked_voter::snapshot_reward_epoch].
+#[derive(Accounts)]
+pub struct SnapshotRewardEpoch<'info> {
+    /// The [Locker].
+    pub locker: Box<Account<'info, Locker>>,
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
+    /// The [RewardDistributor].
+    #[account(has_one = locker)]
+    pub distributor: Account<'info, RewardDistributor>,
+    /// The [RewardEpoch] to snapshot.
+    #[account(mut, has_one = distributor)]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+}
+
+impl<'info> SnapshotRewardEpoch<'info> {
+    /// Records the [RewardEpoch::total_voting_power] at the start of the week.
+    pub fn snapshot_reward_epoch(&mut self) -> Result<()> {
+        let reward_epoch = &mut self.reward_epoch;
+        reward_epoch.record_total_voting_power(&self.checkpoints, &self.locker.params)?;
+
+        emit!(SnapshotRewardEpochEvent {
+            distributor: self.distributor.key(),
+            reward_epoch: reward_epoch.key(),
+            epoch: reward_epoch.epoch,
+            total_voting_power: reward_epoch.total_voting_power,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SnapshotRewardEpoch<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.checkpoints.locker, self.locker);
+        assert_keys_eq!(self.distributor.locker, self.locker);
+        assert_keys_eq!(self.reward_epoch.distributor, self.distributor);
+
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            unwrap_int!(self.reward_epoch.start_ts()) <= now,
+            RewardEpochNotStarted
+        );
+
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::snapshot_reward_epoch].
+pub struct SnapshotRewardEpochEvent {
+    /// The [RewardDistributor].
+    #[index]
+    pub distributor: Pubkey,
+    /// The [RewardEpoch] snapshotted.
+    pub reward_epoch: Pubkey,
+    /// Index of the week.
+    pub epoch: u64,
+    /// Total voting power of the [Locker] at the start of the week.
+    pub total_voting_power: u64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,220 @@
+//! Handler for [locked_voter::split_escrow].
+
+use crate::errors::LockedVoterError;
+use crate::*;
+use anchor_spl::token;
+
+/// Accounts for [locked_voter::split_escrow].
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct SplitEscrow<'info> {
+    /// The [Locker] of both [Escrow]s.
+    #[account(mut)]
+    pub locker: Box<Account<'info, Locker>>,
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
+
+    /// The [Escrow] being split.
+    #[account(mut, has_one = locker)]
+    pub source_escrow: Box<Account<'info, Escrow>>,
+    /// Tokens locked up in the [Self::source_escrow].
+    #[account(mut, constraint = source_escrow.tokens == source_escrow_tokens.key())]
+    pub source_escrow_tokens: Box<Account<'info, TokenAccount>>,
+    /// CHECK: [Blacklist] of the [Self::source_escrow]. Must not exist.
+    #[account(
+        seeds = [
+            b"Blacklist".as_ref(),
+            locker.key().as_ref(),
+            source_escrow.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub source_blacklist: UncheckedAccount<'info>,
+
+    /// The new position [Escrow] receiving the tokens.
+    #[account(
+        init,
+        seeds = [
+            b"Escrow".as_ref(),
+            locker.key().to_bytes().as_ref(),
+            destination_owner.key().to_bytes().as_ref(),
+            position_id.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + Escrow::LEN
+    )]
+    pub destination_escrow: Box<Account<'info, Escrow>>,
+    /// Token account of the [Self::destination_escrow]. Must be its ATA.
+    #[account(mut)]
+    pub destination_escrow_tokens: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Owner of the new [Escrow]. May be the same as the owner of the source.
+    pub destination_owner: UncheckedAccount<'info>,
+
+    /// Authority of the [Self::source_escrow].
+    pub escrow_owner: Signer<'info>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SplitEscrow<'info> {
+    /// Moves `amount` tokens of the source [Escrow] into a new position [Escrow]
+    /// with the same lock times.
+    pub fn split_escrow(&mut self, bump: u8, position_id: u64, amount: u64) -> Result<()> {
+        invariant!(position_id > 0, InvalidPositionId);
+        invariant!(amount > 0, WithdrawAmountZero);
+        invariant!(amount <= self.source_escrow.amount, WithdrawAmountTooLarge);
+
+        let destination = &mut self.destination_escrow;
+        destination.locker = self.locker.key();
+        destination.owner = self.destination_owner.key();
+        destination.bump = bump;
+        destination.tokens = anchor_spl::associated_token::get_associated_token_address(
+            &destination.key(),
+            &self.locker.token_mint,
+        );
+        destination.amount = 0;
+        destination.escrow_started_at = 0;
+        destination.escrow_ends_at = 0;
+        destination.vote_delegate = self.destination_owner.key();
+        destination.position_id = position_id;
+        destination.position_mint = Pubkey::default();
+        destination.max_lock = self.source_escrow.max_lock;
+        assert_keys_eq!(destination.tokens, self.destination_escrow_tokens);
+
+        let seeds: &[&[&[u8]]] = escrow_seeds!(self.source_escrow);
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.source_escrow_tokens.to_account_info(),
+                    to: self.destination_escrow_tokens.to_account_info(),
+                    authority: self.source_escrow.to_account_info(),
+                },
+            )
+            .with_signer(seeds),
+            amount,
+        )?;
+
+        // both sides keep the lock times of the source, so neither lock gets shorter.
+        let now = Clock::get()?.unix_timestamp;
+        let locker = &mut self.locker;
+        let source = &mut self.source_escrow;
+        let destination = &mut self.destination_escrow;
+        let prev_source_checkpoint = unwrap_int!(locker.params.escrow_checkpoint(source, now));
+        source.record_unlock_event(locker, amount)?;
+        let deposits = source.take_deposits(amount);
+        destination.record_lock_event(
+            locker,
+            amount,
+            source.escrow_started_at,
+            source.escrow_ends_at,
+        )?;
+        // the split off tokens keep their deposit timestamps.
+        for deposit in deposits {
+            destination.add_deposit(deposit);
+        }
+        // tokens locked through a whitelist entry still count towards it.
+        let whitelist_entry = source.whitelist_entry;
+        let retained_amount = source.amount;
+        let whitelist_locked_amount = source.release_whitelist_lock(retained_amount);
+        destination.record_whitelist_lock(whitelist_entry, whitelist_locked_amount)?;
+        destination.checkpointed = true;
+        // the source is checkpointed as a whole, even if it predates the checkpoints.
+        source.checkpointed = true;
+
+        source.record_lock_checkpoint(now);
+        destination.record_lock_checkpoint(now);
+
+        let next_source_checkpoint = unwrap_int!(locker.params.escrow_checkpoint(source, now));
+        let next_destination_checkpoint =
+            unwrap_int!(locker.params.escrow_checkpoint(destination, now));
+        self.checkpoints.record_escrow_change(
+            prev_source_checkpoint,
+            next_source_checkpoint,
+            now,
+        )?;
+        self.checkpoints.record_escrow_change(
+            EscrowCheckpoint::default(),
+            next_destination_checkpoint,
+            now,
+        )?;
+
+        emit!(SplitEscrowEvent {
+            locker: locker.key(),
+            escrow_owner: source.owner,
+            source_escrow: source.key(),
+            destination_owner: destination.owner,
+            destination_escrow: destination.key(),
+            position_id,
+            amount,
+            escrow_ends_at: destination.escrow_ends_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> SplitEscrow<'info> {
+    /// Validates the accounts. `position_tokens` proves authority over an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        assert_keys_eq!(self.checkpoints.locker, self.locker);
+        assert_keys_eq!(self.source_escrow.locker, self.locker);
+        self.source_escrow
+            .assert_authority(self.escrow_owner.key(), position_tokens)?;
+        assert_keys_eq!(self.source_escrow.tokens, self.source_escrow_tokens);
+        invariant!(!self.source_escrow.is_vesting(), VestingEscrow);
+        assert_keys_eq!(self.destination_escrow_tokens.mint, self.locker.token_mint);
+
+        invariant!(
+            self.source_blacklist.data_is_empty(),
+            LockedVoterError::EscrowBlacklisted
+        );
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SplitEscrow<'info> {
+    fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::split_escrow].
+pub struct SplitEscrowEvent {
+    /// The [Locker] of the [Escrow]s.
+    #[index]
+    pub locker: Pubkey,
+    /// The owner of the source [Escrow].
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// The [Escrow] that was split.
+    pub source_escrow: Pubkey,
+    /// The owner of the new [Escrow].
+    #[index]
+    pub destination_owner: Pubkey,
+    /// The new [Escrow].
+    pub destination_escrow: Pubkey,
+    /// The [Escrow::position_id] of the new [Escrow].
+    pub position_id: u64,
+    /// Amount of tokens moved.
+    pub amount: u64,
+    /// The end time of both [Escrow]s.
+    pub escrow_ends_at: i64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,26 @@
+//! Handler for [locked_voter::split_escrow_nft].
+
+use crate::*;
+
+/// Accounts for [locked_voter::split_escrow_nft].
+#[derive(Accounts)]
+pub struct SplitEscrowNft<'info> {
+    /// Split accounts. [SplitEscrow::escrow_owner] is the holder of the position token.
+    pub split_escrow: SplitEscrow<'info>,
+    /// Token account of the holder containing the position token.
+    pub position_tokens: Account<'info, TokenAccount>,
+}
+
+pub fn handler(ctx: Context<SplitEscrowNft>, position_id: u64, amount: u64) -> Result<()> {
+    let bump = unwrap_bump!(ctx, "destination_escrow");
+    ctx.accounts
+        .split_escrow
+        .split_escrow(bump, position_id, amount)
+}
+
+impl<'info> Validate<'info> for SplitEscrowNft<'info> {
+    fn validate(&self) -> Result<()> {
+        self.split_escrow
+            .validate_with_position_tokens(Some(&self.position_tokens))
+    }
+}
@@ -0,0 +1,74 @@
+//! Handler for [locked_voter::stage_locker_params].
+
+use crate::*;
+use num_traits::ToPrimitive;
+
+/// Accounts for [locked_voter::stage_locker_params].
+#[derive(Accounts)]
+pub struct StageLockerParams<'info> {
+    /// The [Locker].
+    #[account(mut)]
+    pub locker: Account<'info, Locker>,
+    /// The [Governor].
+    pub governor: Account<'info, Governor>,
+    /// The smart wallet on the [Governor].
+    pub smart_wallet: Signer<'info>,
+}
+
+impl<'info> StageLockerParams<'info> {
+    /// Stages `params` to be applied at `activates_at`, replacing any staged params.
+    ///
+    /// `activates_at` must be at least one voting period away, so proposals that are
+    /// already being voted on finish under the current params.
+    /// `accounts` must include the [LockerParams::early_exit_penalty_recipient], if set.
+    pub fn stage_locker_params(
+        &mut self,
+        params: LockerParams,
+        activates_at: i64,
+        accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        params.validate_update(&self.locker.params, &self.locker.params_bounds())?;
+        params.validate_penalty_recipient(self.locker.token_mint, accounts)?;
+        let now = Clock::get()?.unix_timestamp;
+        let voting_period = unwrap_int!(self.governor.params.voting_period.to_i64());
+        invariant!(
+            activates_at >= unwrap_int!(now.checked_add(voting_period)) && activates_at > now,
+            ParamsActivationTooEarly
+        );
+
+        let locker = &mut self.locker;
+        locker.pending_params = params;
+        locker.pending_params_activates_at = activates_at;
+
+        emit!(StageLockerParamsEvent {
+            locker: locker.key(),
+            params,
+            activates_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for StageLockerParams<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.governor, self.locker.governor, "governor mismatch");
+        assert_keys_eq!(self.smart_wallet, self.governor.smart_wallet);
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::stage_locker_params].
+pub struct StageLockerParamsEvent {
+    /// The [Locker].
+    #[index]
+    pub locker: Pubkey,
+    /// The staged [LockerParams].
+    pub params: LockerParams,
+    /// When the staged [LockerParams] may be applied.
+    pub activates_at: i64,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+}
@@ -0,0 +1,125 @@
+//! Handler for [locked_voter::withdraw_partial].
+
+use crate::*;
+use anchor_spl::token;
+
+/// Accounts for [locked_voter::withdraw_partial].
+#[derive(Accounts)]
+pub struct WithdrawPartial<'info> {
+    /// The [Locker] being withdrawn from.
+    #[account(mut)]
+    pub locker: Account<'info, Locker>,
+
+    /// The [Escrow] that is being withdrawn from. It is kept open.
+    #[account(mut, has_one = locker)]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    /// Authority of the [Escrow].
+    pub escrow_owner: Signer<'info>,
+    /// Tokens locked up in the [Escrow].
+    #[account(mut, constraint = escrow.tokens == escrow_tokens.key())]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+    /// Destination for the tokens to unlock.
+    #[account(mut)]
+    pub destination_tokens: Account<'info, TokenAccount>,
+
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawPartial<'info> {
+    /// Withdraws `amount` tokens from an unlocked [Escrow] without closing it.
+    ///
+    /// If the withdrawal leaves less than the [Escrow::whitelist_locked_amount] in the [Escrow],
+    /// its [Escrow::whitelist_entry] must be in `ra`.
+    pub fn withdraw_partial(&mut self, ra: &[AccountInfo], amount: u64) -> Result<()> {
+        invariant!(amount > 0, WithdrawAmountZero);
+        invariant!(amount <= self.escrow.amount, WithdrawAmountTooLarge);
+
+        let seeds: &[&[&[u8]]] = escrow_seeds!(self.escrow);
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.escrow_tokens.to_account_info(),
+                    to: self.destination_tokens.to_account_info(),
+                    authority: self.escrow.to_account_info(),
+                },
+            )
+            .with_signer(seeds),
+            amount,
+        )?;
+
+        // update the escrow and locker
+        let now = Clock::get()?.unix_timestamp;
+        let locker = &mut self.locker;
+        let escrow = &mut self.escrow;
+        escrow.record_unlock_event(locker, amount)?;
+        escrow.record_lock_checkpoint(now);
+        let retained_amount = escrow.amount;
+        release_from_whitelist_entry(escrow, ra, retained_amount)?;
+
+        emit!(WithdrawPartialEvent {
+            escrow_owner: escrow.owner,
+            locker: locker.key(),
+            timestamp: now,
+            locker_supply: locker.locked_supply,
+            released_amount: amount,
+            remaining_amount: escrow.amount,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> WithdrawPartial<'info> {
+    /// Validates the accounts. `position_tokens` proves authority over an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        assert_keys_eq!(self.locker, self.escrow.locker);
+        self.escrow
+            .assert_authority(self.escrow_owner.key(), position_tokens)?;
+        assert_keys_eq!(self.escrow.tokens, self.escrow_tokens);
+        let now = Clock::get()?.unix_timestamp;
+        msg!(
+            "now: {}; escrow_ends_at: {}",
+            now,
+            self.escrow.escrow_ends_at
+        );
+        invariant!(!self.escrow.max_lock, MaxLockEnabled);
+        // vesting escrows are withdrawn from via withdraw_vested.
+        invariant!(!self.escrow.is_vesting(), VestingEscrow);
+        invariant!(self.escrow.escrow_ends_at < now, EscrowNotEnded);
+
+        assert_keys_neq!(self.escrow_tokens, self.destination_tokens);
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for WithdrawPartial<'info> {
+    fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::withdraw_partial].
+pub struct WithdrawPartialEvent {
+    /// The owner of the [Escrow].
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// The locker for the [Escrow].
+    #[index]
+    pub locker: Pubkey,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+    /// The amount of tokens locked inside the [Locker].
+    pub locker_supply: u64,
+    /// The amount released from the [Escrow].
+    pub released_amount: u64,
+    /// The amount of tokens remaining in the [Escrow].
+    pub remaining_amount: u64,
+}
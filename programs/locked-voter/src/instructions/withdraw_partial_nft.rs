@@ -0,0 +1,28 @@
+//! Handler for [locked_voter::withdraw_partial_nft].
+
+use crate::*;
+
+/// Accounts for [locked_voter::withdraw_partial_nft].
+#[derive(Accounts)]
+pub struct WithdrawPartialNft<'info> {
+    /// Withdraw accounts. [WithdrawPartial::escrow_owner] is the holder of the position token.
+    pub withdraw_partial: WithdrawPartial<'info>,
+    /// Token account of the holder containing the position token.
+    pub position_tokens: Account<'info, TokenAccount>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawPartialNft<'info>>,
+    amount: u64,
+) -> Result<()> {
+    ctx.accounts
+        .withdraw_partial
+        .withdraw_partial(ctx.remaining_accounts, amount)
+}
+
+impl<'info> Validate<'info> for WithdrawPartialNft<'info> {
+    fn validate(&self) -> Result<()> {
+        self.withdraw_partial
+            .validate_with_position_tokens(Some(&self.position_tokens))
+    }
+}
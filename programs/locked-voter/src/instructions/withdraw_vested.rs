@@ -0,0 +1,127 @@
+//! Handler for [locked_voter::withdraw_vested].
+
+use crate::*;
+use anchor_spl::token;
+
+/// Accounts for [locked_voter::withdraw_vested].
+#[derive(Accounts)]
+pub struct WithdrawVested<'info> {
+    /// The [Locker] being withdrawn from.
+    #[account(mut)]
+    pub locker: Account<'info, Locker>,
+
+    /// The vesting [Escrow] that is being withdrawn from. It is kept open,
+    /// and stops vesting once everything has been withdrawn.
+    #[account(mut, has_one = locker)]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
+
+    /// Authority of the [Escrow].
+    pub escrow_owner: Signer<'info>,
+    /// Tokens locked up in the [Escrow].
+    #[account(mut, constraint = escrow.tokens == escrow_tokens.key())]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+    /// Destination for the unlocked tokens.
+    #[account(mut)]
+    pub destination_tokens: Account<'info, TokenAccount>,
+
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawVested<'info> {
+    /// Withdraws all tokens of the [Escrow] which have unlocked so far.
+    pub fn withdraw_vested(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let locked_amount = unwrap_int!(self.escrow.locked_amount_at(now));
+        let amount = unwrap_int!(self.escrow.amount.checked_sub(locked_amount));
+        invariant!(amount > 0, WithdrawAmountZero);
+
+        let seeds: &[&[&[u8]]] = escrow_seeds!(self.escrow);
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.escrow_tokens.to_account_info(),
+                    to: self.destination_tokens.to_account_info(),
+                    authority: self.escrow.to_account_info(),
+                },
+            )
+            .with_signer(seeds),
+            amount,
+        )?;
+
+        // update the escrow and locker
+        let locker = &mut self.locker;
+        let escrow = &mut self.escrow;
+        let prev_checkpoint = unwrap_int!(locker.params.escrow_checkpoint(escrow, now));
+        escrow.record_unlock_event(locker, amount)?;
+        if escrow.amount == 0 {
+            // the schedule is over, so the escrow may be locked again.
+            escrow.vesting_cliff_at = 0;
+            escrow.vesting_amount = 0;
+        }
+        escrow.record_lock_checkpoint(now);
+        let next_checkpoint = unwrap_int!(locker.params.escrow_checkpoint(escrow, now));
+        self.checkpoints
+            .record_escrow_change(prev_checkpoint, next_checkpoint, now)?;
+
+        emit!(WithdrawVestedEvent {
+            escrow_owner: escrow.owner,
+            locker: locker.key(),
+            timestamp: now,
+            locker_supply: locker.locked_supply,
+            released_amount: amount,
+            remaining_amount: escrow.amount,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> WithdrawVested<'info> {
+    /// Validates the accounts. `position_tokens` proves authority over an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        assert_keys_eq!(self.locker, self.escrow.locker);
+        assert_keys_eq!(self.checkpoints.locker, self.locker);
+        self.escrow
+            .assert_authority(self.escrow_owner.key(), position_tokens)?;
+        assert_keys_eq!(self.escrow.tokens, self.escrow_tokens);
+        invariant!(self.escrow.is_vesting(), EscrowNotVesting);
+
+        assert_keys_neq!(self.escrow_tokens, self.destination_tokens);
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for WithdrawVested<'info> {
+    fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::withdraw_vested].
+pub struct WithdrawVestedEvent {
+    /// The owner of the [Escrow].
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// The locker for the [Escrow].
+    #[index]
+    pub locker: Pubkey,
+    /// Timestamp for the event.
+    pub timestamp: i64,
+    /// The amount of tokens locked inside the [Locker].
+    pub locker_supply: u64,
+    /// The amount released from the [Escrow].
+    pub released_amount: u64,
+    /// The amount of tokens still locked in the [Escrow].
+    pub remaining_amount: u64,
+}
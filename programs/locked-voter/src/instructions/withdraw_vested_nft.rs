@@ -0,0 +1,23 @@
+//! Handler for [locked_voter::withdraw_vested_nft].
+
+use crate::*;
+
+/// Accounts for [locked_voter::withdraw_vested_nft].
+#[derive(Accounts)]
+pub struct WithdrawVestedNft<'info> {
+    /// Withdraw accounts. [WithdrawVested::escrow_owner] is the holder of the position token.
+    pub withdraw_vested: WithdrawVested<'info>,
+    /// Token account of the holder containing the position token.
+    pub position_tokens: Account<'info, TokenAccount>,
+}
+
+pub fn handler(ctx: Context<WithdrawVestedNft>) -> Result<()> {
+    ctx.accounts.withdraw_vested.withdraw_vested()
+}
+
+impl<'info> Validate<'info> for WithdrawVestedNft<'info> {
+    fn validate(&self) -> Result<()> {
+        self.withdraw_vested
+            .validate_with_position_tokens(Some(&self.position_tokens))
+    }
+}
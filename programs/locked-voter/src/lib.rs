@@ -10,13 +10,18 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 use govern::{Governor, Proposal, Vote};
 use vipers::prelude::*;
 
+mod checkpoints;
 mod errors;
 mod instructions;
 pub mod locker;
+mod params;
+mod positions;
 mod redeem;
 mod state;
 
+pub use checkpoints::*;
 pub use instructions::*;
+pub use params::*;
 pub use redeem::*;
 pub use state::*;
 
@@ -30,9 +35,36 @@ pub mod locked_voter {
     use super::*;
 
     /// Creates a new [Locker].
+    ///
+    /// If the params set an early exit penalty recipient, its token account must be passed
+    /// as a remaining account.
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_locker<'info>(
+        ctx: Context<'_, '_, '_, 'info, NewLocker<'info>>,
+        _bump: u8,
+        params: LockerParams,
+    ) -> Result<()> {
+        ctx.accounts
+            .new_locker(unwrap_bump!(ctx, "locker"), params, ctx.remaining_accounts)
+    }
+
+    /// Resizes a [Locker] created before new fields were added to its [LockerParams].
+    #[access_control(ctx.accounts.validate())]
+    pub fn resize_locker(ctx: Context<ResizeLocker>) -> Result<()> {
+        ctx.accounts.resize_locker()
+    }
+
+    /// Creates the [LockerCheckpoints] of a [Locker], which track its total voting power over time.
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_locker_checkpoints(ctx: Context<NewLockerCheckpoints>) -> Result<()> {
+        ctx.accounts
+            .new_locker_checkpoints(unwrap_bump!(ctx, "checkpoints"))
+    }
+
+    /// Adds an [Escrow] created before the [LockerCheckpoints] of its [Locker] to them.
     #[access_control(ctx.accounts.validate())]
-    pub fn new_locker(ctx: Context<NewLocker>, _bump: u8, params: LockerParams) -> Result<()> {
-        ctx.accounts.new_locker(unwrap_bump!(ctx, "locker"), params)
+    pub fn checkpoint_escrow(ctx: Context<CheckpointEscrow>) -> Result<()> {
+        ctx.accounts.checkpoint_escrow()
     }
 
     /// Creates a new [Escrow] for an account.
@@ -45,6 +77,37 @@ pub mod locked_voter {
         ctx.accounts.new_escrow(unwrap_bump!(ctx, "escrow"))
     }
 
+    /// Creates a new position [Escrow] for an account.
+    ///
+    /// Positions allow an owner to hold several independent locks with different durations
+    /// within the same [Locker]. `position_id` must be greater than zero.
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_escrow_position(ctx: Context<NewEscrowPosition>, position_id: u64) -> Result<()> {
+        ctx.accounts
+            .new_escrow_position(unwrap_bump!(ctx, "escrow"), position_id)
+    }
+
+    /// Creates a new NFT [Escrow], i.e. a transferable lock position.
+    ///
+    /// A 1-of-1 position token is minted to the `escrow_owner`. Whoever holds the token
+    /// is the authority of the [Escrow].
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_nft_escrow(ctx: Context<NewNftEscrow>, _bump: u8) -> Result<()> {
+        ctx.accounts.new_nft_escrow(unwrap_bump!(ctx, "escrow"))
+    }
+
+    /// Resizes a [LockerWhitelistEntry] created before limits were added to it.
+    #[access_control(ctx.accounts.validate())]
+    pub fn resize_whitelist_entry(ctx: Context<ResizeWhitelistEntry>) -> Result<()> {
+        ctx.accounts.resize_whitelist_entry()
+    }
+
+    /// Resizes an [Escrow] created before new fields were added to it.
+    #[access_control(ctx.accounts.validate())]
+    pub fn resize_escrow(ctx: Context<ResizeEscrow>) -> Result<()> {
+        ctx.accounts.resize_escrow()
+    }
+
     /// Stakes `amount` tokens into the [Escrow].
     /// WARNING: if the program has a whitelist, one should use [crate::locked_voter::lock_with_whitelist] instead.
     /// This version of the instruction is deprecated.
@@ -55,10 +118,14 @@ pub mod locked_voter {
         duration: i64,
     ) -> Result<()> {
         msg!("Warning: this instruction is deprecated in favor of `lock_with_whitelist`, `lock_with_whitelist_entry`, or `lock_permissionless`. Please update your Tribeca SDK.");
-        if ctx.accounts.locker.params.whitelist_enabled {
-            ctx.accounts.check_whitelisted(ctx.remaining_accounts)?;
+        if !ctx.accounts.locker.params.whitelist_enabled {
+            return ctx.accounts.lock(amount, duration);
         }
-        ctx.accounts.lock(amount, duration)
+        let stake_duration_bounds = ctx
+            .accounts
+            .check_whitelisted(ctx.remaining_accounts, amount)?;
+        ctx.accounts
+            .lock_with_stake_duration_bounds(amount, duration, stake_duration_bounds)
     }
 
     /// Stakes `amount` tokens into the [Escrow], if there is a whitelist.
@@ -81,6 +148,19 @@ pub mod locked_voter {
         instructions::lock_with_whitelist_entry::handler(ctx, amount, duration)
     }
 
+    /// Adds `amount` tokens to an existing [Escrow] from any signer, without changing when it unlocks.
+    ///
+    /// If the whitelist is enabled, CPI callers must pass their [LockerWhitelistEntry] as a remaining account.
+    #[access_control(ctx.accounts.validate())]
+    pub fn deposit_for<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositFor<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .check_whitelisted(ctx.remaining_accounts, amount)?;
+        ctx.accounts.deposit_for(amount)
+    }
+
     /// Stakes `amount` tokens into the [Escrow] if there is no whitelist.
     #[access_control(ctx.accounts.validate())]
     pub fn lock_permissionless<'info>(
@@ -91,34 +171,453 @@ pub mod locked_voter {
         instructions::lock_permissionless::handler(ctx, amount, duration)
     }
 
+    /// Stakes `amount` tokens into an NFT [Escrow] as the holder of its position token.
+    #[access_control(ctx.accounts.validate())]
+    pub fn lock_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, LockNft<'info>>,
+        amount: u64,
+        duration: i64,
+    ) -> Result<()> {
+        instructions::lock_nft::handler(ctx, amount, duration)
+    }
+
+    /// Locks `amount` tokens into an empty [Escrow] for `duration` seconds, unlocking them
+    /// linearly from `cliff_duration` seconds after now until the end of the lock.
+    ///
+    /// Only the still locked tokens have voting power. With [VotingPowerCurve::Linear],
+    /// tokens stop counting at the start of the week they unlock in.
+    #[access_control(ctx.accounts.validate())]
+    pub fn lock_vesting(
+        ctx: Context<LockVesting>,
+        amount: u64,
+        cliff_duration: i64,
+        duration: i64,
+    ) -> Result<()> {
+        ctx.accounts.lock_vesting(amount, cliff_duration, duration)
+    }
+
+    /// Withdraws the tokens of a vesting [Escrow] which have unlocked so far.
+    #[access_control(ctx.accounts.validate())]
+    pub fn withdraw_vested(ctx: Context<WithdrawVested>) -> Result<()> {
+        ctx.accounts.withdraw_vested()
+    }
+
+    /// Withdraws the unlocked tokens of a vesting NFT [Escrow].
+    #[access_control(ctx.accounts.validate())]
+    pub fn withdraw_vested_nft(ctx: Context<WithdrawVestedNft>) -> Result<()> {
+        instructions::withdraw_vested_nft::handler(ctx)
+    }
+
     /// Exits the DAO; i.e., withdraws all staked tokens in an [Escrow] if the [Escrow] is unlocked.
     #[access_control(ctx.accounts.validate())]
-    pub fn exit(ctx: Context<Exit>) -> Result<()> {
-        ctx.accounts.exit()
+    pub fn exit<'info>(ctx: Context<'_, '_, '_, 'info, Exit<'info>>) -> Result<()> {
+        ctx.accounts.exit(ctx.remaining_accounts)
+    }
+
+    /// Exits an NFT [Escrow] as the holder of its position token, burning the position token.
+    #[access_control(ctx.accounts.validate())]
+    pub fn exit_nft<'info>(ctx: Context<'_, '_, '_, 'info, ExitNft<'info>>) -> Result<()> {
+        instructions::exit_nft::handler(ctx)
+    }
+
+    /// Withdraws `amount` staked tokens from an unlocked [Escrow] while keeping the [Escrow] open.
+    ///
+    /// The remaining tokens may be re-locked via the lock instructions without creating a new [Escrow].
+    #[access_control(ctx.accounts.validate())]
+    pub fn withdraw_partial<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawPartial<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .withdraw_partial(ctx.remaining_accounts, amount)
+    }
+
+    /// Withdraws `amount` staked tokens from an unlocked NFT [Escrow] while keeping it open.
+    #[access_control(ctx.accounts.validate())]
+    pub fn withdraw_partial_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawPartialNft<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_partial_nft::handler(ctx, amount)
+    }
+
+    /// Merges the source [Escrow] into the destination [Escrow] of the same owner.
+    ///
+    /// The destination keeps the later of the two end times and the source is closed.
+    #[access_control(ctx.accounts.validate())]
+    pub fn merge_escrows(ctx: Context<MergeEscrows>) -> Result<()> {
+        ctx.accounts.merge_escrows()
+    }
+
+    /// Merges two [Escrow]s held by the same account, either of which may be an NFT [Escrow].
+    #[access_control(ctx.accounts.validate())]
+    pub fn merge_escrows_nft(ctx: Context<MergeEscrowsNft>) -> Result<()> {
+        instructions::merge_escrows_nft::handler(ctx)
+    }
+
+    /// Splits `amount` tokens of an [Escrow] into a new position [Escrow] with the same lock times.
+    ///
+    /// The new [Escrow] may belong to a different owner.
+    #[access_control(ctx.accounts.validate())]
+    pub fn split_escrow(ctx: Context<SplitEscrow>, position_id: u64, amount: u64) -> Result<()> {
+        ctx.accounts
+            .split_escrow(unwrap_bump!(ctx, "destination_escrow"), position_id, amount)
+    }
+
+    /// Splits `amount` tokens of an NFT [Escrow] into a new position [Escrow].
+    #[access_control(ctx.accounts.validate())]
+    pub fn split_escrow_nft(
+        ctx: Context<SplitEscrowNft>,
+        position_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::split_escrow_nft::handler(ctx, position_id, amount)
+    }
+
+    /// Exits an [Escrow] before it unlocks, paying a penalty scaled by the remaining lock time.
+    ///
+    /// The penalty goes to [LockerParams::early_exit_penalty_recipient], or to the penalty pool
+    /// claimable by the remaining lockers.
+    #[access_control(ctx.accounts.validate())]
+    pub fn exit_early<'info>(ctx: Context<'_, '_, '_, 'info, ExitEarly<'info>>) -> Result<()> {
+        instructions::exit_early::handler(ctx)
+    }
+
+    /// Exits an NFT [Escrow] before it unlocks and burns its position token.
+    #[access_control(ctx.accounts.validate())]
+    pub fn exit_early_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExitEarlyNft<'info>>,
+    ) -> Result<()> {
+        instructions::exit_early_nft::handler(ctx)
+    }
+
+    /// Adds the share of an [Escrow] in the penalty pool to its lock.
+    #[access_control(ctx.accounts.validate())]
+    pub fn claim_penalty_rewards(ctx: Context<ClaimPenaltyRewards>) -> Result<()> {
+        ctx.accounts.claim_penalty_rewards()
+    }
+
+    /// Creates a new [RewardDistributor] for a reward token.
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_reward_distributor(ctx: Context<NewRewardDistributor>, _bump: u8) -> Result<()> {
+        ctx.accounts
+            .new_reward_distributor(unwrap_bump!(ctx, "distributor"))
+    }
+
+    /// Creates a new [RewardEpoch] for the week with index `epoch`, i.e. starting at `epoch * 1 week`.
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_reward_epoch(ctx: Context<NewRewardEpoch>, epoch: u64) -> Result<()> {
+        ctx.accounts
+            .new_reward_epoch(unwrap_bump!(ctx, "reward_epoch"), epoch)
+    }
+
+    /// Deposits rewards for a week. Anyone may deposit until the week ends.
+    #[access_control(ctx.accounts.validate())]
+    pub fn deposit_rewards(ctx: Context<DepositRewards>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit_rewards(amount)
+    }
+
+    /// Records the total voting power of a [RewardEpoch] once its week has started.
+    /// Anyone may call this; it should be done before the [LockerCheckpoints] history moves on.
+    #[access_control(ctx.accounts.validate())]
+    pub fn snapshot_reward_epoch(ctx: Context<SnapshotRewardEpoch>) -> Result<()> {
+        ctx.accounts.snapshot_reward_epoch()
+    }
+
+    /// Moves the rewards of an ended [RewardEpoch] which nobody can claim to a later [RewardEpoch]
+    /// which has not ended yet. Anyone may call this.
+    ///
+    /// This applies if the total voting power of the week was zero or is no longer kept
+    /// by the [LockerCheckpoints].
+    #[access_control(ctx.accounts.validate())]
+    pub fn roll_over_reward_epoch(ctx: Context<RollOverRewardEpoch>) -> Result<()> {
+        ctx.accounts.roll_over_reward_epoch()
+    }
+
+    /// Claims the rewards of an [Escrow] for a week that has ended.
+    ///
+    /// Each [Escrow] may claim once per [RewardEpoch].
+    #[access_control(ctx.accounts.validate())]
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        ctx.accounts.claim_rewards()
+    }
+
+    /// Claims the rewards of an NFT [Escrow] for a week that has ended.
+    #[access_control(ctx.accounts.validate())]
+    pub fn claim_nft_rewards(ctx: Context<ClaimNftRewards>) -> Result<()> {
+        instructions::claim_nft_rewards::handler(ctx)
+    }
+
+    /// Creates the [GaugeRegistry] of a [Locker].
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_gauge_registry(ctx: Context<NewGaugeRegistry>) -> Result<()> {
+        ctx.accounts
+            .new_gauge_registry(unwrap_bump!(ctx, "registry"))
+    }
+
+    /// Creates a new [Gauge]. Only the smart wallet of the [Governor] may add gauges.
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_gauge(ctx: Context<NewGauge>) -> Result<()> {
+        ctx.accounts.new_gauge(unwrap_bump!(ctx, "gauge"))
+    }
+
+    /// Enables or disables a [Gauge].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_gauge_disabled(ctx: Context<SetGaugeDisabled>, is_disabled: bool) -> Result<()> {
+        ctx.accounts.set_gauge_disabled(is_disabled)
+    }
+
+    /// Creates a new [GaugeVoter] for an [Escrow].
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_gauge_voter(ctx: Context<NewGaugeVoter>) -> Result<()> {
+        ctx.accounts
+            .new_gauge_voter(unwrap_bump!(ctx, "gauge_voter"))
+    }
+
+    /// Creates a new [GaugeVote] of a [GaugeVoter] for a [Gauge].
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_gauge_vote(ctx: Context<NewGaugeVote>) -> Result<()> {
+        ctx.accounts.new_gauge_vote(unwrap_bump!(ctx, "gauge_vote"))
+    }
+
+    /// Sets the weight an [Escrow] allocates to a [Gauge].
+    ///
+    /// May be called by the [Escrow::vote_delegate] on behalf of the owner.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_gauge_vote_weight(ctx: Context<SetGaugeVoteWeight>, weight: u32) -> Result<()> {
+        ctx.accounts.set_gauge_vote_weight(weight)
+    }
+
+    /// Sets the weight an NFT [Escrow] allocates to a [Gauge].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_gauge_vote_weight_nft(
+        ctx: Context<SetGaugeVoteWeightNft>,
+        weight: u32,
+    ) -> Result<()> {
+        instructions::set_gauge_vote_weight_nft::handler(ctx, weight)
+    }
+
+    /// Creates a new [EpochGauge] for the week with index `epoch`.
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_epoch_gauge(ctx: Context<NewEpochGauge>, epoch: u64) -> Result<()> {
+        ctx.accounts
+            .new_epoch_gauge(unwrap_bump!(ctx, "epoch_gauge"), epoch)
+    }
+
+    /// Records the voting power of an [Escrow] for gauge voting in the current week.
+    #[access_control(ctx.accounts.validate())]
+    pub fn prepare_epoch_gauge_voter(
+        ctx: Context<PrepareEpochGaugeVoter>,
+        epoch: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .prepare_epoch_gauge_voter(unwrap_bump!(ctx, "epoch_gauge_voter"), epoch)
+    }
+
+    /// Commits the voting power allocated by a [GaugeVote] to its [EpochGauge].
+    ///
+    /// Each [GaugeVote] may be committed once per week.
+    #[access_control(ctx.accounts.validate())]
+    pub fn commit_gauge_vote(ctx: Context<CommitGaugeVote>) -> Result<()> {
+        ctx.accounts.commit_gauge_vote()
+    }
+
+    /// Puts an [Escrow] into max-lock mode.
+    ///
+    /// While enabled, the [Escrow] votes as if it were locked for the maximum duration and cannot be exited.
+    #[access_control(ctx.accounts.validate())]
+    pub fn enable_max_lock(ctx: Context<EnableMaxLock>) -> Result<()> {
+        ctx.accounts.enable_max_lock()
+    }
+
+    /// Puts an NFT [Escrow] into max-lock mode.
+    #[access_control(ctx.accounts.validate())]
+    pub fn enable_max_lock_nft(ctx: Context<EnableMaxLockNft>) -> Result<()> {
+        instructions::enable_max_lock_nft::handler(ctx)
+    }
+
+    /// Takes an [Escrow] out of max-lock mode.
+    ///
+    /// The [Escrow] unlocks after the maximum stake duration, decaying as a normal lock.
+    #[access_control(ctx.accounts.validate())]
+    pub fn disable_max_lock(ctx: Context<DisableMaxLock>) -> Result<()> {
+        ctx.accounts.disable_max_lock()
+    }
+
+    /// Takes an NFT [Escrow] out of max-lock mode.
+    #[access_control(ctx.accounts.validate())]
+    pub fn disable_max_lock_nft(ctx: Context<DisableMaxLockNft>) -> Result<()> {
+        instructions::disable_max_lock_nft::handler(ctx)
     }
 
     /// Activates a proposal.
+    ///
+    /// Additional [Escrow] positions of the owner may be passed as remaining accounts.
     #[access_control(ctx.accounts.validate())]
-    pub fn activate_proposal(ctx: Context<ActivateProposal>) -> Result<()> {
-        ctx.accounts.activate_proposal()
+    pub fn activate_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ActivateProposal<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.activate_proposal(ctx.remaining_accounts)
+    }
+
+    /// Activates a proposal with the voting power of an NFT [Escrow].
+    #[access_control(ctx.accounts.validate())]
+    pub fn activate_proposal_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, ActivateProposalNft<'info>>,
+    ) -> Result<()> {
+        instructions::activate_proposal_nft::handler(ctx)
     }
 
     /// Casts a vote.
+    ///
+    /// Additional [Escrow] positions of the owner may be passed as remaining accounts;
+    /// the vote weight is the sum of the voting power of all provided positions.
+    /// A delegate in the [VoteDelegation] of the [Escrow] votes only its share of the [Escrow].
+    #[access_control(ctx.accounts.validate())]
+    pub fn cast_vote<'info>(
+        ctx: Context<'_, '_, '_, 'info, CastVote<'info>>,
+        side: u8,
+    ) -> Result<()> {
+        ctx.accounts.cast_vote(side, ctx.remaining_accounts, None)
+    }
+
+    /// Casts a vote with an NFT [Escrow].
+    ///
+    /// The holder of the position token votes, unless it has set a delegate.
+    #[access_control(ctx.accounts.validate())]
+    pub fn cast_vote_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, CastVoteNft<'info>>,
+        side: u8,
+    ) -> Result<()> {
+        instructions::cast_vote_nft::handler(ctx, side)
+    }
+
+    /// Casts votes for many [Escrow]s of one vote delegate.
+    ///
+    /// Pairs of [Escrow] and [Vote] accounts are passed as remaining accounts.
+    /// Votes are not aggregated into a single record of the delegate: the [Vote] of each owner
+    /// is what keeps the owner from voting the same power again, so one `set_vote` is made per [Escrow].
     #[access_control(ctx.accounts.validate())]
-    pub fn cast_vote(ctx: Context<CastVote>, side: u8) -> Result<()> {
-        ctx.accounts.cast_vote(side)
+    pub fn cast_delegate_votes<'info>(
+        ctx: Context<'_, '_, '_, 'info, CastDelegateVotes<'info>>,
+        side: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .cast_delegate_votes(side, ctx.remaining_accounts)
     }
 
     /// Delegate escrow vote.
     #[access_control(ctx.accounts.validate())]
     pub fn set_vote_delegate(ctx: Context<SetVoteDelegate>, new_delegate: Pubkey) -> Result<()> {
-        ctx.accounts.set_vote_delegate(new_delegate)
+        ctx.accounts
+            .set_vote_delegate(new_delegate, DelegationTerms::default())
+    }
+
+    /// Delegate NFT escrow vote as the holder of its position token.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_nft_vote_delegate(
+        ctx: Context<SetNftVoteDelegate>,
+        new_delegate: Pubkey,
+    ) -> Result<()> {
+        instructions::set_nft_vote_delegate::handler(ctx, new_delegate, DelegationTerms::default())
+    }
+
+    /// Delegate escrow vote until an expiry, optionally only for one [Governor].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_scoped_vote_delegate(
+        ctx: Context<SetVoteDelegate>,
+        new_delegate: Pubkey,
+        terms: DelegationTerms,
+    ) -> Result<()> {
+        ctx.accounts.set_vote_delegate(new_delegate, terms)
+    }
+
+    /// Delegate NFT escrow vote until an expiry, optionally only for one [Governor],
+    /// as the holder of its position token.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_nft_scoped_vote_delegate(
+        ctx: Context<SetNftVoteDelegate>,
+        new_delegate: Pubkey,
+        terms: DelegationTerms,
+    ) -> Result<()> {
+        instructions::set_nft_vote_delegate::handler(ctx, new_delegate, terms)
+    }
+
+    /// Creates an empty [VoteDelegation] for an [Escrow].
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_vote_delegation(ctx: Context<NewVoteDelegation>) -> Result<()> {
+        ctx.accounts
+            .new_vote_delegation(unwrap_bump!(ctx, "vote_delegation"))
+    }
+
+    /// Splits the voting power of an [Escrow] across several delegates, in basis points.
+    /// The remaining share is voted by the [Escrow::vote_delegate].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_vote_delegation(
+        ctx: Context<SetVoteDelegation>,
+        shares: Vec<DelegateShare>,
+        terms: DelegationTerms,
+    ) -> Result<()> {
+        ctx.accounts.set_vote_delegation(shares, terms)
     }
 
-    /// Set locker params.
+    /// Splits the voting power of an NFT [Escrow] as the holder of its position token.
     #[access_control(ctx.accounts.validate())]
-    pub fn set_locker_params(ctx: Context<SetLockerParams>, params: LockerParams) -> Result<()> {
-        ctx.accounts.set_locker_params(params)
+    pub fn set_nft_vote_delegation(
+        ctx: Context<SetNftVoteDelegation>,
+        shares: Vec<DelegateShare>,
+        terms: DelegationTerms,
+    ) -> Result<()> {
+        instructions::set_nft_vote_delegation::handler(ctx, shares, terms)
+    }
+
+    /// Set locker params. Only possible while nothing is locked in the [Locker];
+    /// otherwise they must be staged with [locked_voter::stage_locker_params].
+    ///
+    /// If the params set an early exit penalty recipient, its token account must be passed
+    /// as a remaining account.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_locker_params<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetLockerParams<'info>>,
+        params: LockerParams,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_locker_params(params, ctx.remaining_accounts)
+    }
+
+    /// Stages [LockerParams] that anyone may apply once `activates_at` has passed.
+    ///
+    /// If the params set an early exit penalty recipient, its token account must be passed
+    /// as a remaining account.
+    #[access_control(ctx.accounts.validate())]
+    pub fn stage_locker_params<'info>(
+        ctx: Context<'_, '_, '_, 'info, StageLockerParams<'info>>,
+        params: LockerParams,
+        activates_at: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .stage_locker_params(params, activates_at, ctx.remaining_accounts)
+    }
+
+    /// Sets the bounds on the [LockerParams] of a [Locker], within [LockerParamsBounds::DEFAULT].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_locker_params_bounds(
+        ctx: Context<SetLockerParamsBounds>,
+        bounds: LockerParamsBounds,
+    ) -> Result<()> {
+        ctx.accounts.set_locker_params_bounds(bounds)
+    }
+
+    /// Applies the staged [LockerParams] of a [Locker].
+    #[access_control(ctx.accounts.validate())]
+    pub fn apply_locker_params(ctx: Context<ApplyLockerParams>) -> Result<()> {
+        ctx.accounts.apply_locker_params()
+    }
+
+    /// Discards the staged [LockerParams] of a [Locker].
+    #[access_control(ctx.accounts.validate())]
+    pub fn cancel_locker_params(ctx: Context<CancelLockerParams>) -> Result<()> {
+        ctx.accounts.cancel_locker_params()
     }
 
     /// Creates a new [LockerWhitelistEntry] to whitelist program from CPI.
@@ -131,6 +630,21 @@ pub mod locked_voter {
             .approve_program_lock_privilege(unwrap_bump!(ctx, "whitelist_entry"))
     }
 
+    /// Sets the [WhitelistEntryLimits] of a [LockerWhitelistEntry].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_whitelist_entry_limits(
+        ctx: Context<SetWhitelistEntryLimits>,
+        limits: WhitelistEntryLimits,
+    ) -> Result<()> {
+        ctx.accounts.set_whitelist_entry_limits(limits)
+    }
+
+    /// Releases the tokens that have left an [Escrow] from its [Escrow::whitelist_entry].
+    #[access_control(ctx.accounts.validate())]
+    pub fn release_whitelist_lock(ctx: Context<ReleaseWhitelistLock>) -> Result<()> {
+        ctx.accounts.release_whitelist_lock()
+    }
+
     /// Close a [LockerWhitelistEntry] revoking program's CPI privilege.
     #[access_control(ctx.accounts.validate())]
     pub fn revoke_program_lock_privilege(ctx: Context<RevokeProgramLockPrivilege>) -> Result<()> {
@@ -139,12 +653,46 @@ pub mod locked_voter {
 
     // REDEEMER
 
-    /// Instantly withdraws all tokens from an [Escrow] before the lock period ends.
+    /// Instantly withdraws the tokens of an [Escrow] eligible for the [LockerRedeemer] before the lock period ends.
+    ///
+    /// The [Escrow] is closed once all of its tokens are redeemed.
+    /// Otherwise it is kept open, and the tokens that are not eligible stay locked.
     #[access_control(ctx.accounts.validate())]
     pub fn instant_withdraw(ctx: Context<InstantWithdraw>) -> Result<()> {
         ctx.accounts.instant_withdraw()
     }
 
+    /// Instantly withdraws the eligible tokens of an NFT [Escrow] as the holder of its position token.
+    ///
+    /// The position token is burned if the [Escrow] is closed.
+    #[access_control(ctx.accounts.validate())]
+    pub fn instant_withdraw_nft(ctx: Context<InstantWithdrawNft>) -> Result<()> {
+        ctx.accounts.instant_withdraw_nft()
+    }
+
+    /// Instantly withdraws `amount` eligible tokens from an [Escrow] before the lock period ends,
+    /// in exchange for their share of the voting power of the [Escrow].
+    ///
+    /// The [Escrow] is not blacklisted and may be redeemed from again.
+    #[access_control(ctx.accounts.validate())]
+    pub fn partial_instant_withdraw(
+        ctx: Context<PartialInstantWithdraw>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.partial_instant_withdraw(amount)
+    }
+
+    /// Instantly withdraws `amount` eligible tokens from an NFT [Escrow] as the holder of its position token.
+    #[access_control(ctx.accounts.validate())]
+    pub fn partial_instant_withdraw_nft(
+        ctx: Context<PartialInstantWithdrawNft>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .partial_instant_withdraw
+            .partial_instant_withdraw(amount)
+    }
+
     /// Creates a new [LockerRedeemer].
     pub fn create_redeemer(
         ctx: Context<CreateRedeemer>,
@@ -200,6 +748,40 @@ pub mod locked_voter {
     pub fn update_redemption_rate(ctx: Context<UpdateRedemptionRate>, new_rate: u64) -> Result<()> {
         ctx.accounts.update_redemption_rate(new_rate)
     }
+
+    /// Updates which tokens of an [Escrow] a [LockerRedeemer] considers locked before its cutoff date.
+    pub fn update_redemption_eligibility(
+        ctx: Context<UpdateRedemptionEligibility>,
+        eligibility: RedemptionEligibility,
+    ) -> Result<()> {
+        ctx.accounts.update_redemption_eligibility(eligibility)
+    }
+
+    /// Sets the redemption rate schedule of a [LockerRedeemer].
+    ///
+    /// A [RedemptionRateCurve::Fixed] schedule returns to the [LockerRedeemer::redemption_rate].
+    pub fn set_redemption_rate_schedule(
+        ctx: Context<SetRedemptionRateSchedule>,
+        rate_schedule: RedemptionRateSchedule,
+    ) -> Result<()> {
+        ctx.accounts.set_redemption_rate_schedule(rate_schedule)
+    }
+
+    /// Sets the price of one whole unit of voting power to `numerator / denominator` whole receipt tokens,
+    /// accounting for the decimals of both mints. Zero for both removes the price.
+    pub fn set_redemption_price(
+        ctx: Context<SetRedemptionPrice>,
+        numerator: u64,
+        denominator: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_redemption_price(numerator, denominator)
+    }
+
+    /// Resizes a [LockerRedeemer] created before the redemption rate schedule and price were added to it.
+    #[access_control(ctx.accounts.validate())]
+    pub fn resize_redeemer(ctx: Context<ResizeRedeemer>) -> Result<()> {
+        ctx.accounts.resize_redeemer()
+    }
 }
 
 /// [locked_voter] errors.
@@ -223,4 +805,100 @@ pub enum ErrorCode {
     MustCallLockWithWhitelistEntry,
     #[msg("Must call `lock_permissionless` since this DAO does not have a CPI whitelist.")]
     MustCallLockPermissionless,
+    #[msg("Withdrawal amount must be greater than zero.")]
+    WithdrawAmountZero,
+    #[msg("Withdrawal amount exceeds the amount of tokens in the escrow.")]
+    WithdrawAmountTooLarge,
+    #[msg("Position ID must be greater than zero.")]
+    InvalidPositionId,
+    #[msg("The same escrow position was provided more than once.")]
+    DuplicatePosition,
+    #[msg("Escrow position does not belong to the escrow owner.")]
+    PositionOwnerMismatch,
+    #[msg("Must provide the token account holding the position token of this NFT escrow.")]
+    MustProvidePositionTokens,
+    #[msg("Signer does not hold the position token of this NFT escrow.")]
+    NotPositionHolder,
+    #[msg("Escrow is in max-lock mode.")]
+    MaxLockEnabled,
+    #[msg("Escrow is not in max-lock mode.")]
+    MaxLockNotEnabled,
+    #[msg("Too many distinct lock end times are pending in the locker checkpoints.")]
+    TooManySlopeChanges,
+    #[msg("Escrow is already included in the locker checkpoints.")]
+    EscrowAlreadyCheckpointed,
+    #[msg("Lock checkpoint at the requested time is no longer kept by the escrow.")]
+    LockCheckpointUnavailable,
+    #[msg("Early exits are disabled for this locker.")]
+    EarlyExitDisabled,
+    #[msg("Escrow has unclaimed penalty rewards. Claim them before exiting.")]
+    MustClaimPenaltyRewards,
+    #[msg("Reward epoch has ended.")]
+    RewardEpochEnded,
+    #[msg("Reward epoch has not ended yet.")]
+    RewardEpochNotEnded,
+    #[msg("Gauge epoch has not started yet.")]
+    GaugeEpochNotStarted,
+    #[msg("Gauge epoch has ended.")]
+    GaugeEpochEnded,
+    #[msg("Gauge is disabled.")]
+    GaugeDisabled,
+    #[msg("Too many delegates for a vote delegation.")]
+    TooManyDelegates,
+    #[msg("Delegate shares must be unique, non-zero and sum to at most 10,000 bps.")]
+    InvalidDelegateShare,
+    #[msg("Signer is not a delegate of this escrow.")]
+    NotDelegate,
+    #[msg("Delegation has expired or does not apply to this governor.")]
+    DelegationInactive,
+    #[msg("Max stake duration is zero or outside of the allowed bounds.")]
+    InvalidMaxStakeDuration,
+    #[msg("Min stake duration must be at least a week shorter than the max stake duration.")]
+    InvalidMinStakeDuration,
+    #[msg("Max stake vote multiplier is zero or outside of the allowed bounds.")]
+    InvalidMaxStakeVoteMultiplier,
+    #[msg("Baseline power must not exceed 10,000 bps.")]
+    InvalidBaselinePowerBps,
+    #[msg("Early exit penalty is outside of the allowed bounds.")]
+    InvalidEarlyExitPenaltyBps,
+    #[msg("Staged params must activate at least one voting period from now.")]
+    ParamsActivationTooEarly,
+    #[msg("Locker has no staged params.")]
+    NoPendingParams,
+    #[msg("Staged params cannot be applied yet.")]
+    PendingParamsNotActive,
+    #[msg("Whitelist entry has expired.")]
+    WhitelistEntryExpired,
+    #[msg("Lock exceeds the amount allowed by the whitelist entry.")]
+    WhitelistEntryAmountExceeded,
+    #[msg("Whitelist entry min stake duration exceeds its max stake duration.")]
+    InvalidWhitelistEntryLimits,
+    #[msg("Escrow has expired.")]
+    EscrowExpired,
+    #[msg("Vesting escrows only support withdraw_vested and exit.")]
+    VestingEscrow,
+    #[msg("Escrow is not vesting.")]
+    EscrowNotVesting,
+    #[msg("Vesting locks require an empty escrow.")]
+    VestingEscrowNotEmpty,
+    #[msg("Vesting cliff must be between the start and end of the lock.")]
+    InvalidVestingSchedule,
+    #[msg("Redemption rate schedule must have positive rates and steps, and end after it starts.")]
+    InvalidRedemptionRateSchedule,
+    #[msg("Max stake duration may not be decreased.")]
+    MaxStakeDurationDecreased,
+    #[msg("Reward epoch has not started yet.")]
+    RewardEpochNotStarted,
+    #[msg("Vote delegation changed after the proposal was activated.")]
+    DelegationChangedDuringVote,
+    #[msg("Locker params bounds are inconsistent or looser than the defaults.")]
+    InvalidLockerParamsBounds,
+    #[msg("Early exit penalty recipient must be a token account of the locker mint.")]
+    InvalidEarlyExitPenaltyRecipient,
+    #[msg("Locker has locked tokens, so its params must be staged.")]
+    LockerParamsMustBeStaged,
+    #[msg("Escrow already counts towards a different whitelist entry.")]
+    WhitelistEntryMismatch,
+    #[msg("Reward epoch has voting power to claim its rewards.")]
+    RewardEpochClaimable,
 }
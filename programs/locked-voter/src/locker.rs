@@ -4,6 +4,56 @@
 use crate::*;
 use num_traits::ToPrimitive;
 
+/// Number of seconds in a week, the step size of [VotingPowerCurve::SteppedWeekly].
+pub const SECONDS_PER_WEEK: u64 = 7 * 24 * 60 * 60;
+
+/// Number of times voting power halves over the max stake duration with [VotingPowerCurve::Exponential].
+pub const EXPONENTIAL_HALVINGS: u64 = 8;
+
+/// Basis points in one unit.
+pub const BPS_PER_UNIT: u64 = 10_000;
+
+/// Precision of [Locker::penalty_reward_per_token].
+pub const PENALTY_REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// When the week with index `epoch` since the Unix epoch starts.
+pub fn week_start_ts(epoch: u64) -> Option<i64> {
+    epoch.checked_mul(SECONDS_PER_WEEK)?.to_i64()
+}
+
+/// When the week with index `epoch` since the Unix epoch ends.
+pub fn week_end_ts(epoch: u64) -> Option<i64> {
+    week_start_ts(epoch.checked_add(1)?)
+}
+
+/// Rounds `timestamp` down to the start of its week.
+///
+/// Locks end on week boundaries, so the [LockerCheckpoints] have at most one
+/// [SlopeChange] per week of the [LockerParams::max_stake_duration].
+pub fn round_down_to_week(timestamp: i64) -> Option<i64> {
+    let week = SECONDS_PER_WEEK.to_i64()?;
+    timestamp.checked_div(week)?.checked_mul(week)
+}
+
+/// Gets when a lock of `duration` seconds from `now` ends, rounded down to the start of the week.
+///
+/// The rounded duration must be within the `(min, max)` stake durations.
+pub fn lock_ends_at(
+    now: i64,
+    duration: i64,
+    (min_stake_duration, max_stake_duration): (u64, u64),
+) -> Result<i64> {
+    let ends_at = unwrap_int!(round_down_to_week(unwrap_int!(now.checked_add(duration))));
+    invariant!(ends_at > now, LockupDurationTooShort);
+    let locked_duration = unwrap_int!(unwrap_int!(ends_at.checked_sub(now)).to_u64());
+    invariant!(
+        locked_duration >= min_stake_duration,
+        LockupDurationTooShort
+    );
+    invariant!(locked_duration <= max_stake_duration, LockupDurationTooLong);
+    Ok(ends_at)
+}
+
 impl LockerParams {
     /// Calculates the amount of voting power an [Escrow] has.
     pub fn calculate_voter_power(&self, escrow: &Escrow, now: i64) -> Option<u64> {
@@ -15,29 +65,231 @@ impl LockerParams {
             return Some(0);
         }
         // Lockup had zero power before the start time.
-        // at the end time, lockup also has zero power.
-        if now < escrow.escrow_started_at || now >= escrow.escrow_ends_at {
+        if now < escrow.escrow_started_at {
+            return Some(0);
+        }
+        // at the end time, lockup also has zero power, unless it is a max lock.
+        if now >= escrow.escrow_ends_at && !escrow.max_lock {
             return Some(0);
         }
 
-        let seconds_until_lockup_expiry = escrow.escrow_ends_at.checked_sub(now)?;
-        // elapsed seconds, clamped to the maximum duration
-        let relevant_seconds_until_lockup_expiry = seconds_until_lockup_expiry
-            .to_u64()?
-            .min(self.max_stake_duration);
+        // a max lock behaves as if it always had the max stake duration remaining.
+        let relevant_seconds_until_lockup_expiry = if escrow.max_lock {
+            self.max_stake_duration
+        } else {
+            let seconds_until_lockup_expiry = escrow.escrow_ends_at.checked_sub(now)?;
+            // elapsed seconds, clamped to the maximum duration
+            seconds_until_lockup_expiry
+                .to_u64()?
+                .min(self.max_stake_duration)
+        };
+
+        // only the still locked part of a vesting escrow has voting power.
+        let amount = escrow.locked_amount_at(now)?;
+
+        // vesting tokens count until the start of the week they unlock in,
+        // so that the LockerCheckpoints can follow them.
+        if escrow.is_vesting() && self.voting_power_curve == VotingPowerCurve::Linear {
+            let power = self.vesting_checkpoint(escrow, now)?.voting_power(self)?;
+            return Some(power.max(self.baseline_power(amount)?));
+        }
 
         // voting power at max lockup
-        let power_if_max_lockup = escrow
-            .amount
-            .checked_mul(self.max_stake_vote_multiplier.into())?;
+        let power_if_max_lockup = amount.checked_mul(self.max_stake_vote_multiplier.into())?;
+
+        let power = match self.voting_power_curve {
+            VotingPowerCurve::Linear => self
+                .fraction_of_max_power(power_if_max_lockup, relevant_seconds_until_lockup_expiry)?,
+            VotingPowerCurve::Flat => {
+                let lockup_duration = if escrow.max_lock {
+                    self.max_stake_duration
+                } else {
+                    escrow
+                        .escrow_ends_at
+                        .checked_sub(escrow.escrow_started_at)?
+                        .to_u64()?
+                        .min(self.max_stake_duration)
+                };
+                self.fraction_of_max_power(power_if_max_lockup, lockup_duration)?
+            }
+            VotingPowerCurve::Exponential => {
+                self.exponential_power(power_if_max_lockup, relevant_seconds_until_lockup_expiry)?
+            }
+            VotingPowerCurve::SteppedWeekly => {
+                let whole_weeks_until_lockup_expiry = relevant_seconds_until_lockup_expiry
+                    .checked_div(SECONDS_PER_WEEK)?
+                    .checked_mul(SECONDS_PER_WEEK)?;
+                self.fraction_of_max_power(power_if_max_lockup, whole_weeks_until_lockup_expiry)?
+            }
+        };
+
+        Some(power.max(self.baseline_power(amount)?))
+    }
+
+    /// Multiplies the max lockup power by the fraction of the max stake duration.
+    fn fraction_of_max_power(&self, power_if_max_lockup: u64, seconds: u64) -> Option<u64> {
+        (power_if_max_lockup as u128)
+            .checked_mul(seconds.into())?
+            .checked_div(self.max_stake_duration.into())?
+            .to_u64()
+    }
+
+    /// Halves the max lockup power for every [EXPONENTIAL_HALVINGS]th of the max stake duration
+    /// that has elapsed, interpolating linearly within each halving period.
+    fn exponential_power(&self, power_if_max_lockup: u64, seconds: u64) -> Option<u64> {
+        let elapsed = self.max_stake_duration.checked_sub(seconds)?;
+        let halving_period = self
+            .max_stake_duration
+            .checked_div(EXPONENTIAL_HALVINGS)?
+            .max(1);
+        let halvings = elapsed.checked_div(halving_period)?.min(u64::BITS.into());
+        let into_period = elapsed.checked_rem(halving_period)?;
+
+        let start = power_if_max_lockup
+            .checked_shr(halvings.to_u32()?)
+            .unwrap_or(0);
+        let decay = (start.checked_sub(start.checked_shr(1)?)? as u128)
+            .checked_mul(into_period.into())?
+            .checked_div(halving_period.into())?
+            .to_u64()?;
+        start.checked_sub(decay)
+    }
+
+    /// The minimum voting power of an active lock of `amount` tokens.
+    fn baseline_power(&self, amount: u64) -> Option<u64> {
+        (amount as u128)
+            .checked_mul(self.baseline_power_bps.into())?
+            .checked_div(BPS_PER_UNIT.into())?
+            .to_u64()
+    }
 
-        // multiply the max lockup power by the fraction of the max stake duration
-        let power = (power_if_max_lockup as u128)
-            .checked_mul(relevant_seconds_until_lockup_expiry.into())?
+    /// Calculates the penalty for exiting an [Escrow] at `now`, before it unlocks.
+    ///
+    /// The penalty is [LockerParams::early_exit_penalty_bps] of the locked amount at the maximum
+    /// stake duration remaining, scaled down linearly with the remaining lock time.
+    pub fn calculate_early_exit_penalty(&self, escrow: &Escrow, now: i64) -> Option<u64> {
+        if now >= escrow.escrow_ends_at {
+            return Some(0);
+        }
+        let seconds_until_lockup_expiry = escrow
+            .escrow_ends_at
+            .checked_sub(now)?
+            .to_u64()?
+            .min(self.max_stake_duration);
+        let penalty = (escrow.amount as u128)
+            .checked_mul(self.early_exit_penalty_bps.into())?
+            .checked_mul(seconds_until_lockup_expiry.into())?
+            .checked_div(BPS_PER_UNIT.into())?
             .checked_div(self.max_stake_duration.into())?
             .to_u64()?;
+        Some(penalty.min(escrow.amount))
+    }
+
+    /// Whether the [LockerCheckpoints] can track the total voting power under these params.
+    ///
+    /// The checkpoints follow the linear decay of locks, so other curves and a baseline power
+    /// cannot be represented.
+    pub fn tracks_total_voting_power(&self) -> bool {
+        self.voting_power_curve == VotingPowerCurve::Linear && self.baseline_power_bps == 0
+    }
 
-        Some(power)
+    /// Calculates the contribution of an [Escrow] to the [LockerCheckpoints] at `now`.
+    ///
+    /// Escrows which are not yet [Escrow::checkpointed] contribute nothing.
+    ///
+    /// The contribution does not depend on the params, so it stays valid when they change,
+    /// as long as no lock outlasts the [LockerParams::max_stake_duration].
+    pub fn escrow_checkpoint(&self, escrow: &Escrow, now: i64) -> Option<EscrowCheckpoint> {
+        if !escrow.checkpointed || escrow.escrow_started_at == 0 || now < escrow.escrow_started_at {
+            return Some(EscrowCheckpoint::default());
+        }
+        if escrow.max_lock {
+            return Some(EscrowCheckpoint {
+                max_locked_amount: escrow.amount,
+                ..EscrowCheckpoint::default()
+            });
+        }
+        if now >= escrow.escrow_ends_at {
+            return Some(EscrowCheckpoint::default());
+        }
+        if escrow.is_vesting() {
+            return self.vesting_checkpoint(escrow, now);
+        }
+        let seconds_until_lockup_expiry = escrow.escrow_ends_at.checked_sub(now)?.to_u64()?;
+        let amount = u128::from(escrow.amount);
+        Some(EscrowCheckpoint {
+            bias: amount.checked_mul(seconds_until_lockup_expiry.into())?,
+            slope: amount,
+            slope_changes: vec![SlopeChange {
+                time: escrow.escrow_ends_at,
+                slope_delta: amount,
+            }],
+            max_locked_amount: 0,
+        })
+    }
+
+    /// Calculates the contribution of a vesting [Escrow] at `now`, before it ends.
+    ///
+    /// The tokens unlocking during a week are counted as unlocked from the start of that week,
+    /// which keeps to one [SlopeChange] per week and never counts more than
+    /// [Escrow::locked_amount_at].
+    fn vesting_checkpoint(&self, escrow: &Escrow, now: i64) -> Option<EscrowCheckpoint> {
+        let week = SECONDS_PER_WEEK.to_i64()?;
+        let mut checkpoint = EscrowCheckpoint::default();
+        let mut segment_start = now;
+        let mut segment_end = round_down_to_week(now)?
+            .checked_add(week)?
+            .min(escrow.escrow_ends_at);
+        let mut locked_amount = escrow.locked_amount_at(segment_end)?;
+        checkpoint.slope = locked_amount.into();
+        while segment_start < escrow.escrow_ends_at {
+            let seconds = segment_end.checked_sub(segment_start)?.to_u128()?;
+            checkpoint.bias = checkpoint
+                .bias
+                .checked_add(u128::from(locked_amount).checked_mul(seconds)?)?;
+            if segment_end >= escrow.escrow_ends_at {
+                break;
+            }
+            let next_segment_end = segment_end.checked_add(week)?.min(escrow.escrow_ends_at);
+            let next_locked_amount = escrow.locked_amount_at(next_segment_end)?;
+            if next_locked_amount < locked_amount {
+                checkpoint.slope_changes.push(SlopeChange {
+                    time: segment_end,
+                    slope_delta: locked_amount.checked_sub(next_locked_amount)?.into(),
+                });
+            }
+            segment_start = segment_end;
+            segment_end = next_segment_end;
+            locked_amount = next_locked_amount;
+        }
+        Some(checkpoint)
+    }
+}
+
+impl Locker {
+    /// Whether early exit penalties go to the pool claimable by lockers.
+    pub fn penalties_go_to_pool(&self) -> bool {
+        self.params.early_exit_penalty_recipient == Pubkey::default()
+    }
+
+    /// Distributes `penalty` tokens to the pool, pro rata to the [Locker::locked_supply].
+    ///
+    /// If nothing is locked, the penalty is kept in [Locker::undistributed_penalty_rewards]
+    /// and distributed along with the next penalty or lock.
+    pub fn distribute_penalty_rewards(&mut self, penalty: u64) -> Option<()> {
+        let rewards = self.undistributed_penalty_rewards.checked_add(penalty)?;
+        if self.locked_supply == 0 {
+            self.undistributed_penalty_rewards = rewards;
+            return Some(());
+        }
+        let reward_per_token = (rewards as u128)
+            .checked_mul(PENALTY_REWARD_PRECISION)?
+            .checked_div(self.locked_supply.into())?;
+        self.penalty_reward_per_token = self
+            .penalty_reward_per_token
+            .checked_add(reward_per_token)?;
+        self.undistributed_penalty_rewards = 0;
+        Some(())
     }
 }
 
@@ -62,6 +314,15 @@ mod tests {
        }
     }
 
+    fn voting_power_curve() -> impl Strategy<Value = VotingPowerCurve> {
+        prop_oneof![
+            Just(VotingPowerCurve::Linear),
+            Just(VotingPowerCurve::Flat),
+            Just(VotingPowerCurve::Exponential),
+            Just(VotingPowerCurve::SteppedWeekly),
+        ]
+    }
+
     #[test]
     fn test_max_lockup() {
         let locker_params = &LockerParams {
@@ -144,4 +405,366 @@ mod tests {
             assert_eq!(power, 0);
         }
     }
+
+    #[test]
+    fn test_lock_ends_at() {
+        let week = SECONDS_PER_WEEK as i64;
+        let now = 10 * week + 100;
+        let bounds = (2 * SECONDS_PER_WEEK, 4 * SECONDS_PER_WEEK);
+
+        // the duration is checked once rounded down to the week.
+        assert_eq!(lock_ends_at(now, 3 * week, bounds).unwrap(), 13 * week);
+        assert!(lock_ends_at(now, 2 * week + 100, bounds).is_err());
+        assert_eq!(
+            lock_ends_at(now, 3 * week - 100, bounds).unwrap(),
+            13 * week
+        );
+        assert_eq!(lock_ends_at(now, 4 * week + 99, bounds).unwrap(), 14 * week);
+        assert!(lock_ends_at(now, 5 * week, bounds).is_err());
+
+        // locks ending before the next week are rejected.
+        assert!(lock_ends_at(now, week - 101, (0, 4 * SECONDS_PER_WEEK)).is_err());
+        assert!(lock_ends_at(now, -1, (0, 4 * SECONDS_PER_WEEK)).is_err());
+    }
+
+    #[test]
+    fn test_flat_curve() {
+        let locker_params = &LockerParams {
+            max_stake_duration: 4 * ONE_YEAR,
+            max_stake_vote_multiplier: 10,
+            voting_power_curve: VotingPowerCurve::Flat,
+            ..LockerParams::default()
+        };
+        let escrow = Escrow {
+            escrow_started_at: 100,
+            escrow_ends_at: 100 + 2 * ONE_YEAR as i64,
+            amount: 100_000,
+            ..Escrow::default()
+        };
+        let half_power = escrow.amount * locker_params.max_stake_vote_multiplier as u64 / 2;
+        for now in [100, 100 + ONE_YEAR as i64, 100 + 2 * ONE_YEAR as i64 - 1] {
+            assert_eq!(
+                locker_params.calculate_voter_power(&escrow, now).unwrap(),
+                half_power
+            );
+        }
+        assert_eq!(
+            locker_params
+                .calculate_voter_power(&escrow, 100 + 2 * ONE_YEAR as i64)
+                .unwrap(),
+            0,
+            "expired lockup"
+        );
+    }
+
+    #[test]
+    fn test_exponential_curve() {
+        let locker_params = &LockerParams {
+            max_stake_duration: 8 * ONE_YEAR,
+            max_stake_vote_multiplier: 1,
+            voting_power_curve: VotingPowerCurve::Exponential,
+            ..LockerParams::default()
+        };
+        let escrow = Escrow {
+            escrow_started_at: 100,
+            escrow_ends_at: 100 + 8 * ONE_YEAR as i64,
+            amount: 1 << 20,
+            ..Escrow::default()
+        };
+        for years in 0..8 {
+            assert_eq!(
+                locker_params
+                    .calculate_voter_power(&escrow, 100 + (years * ONE_YEAR) as i64)
+                    .unwrap(),
+                escrow.amount >> years,
+                "halves every year"
+            );
+        }
+        assert_eq!(
+            locker_params
+                .calculate_voter_power(&escrow, 100 + (ONE_YEAR / 2) as i64)
+                .unwrap(),
+            escrow.amount * 3 / 4,
+            "interpolates within a halving period"
+        );
+    }
+
+    #[test]
+    fn test_stepped_weekly_curve() {
+        let locker_params = &LockerParams {
+            max_stake_duration: 4 * ONE_YEAR,
+            max_stake_vote_multiplier: 1,
+            voting_power_curve: VotingPowerCurve::SteppedWeekly,
+            ..LockerParams::default()
+        };
+        let escrow = Escrow {
+            escrow_started_at: 100,
+            escrow_ends_at: 100 + 10 * SECONDS_PER_WEEK as i64,
+            amount: 4 * ONE_YEAR,
+            ..Escrow::default()
+        };
+        let power_at = |now: i64| locker_params.calculate_voter_power(&escrow, now).unwrap();
+        assert_eq!(power_at(100), 10 * SECONDS_PER_WEEK);
+        assert_eq!(power_at(101), 9 * SECONDS_PER_WEEK, "steps down");
+        assert_eq!(
+            power_at(100 + SECONDS_PER_WEEK as i64),
+            9 * SECONDS_PER_WEEK,
+            "constant within a week"
+        );
+        assert_eq!(
+            power_at(100 + 9 * SECONDS_PER_WEEK as i64 + 1),
+            0,
+            "no power in the last week"
+        );
+    }
+
+    #[test]
+    fn test_baseline_power() {
+        let locker_params = &LockerParams {
+            max_stake_duration: 4 * ONE_YEAR,
+            max_stake_vote_multiplier: 10,
+            baseline_power_bps: 5_000,
+            ..LockerParams::default()
+        };
+        let escrow = Escrow {
+            escrow_started_at: 100,
+            escrow_ends_at: 100 + ONE_DAY as i64,
+            amount: 100_000,
+            ..Escrow::default()
+        };
+        assert_eq!(
+            locker_params.calculate_voter_power(&escrow, 100).unwrap(),
+            50_000,
+            "baseline applies to short locks"
+        );
+        assert_eq!(
+            locker_params
+                .calculate_voter_power(&escrow, 100 + ONE_DAY as i64)
+                .unwrap(),
+            0,
+            "baseline does not apply to expired locks"
+        );
+    }
+
+    #[test]
+    fn test_early_exit_penalty() {
+        let locker_params = &LockerParams {
+            max_stake_duration: 4 * ONE_YEAR,
+            max_stake_vote_multiplier: 10,
+            early_exit_penalty_bps: 5_000,
+            ..LockerParams::default()
+        };
+        let escrow = Escrow {
+            escrow_started_at: 100,
+            escrow_ends_at: 100 + 4 * ONE_YEAR as i64,
+            amount: 1_000_000,
+            ..Escrow::default()
+        };
+        let penalty_at = |now: i64| {
+            locker_params
+                .calculate_early_exit_penalty(&escrow, now)
+                .unwrap()
+        };
+        assert_eq!(penalty_at(100), 500_000, "full penalty at max lockup");
+        assert_eq!(penalty_at(100 + 2 * ONE_YEAR as i64), 250_000);
+        assert_eq!(
+            penalty_at(100 + 4 * ONE_YEAR as i64),
+            0,
+            "no penalty once unlocked"
+        );
+    }
+
+    #[test]
+    fn test_penalty_rewards_distribution() {
+        let locker = &mut Locker::default();
+        let alice = &mut Escrow::default();
+        let bob = &mut Escrow::default();
+        alice.record_lock_event(locker, 1_000, 100, 200).unwrap();
+        bob.record_lock_event(locker, 3_000, 100, 200).unwrap();
+
+        locker.distribute_penalty_rewards(400).unwrap();
+        assert_eq!(alice.pending_penalty_rewards(locker), Some(100));
+        assert_eq!(bob.pending_penalty_rewards(locker), Some(300));
+
+        // locking more does not earn a share of past penalties.
+        alice.record_lock_event(locker, 4_000, 100, 200).unwrap();
+        assert_eq!(alice.penalty_rewards_owed, 100);
+        locker.distribute_penalty_rewards(800).unwrap();
+        assert_eq!(alice.pending_penalty_rewards(locker), Some(100 + 500));
+        assert_eq!(bob.pending_penalty_rewards(locker), Some(300 + 300));
+
+        // without lockers, penalties are kept for the next lock.
+        let empty = &mut Locker::default();
+        empty.distribute_penalty_rewards(400).unwrap();
+        assert_eq!(empty.penalty_reward_per_token, 0);
+        assert_eq!(empty.undistributed_penalty_rewards, 400);
+        let carol = &mut Escrow::default();
+        carol.record_lock_event(empty, 2_000, 100, 200).unwrap();
+        assert_eq!(empty.undistributed_penalty_rewards, 0);
+        assert_eq!(carol.pending_penalty_rewards(empty), Some(400));
+    }
+
+    #[test]
+    fn test_max_lock() {
+        let locker_params = &LockerParams {
+            max_stake_duration: 4 * ONE_YEAR,
+            max_stake_vote_multiplier: 10,
+            ..LockerParams::default()
+        };
+        let mut escrow = Escrow {
+            escrow_started_at: 100,
+            escrow_ends_at: 100 + ONE_YEAR as i64,
+            amount: 100_000,
+            max_lock: true,
+            ..Escrow::default()
+        };
+        let max_power = escrow.amount * locker_params.max_stake_vote_multiplier as u64;
+        for now in [100, 100 + ONE_YEAR as i64, 100 + 10 * ONE_YEAR as i64] {
+            assert_eq!(
+                locker_params.calculate_voter_power(&escrow, now).unwrap(),
+                max_power,
+                "max lock does not decay"
+            );
+        }
+
+        // disabling the max lock starts a countdown of the max stake duration
+        let disabled_at = 100 + 10 * ONE_YEAR as i64;
+        escrow.max_lock = false;
+        escrow.escrow_ends_at = disabled_at + locker_params.max_stake_duration as i64;
+        assert_eq!(
+            locker_params
+                .calculate_voter_power(&escrow, disabled_at + 2 * ONE_YEAR as i64)
+                .unwrap(),
+            max_power / 2,
+            "decays after disabling"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_curve_power_bounded(
+            voting_power_curve in voting_power_curve(),
+            max_stake_vote_multiplier in 0..=u8::MAX,
+            max_stake_duration_years in 1..=10u64,
+            amount in 0..=(u64::MAX / u8::MAX as u64),
+            lockup_seconds in 1..MAX_SECONDS_BETWEEN_CHECKPOINTS,
+            elapsed_seconds in 0..MAX_SECONDS_BETWEEN_CHECKPOINTS,
+        ) {
+            let locker_params = &LockerParams {
+                max_stake_duration: max_stake_duration_years * ONE_YEAR,
+                max_stake_vote_multiplier,
+                voting_power_curve,
+                ..LockerParams::default()
+            };
+            let escrow = Escrow {
+                escrow_started_at: 100,
+                escrow_ends_at: 100 + lockup_seconds,
+                amount,
+                ..Escrow::default()
+            };
+            let power = locker_params
+                .calculate_voter_power(&escrow, 100 + elapsed_seconds)
+                .unwrap();
+            prop_assert!(power <= amount * max_stake_vote_multiplier as u64);
+        }
+
+        #[test]
+        fn test_curve_power_non_increasing(
+            voting_power_curve in voting_power_curve(),
+            max_stake_vote_multiplier in 1..=u8::MAX,
+            max_stake_duration_years in 1..=10u64,
+            baseline_power_bps in 0..=BPS_PER_UNIT as u16,
+            amount in 0..=(u64::MAX / u8::MAX as u64),
+            lockup_seconds in 1..MAX_SECONDS_BETWEEN_CHECKPOINTS,
+            (later_elapsed, earlier_elapsed) in (0..MAX_SECONDS_BETWEEN_CHECKPOINTS)
+                .prop_flat_map(|later| (Just(later), 0..=later)),
+        ) {
+            let locker_params = &LockerParams {
+                max_stake_duration: max_stake_duration_years * ONE_YEAR,
+                max_stake_vote_multiplier,
+                voting_power_curve,
+                baseline_power_bps,
+                ..LockerParams::default()
+            };
+            let escrow = Escrow {
+                escrow_started_at: 100,
+                escrow_ends_at: 100 + lockup_seconds,
+                amount,
+                ..Escrow::default()
+            };
+            let earlier = locker_params
+                .calculate_voter_power(&escrow, 100 + earlier_elapsed)
+                .unwrap();
+            let later = locker_params
+                .calculate_voter_power(&escrow, 100 + later_elapsed)
+                .unwrap();
+            prop_assert!(earlier >= later);
+        }
+
+        #[test]
+        fn test_baseline_power_floor(
+            voting_power_curve in voting_power_curve(),
+            max_stake_vote_multiplier in 0..=u8::MAX,
+            max_stake_duration_years in 1..=10u64,
+            baseline_power_bps in 0..=BPS_PER_UNIT as u16,
+            amount in 0..=(u64::MAX / u8::MAX as u64),
+            (lockup_seconds, elapsed_seconds) in (1..MAX_SECONDS_BETWEEN_CHECKPOINTS)
+                .prop_flat_map(|lockup| (Just(lockup), 0..lockup)),
+        ) {
+            let locker_params = &LockerParams {
+                max_stake_duration: max_stake_duration_years * ONE_YEAR,
+                max_stake_vote_multiplier,
+                voting_power_curve,
+                baseline_power_bps,
+                ..LockerParams::default()
+            };
+            let escrow = Escrow {
+                escrow_started_at: 100,
+                escrow_ends_at: 100 + lockup_seconds,
+                amount,
+                ..Escrow::default()
+            };
+            let power = locker_params
+                .calculate_voter_power(&escrow, 100 + elapsed_seconds)
+                .unwrap();
+            prop_assert!(
+                power as u128 >= amount as u128 * baseline_power_bps as u128 / BPS_PER_UNIT as u128
+            );
+        }
+
+        #[test]
+        fn test_max_lock_power_constant(
+            voting_power_curve in voting_power_curve(),
+            max_stake_vote_multiplier in 0..=u8::MAX,
+            max_stake_duration_years in 1..=10u64,
+            amount in 0..=(u64::MAX / u8::MAX as u64),
+            lockup_seconds in 1..MAX_SECONDS_BETWEEN_CHECKPOINTS,
+            elapsed_seconds in 0..MAX_SECONDS_BETWEEN_CHECKPOINTS,
+        ) {
+            let locker_params = &LockerParams {
+                max_stake_duration: max_stake_duration_years * ONE_YEAR,
+                max_stake_vote_multiplier,
+                voting_power_curve,
+                ..LockerParams::default()
+            };
+            let escrow = Escrow {
+                escrow_started_at: 100,
+                escrow_ends_at: 100 + lockup_seconds,
+                amount,
+                max_lock: true,
+                ..Escrow::default()
+            };
+            let now = 100 + elapsed_seconds;
+            let max_lockup = Escrow {
+                escrow_started_at: now,
+                escrow_ends_at: now + locker_params.max_stake_duration as i64,
+                amount,
+                ..Escrow::default()
+            };
+            prop_assert_eq!(
+                locker_params.calculate_voter_power(&escrow, now).unwrap(),
+                locker_params.calculate_voter_power(&max_lockup, now).unwrap()
+            );
+        }
+    }
 }
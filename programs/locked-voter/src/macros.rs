@@ -13,6 +13,8 @@ macro_rules! locker_seeds {
 }
 
 /// Generates the signer seeds for an [crate::Escrow].
+///
+/// The position seed is empty for the primary [crate::Escrow], which keeps its address unchanged.
 #[macro_export]
 macro_rules! escrow_seeds {
     ($escrow: expr) => {
@@ -20,6 +22,7 @@ macro_rules! escrow_seeds {
             b"Escrow" as &[u8],
             &$escrow.locker.to_bytes(),
             &$escrow.owner.to_bytes(),
+            &$escrow.position_id.to_le_bytes()[..$escrow.position_seed_len()],
             &[$escrow.bump],
         ]]
     };
@@ -37,3 +40,16 @@ macro_rules! redeemer_seeds {
         ]]
     };
 }
+
+/// Generates the signer seeds for a [crate::RewardDistributor].
+#[macro_export]
+macro_rules! reward_distributor_seeds {
+    ($distributor: expr) => {
+        &[&[
+            b"RewardDistributor" as &[u8],
+            &$distributor.locker.to_bytes(),
+            &$distributor.mint.to_bytes(),
+            &[$distributor.bump],
+        ]]
+    };
+}
@@ -0,0 +1,265 @@
+//! Validation of [LockerParams].
+
+use crate::*;
+
+/// Bounds on [LockerParams] beyond the invariants every [Locker] needs to function.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LockerParamsBounds {
+    /// Shortest allowed [LockerParams::max_stake_duration].
+    pub shortest_max_stake_duration: u64,
+    /// Longest allowed [LockerParams::max_stake_duration].
+    pub longest_max_stake_duration: u64,
+    /// Largest allowed [LockerParams::max_stake_vote_multiplier].
+    pub max_stake_vote_multiplier: u8,
+    /// Largest allowed [LockerParams::early_exit_penalty_bps].
+    pub max_early_exit_penalty_bps: u16,
+}
+
+impl LockerParamsBounds {
+    /// Number of bytes in a [LockerParamsBounds].
+    pub const LEN: usize = 8 + 8 + 1 + 2;
+
+    /// Bounds of a [Locker] until it sets its own with [locked_voter::set_locker_params_bounds].
+    /// The bounds of a [Locker] may never be looser than these.
+    pub const DEFAULT: LockerParamsBounds = LockerParamsBounds {
+        shortest_max_stake_duration: crate::locker::SECONDS_PER_WEEK,
+        longest_max_stake_duration: 4 * 365 * 24 * 60 * 60,
+        max_stake_vote_multiplier: 100,
+        max_early_exit_penalty_bps: crate::locker::BPS_PER_UNIT as u16,
+    };
+
+    /// Checks that the bounds are consistent and no looser than [LockerParamsBounds::DEFAULT].
+    pub fn validate(&self) -> Result<()> {
+        let default = LockerParamsBounds::DEFAULT;
+        invariant!(
+            self.shortest_max_stake_duration >= default.shortest_max_stake_duration
+                && self.shortest_max_stake_duration <= self.longest_max_stake_duration
+                && self.longest_max_stake_duration <= default.longest_max_stake_duration
+                && self.max_stake_vote_multiplier > 0
+                && self.max_stake_vote_multiplier <= default.max_stake_vote_multiplier
+                && self.max_early_exit_penalty_bps <= default.max_early_exit_penalty_bps,
+            InvalidLockerParamsBounds
+        );
+        Ok(())
+    }
+}
+
+impl LockerParams {
+    /// Checks that the parameters are within [LockerParamsBounds::DEFAULT].
+    pub fn validate(&self) -> Result<()> {
+        self.validate_with_bounds(&LockerParamsBounds::DEFAULT)
+    }
+
+    /// Checks that the parameters are within `bounds`, which must themselves be valid.
+    pub fn validate_with_bounds(&self, bounds: &LockerParamsBounds) -> Result<()> {
+        bounds.validate()?;
+        // locks end on week boundaries, so they could not be shorter than a week.
+        invariant!(
+            self.max_stake_duration >= bounds.shortest_max_stake_duration
+                && self.max_stake_duration >= crate::locker::SECONDS_PER_WEEK
+                && self.max_stake_duration <= bounds.longest_max_stake_duration
+                && i64::try_from(self.max_stake_duration).is_ok(),
+            InvalidMaxStakeDuration
+        );
+        // every week a lock may end in needs a slot in the LockerCheckpoints.
+        invariant!(
+            self.max_stake_duration / crate::locker::SECONDS_PER_WEEK
+                < (LockerCheckpoints::MAX_SLOPE_CHANGES as u64),
+            InvalidMaxStakeDuration
+        );
+        // a lock for the max stake duration lasts more than a week less once rounded down,
+        // which must still be long enough.
+        invariant!(
+            self.min_stake_duration <= self.max_stake_duration - crate::locker::SECONDS_PER_WEEK,
+            InvalidMinStakeDuration
+        );
+        invariant!(
+            self.max_stake_vote_multiplier > 0
+                && self.max_stake_vote_multiplier <= bounds.max_stake_vote_multiplier,
+            InvalidMaxStakeVoteMultiplier
+        );
+        invariant!(
+            u64::from(self.baseline_power_bps) <= crate::locker::BPS_PER_UNIT,
+            InvalidBaselinePowerBps
+        );
+        invariant!(
+            self.early_exit_penalty_bps <= bounds.max_early_exit_penalty_bps
+                && u64::from(self.early_exit_penalty_bps) <= crate::locker::BPS_PER_UNIT,
+            InvalidEarlyExitPenaltyBps
+        );
+        Ok(())
+    }
+
+    /// Checks that the parameters may replace `prev` within `bounds`.
+    ///
+    /// The [LockerParams::max_stake_duration] may not decrease, since locks longer than it
+    /// would have their voting power clamped, which the [LockerCheckpoints] cannot follow.
+    pub fn validate_update(&self, prev: &LockerParams, bounds: &LockerParamsBounds) -> Result<()> {
+        self.validate_with_bounds(bounds)?;
+        invariant!(
+            self.max_stake_duration >= prev.max_stake_duration,
+            MaxStakeDurationDecreased
+        );
+        Ok(())
+    }
+
+    /// Checks that the [LockerParams::early_exit_penalty_recipient], if set, is a token account
+    /// of `token_mint` in `accounts`, so that early exits cannot be blocked by a bad recipient.
+    pub fn validate_penalty_recipient(
+        &self,
+        token_mint: Pubkey,
+        accounts: &[AccountInfo],
+    ) -> Result<()> {
+        let recipient = self.early_exit_penalty_recipient;
+        if recipient == Pubkey::default() {
+            return Ok(());
+        }
+        let info = unwrap_opt!(
+            accounts.iter().find(|info| info.key() == recipient),
+            InvalidEarlyExitPenaltyRecipient
+        );
+        let recipient_tokens: Account<TokenAccount> = Account::try_from(info)?;
+        invariant!(
+            recipient_tokens.mint == token_mint,
+            InvalidEarlyExitPenaltyRecipient
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE_WEEK: u64 = 7 * 24 * 60 * 60;
+
+    fn params() -> LockerParams {
+        LockerParams {
+            max_stake_vote_multiplier: 10,
+            min_stake_duration: ONE_WEEK,
+            max_stake_duration: 52 * ONE_WEEK,
+            ..LockerParams::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_locker_params() {
+        assert!(params().validate().is_ok());
+
+        let invalid = [
+            LockerParams {
+                max_stake_duration: 0,
+                min_stake_duration: 0,
+                ..params()
+            },
+            LockerParams {
+                max_stake_duration: u64::MAX,
+                ..params()
+            },
+            LockerParams {
+                min_stake_duration: 53 * ONE_WEEK,
+                ..params()
+            },
+            LockerParams {
+                min_stake_duration: 51 * ONE_WEEK + 1,
+                ..params()
+            },
+            LockerParams {
+                max_stake_vote_multiplier: 0,
+                ..params()
+            },
+            LockerParams {
+                max_stake_vote_multiplier: 101,
+                ..params()
+            },
+            LockerParams {
+                baseline_power_bps: 10_001,
+                ..params()
+            },
+            LockerParams {
+                early_exit_penalty_bps: 10_001,
+                ..params()
+            },
+        ];
+        for params in invalid.iter() {
+            assert!(params.validate().is_err(), "{:?}", params);
+        }
+    }
+
+    #[test]
+    fn test_validate_locker_params_with_bounds() {
+        let bounds = LockerParamsBounds {
+            max_early_exit_penalty_bps: 1_000,
+            longest_max_stake_duration: 26 * ONE_WEEK,
+            ..LockerParamsBounds::DEFAULT
+        };
+        assert!(params().validate_with_bounds(&bounds).is_err());
+
+        let params = LockerParams {
+            max_stake_duration: 26 * ONE_WEEK,
+            early_exit_penalty_bps: 1_000,
+            ..params()
+        };
+        assert!(params.validate_with_bounds(&bounds).is_ok());
+        assert!(LockerParams {
+            early_exit_penalty_bps: 1_001,
+            ..params
+        }
+        .validate_with_bounds(&bounds)
+        .is_err());
+    }
+
+    #[test]
+    fn test_validate_locker_params_update() {
+        let longer = LockerParams {
+            max_stake_duration: 104 * ONE_WEEK,
+            ..params()
+        };
+        let bounds = &LockerParamsBounds::DEFAULT;
+        assert!(longer.validate_update(&params(), bounds).is_ok());
+        assert!(params().validate_update(&longer, bounds).is_err());
+
+        // the bounds of a locker apply to updates.
+        let bounds = &LockerParamsBounds {
+            longest_max_stake_duration: 52 * ONE_WEEK,
+            ..LockerParamsBounds::DEFAULT
+        };
+        assert!(longer.validate_update(&params(), bounds).is_err());
+    }
+
+    #[test]
+    fn test_validate_locker_params_bounds() {
+        assert!(LockerParamsBounds::DEFAULT.validate().is_ok());
+        let invalid = [
+            LockerParamsBounds::default(),
+            LockerParamsBounds {
+                longest_max_stake_duration: LockerParamsBounds::DEFAULT.longest_max_stake_duration
+                    + 1,
+                ..LockerParamsBounds::DEFAULT
+            },
+            LockerParamsBounds {
+                shortest_max_stake_duration: 2 * ONE_WEEK,
+                longest_max_stake_duration: ONE_WEEK,
+                ..LockerParamsBounds::DEFAULT
+            },
+            LockerParamsBounds {
+                max_stake_vote_multiplier: 0,
+                ..LockerParamsBounds::DEFAULT
+            },
+            LockerParamsBounds {
+                max_stake_vote_multiplier: 101,
+                ..LockerParamsBounds::DEFAULT
+            },
+        ];
+        for bounds in invalid.iter() {
+            assert!(bounds.validate().is_err(), "{:?}", bounds);
+            assert!(params().validate_with_bounds(bounds).is_err());
+        }
+
+        // lockers without their own bounds use the defaults.
+        assert_eq!(
+            Locker::default().params_bounds(),
+            LockerParamsBounds::DEFAULT
+        );
+    }
+}
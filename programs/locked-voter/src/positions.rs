@@ -0,0 +1,44 @@
+//! Helpers for owners with multiple [Escrow] lock positions.
+
+use crate::*;
+
+/// Loads the additional [Escrow] positions of the owner of `escrow` from `remaining_accounts`.
+///
+/// Every position must belong to the same [Locker] and [Escrow::owner] as `escrow`,
+/// and no position may be passed more than once.
+pub(crate) fn load_positions<'info>(
+    escrow: &Account<'info, Escrow>,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<Vec<Account<'info, Escrow>>> {
+    let mut positions: Vec<Account<'info, Escrow>> = Vec::with_capacity(remaining_accounts.len());
+    for info in remaining_accounts {
+        assert_keys_neq!(info.key(), escrow.key(), DuplicatePosition);
+        invariant!(
+            !positions
+                .iter()
+                .any(|position| position.key() == info.key()),
+            DuplicatePosition
+        );
+
+        let position = Account::<Escrow>::try_from(info)?;
+        assert_keys_eq!(position.locker, escrow.locker);
+        assert_keys_eq!(position.owner, escrow.owner, PositionOwnerMismatch);
+        positions.push(position);
+    }
+    Ok(positions)
+}
+
+/// Sums the voting power of `escrow` and its additional `positions` at `timestamp`.
+pub(crate) fn total_voting_power_at_time(
+    params: &LockerParams,
+    escrow: &Escrow,
+    positions: &[Account<Escrow>],
+    timestamp: i64,
+) -> Result<u64> {
+    let mut total = unwrap_int!(escrow.voting_power_at_time(params, timestamp));
+    for position in positions {
+        let power = unwrap_int!(position.voting_power_at_time(params, timestamp));
+        total = unwrap_int!(total.checked_add(power));
+    }
+    Ok(total)
+}
@@ -18,7 +18,7 @@ pub struct AddBlacklistEntry<'info> {
         mut,
         has_one = locker,
     )]
-    pub escrow: Account<'info, Escrow>,
+    pub escrow: Box<Account<'info, Escrow>>,
 
     /// The [Blacklist] account to create.
     #[account(
@@ -14,7 +14,7 @@ pub struct CreateRedeemer<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + std::mem::size_of::<LockerRedeemer>(),
+        space = 8 + LockerRedeemer::LEN,
         seeds = [
             b"Redeemer".as_ref(),
             locker.key().as_ref(),
@@ -80,6 +80,9 @@ impl<'info> CreateRedeemer<'info> {
         redeemer.redemption_rate = redemption_rate;
         redeemer.treasury = self.treasury_token_account.key();
         redeemer.cutoff_date = cutoff_date;
+        redeemer.eligibility = RedemptionEligibility::LastLock;
+        redeemer.rate_schedule = RedemptionRateSchedule::default();
+        redeemer.price_numerator = 0;
         redeemer.bump = bump;
 
         msg!("Created locker redeemer for locker {}", self.locker.key());
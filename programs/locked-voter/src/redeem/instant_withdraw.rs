@@ -1,6 +1,8 @@
 use crate::errors::LockedVoterError;
 use crate::*;
+use anchor_lang::AccountsClose;
 use anchor_spl::associated_token::get_associated_token_address;
+use num_traits::ToPrimitive;
 
 /// Accounts for [locked_voter::instant_withdraw].
 #[derive(Accounts)]
@@ -17,18 +19,21 @@ pub struct InstantWithdraw<'info> {
     pub redeemer: Box<Account<'info, LockerRedeemer>>,
 
     /// The [Escrow] that tokens are being withdrawn from.
+    /// It is closed to the payer once all of its tokens are redeemed;
+    /// otherwise it is kept open, so that tokens which are not eligible stay locked.
     #[account(
         mut,
-        close = payer,
         has_one = locker,
     )]
     pub escrow: Box<Account<'info, Escrow>>,
 
-    /// The [EscrowOwner].
-    #[account(
-        mut,
-        constraint = escrow.owner == escrow_owner.key(),
-    )]
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
+
+    /// Authority of the [Escrow].
+    /// For NFT escrows, this is the holder of the position token.
+    #[account(mut)]
     pub escrow_owner: Signer<'info>,
 
     /// The [Blacklist].
@@ -92,6 +97,17 @@ pub struct InstantWithdraw<'info> {
 
 impl<'info> InstantWithdraw<'info> {
     pub fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+
+    /// Validates the accounts. `position_tokens` proves authority over an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        self.escrow
+            .assert_authority(self.escrow_owner.key(), position_tokens)?;
+
         let redeemer_ata =
             get_associated_token_address(&self.redeemer.key(), &self.redeemer.receipt_mint);
 
@@ -107,13 +123,17 @@ impl<'info> InstantWithdraw<'info> {
 
         require!(self.escrow.amount > 0, LockedVoterError::EscrowEmpty);
 
+        require!(!self.escrow.is_vesting(), LockedVoterError::VestingEscrow);
+
         require!(
             self.blacklist.timestamp == 0,
             LockedVoterError::EscrowBlacklisted
         );
 
         require!(
-            self.escrow.escrow_started_at < self.redeemer.cutoff_date,
+            self.escrow
+                .redeemable_amount(self.redeemer.eligibility, self.redeemer.cutoff_date)
+                > 0,
             LockedVoterError::EscrowTooRecent
         );
 
@@ -121,10 +141,20 @@ impl<'info> InstantWithdraw<'info> {
     }
 
     pub fn instant_withdraw(&mut self) -> Result<()> {
-        let base_amount = self.escrow.amount;
-        let ve_sbr_amount = self.escrow.voting_power(&self.locker.params)?;
-        let redemption_rate = self.redeemer.redemption_rate;
-        let receipt_amount = ve_sbr_amount.checked_div(redemption_rate).unwrap();
+        let base_amount = self
+            .escrow
+            .redeemable_amount(self.redeemer.eligibility, self.redeemer.cutoff_date);
+        // only the voting power of the eligible tokens is redeemed.
+        let ve_sbr_amount = unwrap_int!((self.escrow.voting_power(&self.locker.params)? as u128)
+            .checked_mul(base_amount.into())
+            .and_then(|power| power.checked_div(self.escrow.amount.into()))
+            .and_then(|power| power.to_u64()));
+        let now = Clock::get()?.unix_timestamp;
+        let redemption_rate = unwrap_int!(self.redeemer.redemption_rate_at(now));
+        let receipt_amount =
+            unwrap_int!(self.redeemer.receipt_amount(ve_sbr_amount, redemption_rate));
+        // redeeming dust would take the tokens without paying anything.
+        require!(receipt_amount > 0, LockedVoterError::ReceiptAmountZero);
 
         let escrow_seeds: &[&[&[u8]]] = escrow_seeds!(self.escrow);
         let redeemer_seeds: &[&[&[u8]]] = redeemer_seeds!(self.redeemer);
@@ -161,15 +191,23 @@ impl<'info> InstantWithdraw<'info> {
         self.redeemer.amount = self.redeemer.amount.checked_sub(receipt_amount).unwrap();
 
         // Update the escrow state
-        self.escrow.amount = self.escrow.amount.checked_sub(base_amount).unwrap();
-        self.escrow.escrow_ends_at = 0;
-        self.escrow.escrow_started_at = 0;
+        let prev_checkpoint = unwrap_int!(self.locker.params.escrow_checkpoint(&self.escrow, now));
+        self.escrow.record_redemption(
+            &mut self.locker,
+            self.redeemer.eligibility,
+            self.redeemer.cutoff_date,
+            base_amount,
+        )?;
+        self.escrow.record_lock_checkpoint(now);
+        let next_checkpoint = unwrap_int!(self.locker.params.escrow_checkpoint(&self.escrow, now));
+        self.checkpoints
+            .record_escrow_change(prev_checkpoint, next_checkpoint, now)?;
 
         // update blacklist
         self.blacklist.locker = self.locker.key();
         self.blacklist.escrow = self.escrow.key();
         self.blacklist.owner = self.escrow_owner.key();
-        self.blacklist.timestamp = Clock::get()?.unix_timestamp;
+        self.blacklist.timestamp = now;
 
         // Emit an event for the withdrawal
         emit!(InstantWithdrawEvent {
@@ -177,9 +215,14 @@ impl<'info> InstantWithdraw<'info> {
             escrow: self.escrow.key(),
             owner: self.escrow_owner.key(),
             amount: receipt_amount,
-            timestamp: Clock::get()?.unix_timestamp
+            redemption_rate,
+            timestamp: now,
         });
 
+        if self.escrow.amount == 0 {
+            self.escrow.close(self.payer.to_account_info())?;
+        }
+
         Ok(())
     }
 }
@@ -198,6 +241,8 @@ pub struct InstantWithdrawEvent {
     pub owner: Pubkey,
     /// The amount withdrawn.
     pub amount: u64,
+    /// The redemption rate in effect.
+    pub redemption_rate: u64,
     /// The time of withdrawal.
     pub timestamp: i64,
 }
@@ -0,0 +1,47 @@
+use crate::*;
+use anchor_spl::token;
+
+/// Accounts for [locked_voter::instant_withdraw_nft].
+#[derive(Accounts)]
+pub struct InstantWithdrawNft<'info> {
+    /// Instant withdraw accounts. [InstantWithdraw::escrow_owner] is the holder of the position token.
+    pub instant_withdraw: InstantWithdraw<'info>,
+    /// Token account of the holder containing the position token.
+    #[account(mut)]
+    pub position_tokens: Box<Account<'info, TokenAccount>>,
+    /// Mint of the position token.
+    #[account(mut)]
+    pub position_mint: Box<Account<'info, Mint>>,
+}
+
+impl<'info> InstantWithdrawNft<'info> {
+    pub fn validate(&self) -> Result<()> {
+        self.instant_withdraw
+            .validate_with_position_tokens(Some(&self.position_tokens))?;
+        assert_keys_eq!(
+            self.position_mint,
+            self.instant_withdraw.escrow.position_mint
+        );
+        Ok(())
+    }
+
+    pub fn instant_withdraw_nft(&mut self) -> Result<()> {
+        self.instant_withdraw.instant_withdraw()?;
+        if self.instant_withdraw.escrow.amount > 0 {
+            return Ok(());
+        }
+
+        // the escrow was closed, so the position no longer represents any tokens.
+        token::burn(
+            CpiContext::new(
+                self.instant_withdraw.token_program.to_account_info(),
+                token::Burn {
+                    mint: self.position_mint.to_account_info(),
+                    from: self.position_tokens.to_account_info(),
+                    authority: self.instant_withdraw.escrow_owner.to_account_info(),
+                },
+            ),
+            1,
+        )
+    }
+}
@@ -3,10 +3,17 @@ pub mod add_blacklist_entry;
 pub mod add_funds;
 pub mod create_redeemer;
 pub mod instant_withdraw;
+pub mod instant_withdraw_nft;
+pub mod partial_instant_withdraw;
+pub mod partial_instant_withdraw_nft;
 pub mod remove_all_funds;
 pub mod remove_blacklist_entry;
+pub mod resize_redeemer;
+pub mod set_redemption_price;
+pub mod set_redemption_rate_schedule;
 pub mod toggle_redeemer;
 pub mod update_redeemer_admin;
+pub mod update_redemption_eligibility;
 pub mod update_redemption_rate;
 pub mod update_treasury;
 
@@ -15,9 +22,16 @@ pub use add_blacklist_entry::*;
 pub use add_funds::*;
 pub use create_redeemer::*;
 pub use instant_withdraw::*;
+pub use instant_withdraw_nft::*;
+pub use partial_instant_withdraw::*;
+pub use partial_instant_withdraw_nft::*;
 pub use remove_all_funds::*;
 pub use remove_blacklist_entry::*;
+pub use resize_redeemer::*;
+pub use set_redemption_price::*;
+pub use set_redemption_rate_schedule::*;
 pub use toggle_redeemer::*;
 pub use update_redeemer_admin::*;
+pub use update_redemption_eligibility::*;
 pub use update_redemption_rate::*;
 pub use update_treasury::*;
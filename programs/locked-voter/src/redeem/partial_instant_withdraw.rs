@@ -0,0 +1,230 @@
+use crate::errors::LockedVoterError;
+use crate::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use num_traits::ToPrimitive;
+
+/// Accounts for [locked_voter::partial_instant_withdraw].
+#[derive(Accounts)]
+pub struct PartialInstantWithdraw<'info> {
+    /// The [Locker].
+    #[account(mut)]
+    pub locker: Box<Account<'info, Locker>>,
+
+    /// The [LockerRedeemer].
+    #[account(
+        mut,
+        constraint = redeemer.locker == locker.key(),
+    )]
+    pub redeemer: Box<Account<'info, LockerRedeemer>>,
+
+    /// The [Escrow] that tokens are being withdrawn from. It is kept open.
+    #[account(
+        mut,
+        has_one = locker,
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    /// The [LockerCheckpoints] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub checkpoints: Box<Account<'info, LockerCheckpoints>>,
+
+    /// Authority of the [Escrow].
+    /// For NFT escrows, this is the holder of the position token.
+    pub escrow_owner: Signer<'info>,
+
+    /// CHECK: [Blacklist] of the [Escrow]. Must not exist.
+    #[account(
+        seeds = [
+            b"Blacklist".as_ref(),
+            locker.key().as_ref(),
+            escrow.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+
+    /// The receipt token [Mint].
+    #[account(mut)]
+    pub receipt_mint: Box<Account<'info, Mint>>,
+
+    /// The [TokenAccount] holding the redeemer's receipt tokens.
+    #[account(
+        mut,
+        constraint = redeemer_receipt_account.mint == receipt_mint.key(),
+    )]
+    pub redeemer_receipt_account: Box<Account<'info, TokenAccount>>,
+
+    /// The [TokenAccount] holding the escrow's tokens, i.e., the source of the withdrawal.
+    #[account(
+        mut,
+        constraint = escrow_tokens.key() == escrow.tokens,
+    )]
+    pub escrow_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// The DAO treasury [TokenAccount] that will receive the withdrawn tokens.
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == locker.token_mint,
+        constraint = treasury_token_account.key() == redeemer.treasury,
+    )]
+    pub treasury_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// The receipt [TokenAccount] owned by the user.
+    #[account(
+        mut,
+        constraint = user_receipt.mint == receipt_mint.key(),
+        constraint = user_receipt.owner == escrow_owner.key(),
+    )]
+    pub user_receipt: Box<Account<'info, TokenAccount>>,
+
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> PartialInstantWithdraw<'info> {
+    pub fn validate(&self) -> Result<()> {
+        self.validate_with_position_tokens(None)
+    }
+
+    /// Validates the accounts. `position_tokens` proves authority over an NFT [Escrow].
+    pub fn validate_with_position_tokens(
+        &self,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        self.escrow
+            .assert_authority(self.escrow_owner.key(), position_tokens)?;
+
+        let redeemer_ata =
+            get_associated_token_address(&self.redeemer.key(), &self.redeemer.receipt_mint);
+
+        require!(
+            redeemer_ata == self.redeemer_receipt_account.key(),
+            LockedVoterError::InvalidTokenAccount,
+        );
+
+        require!(
+            self.redeemer.status == 1,
+            LockedVoterError::RedeemerNotActive
+        );
+
+        require!(self.escrow.amount > 0, LockedVoterError::EscrowEmpty);
+
+        require!(!self.escrow.is_vesting(), LockedVoterError::VestingEscrow);
+
+        require!(
+            self.blacklist.data_is_empty(),
+            LockedVoterError::EscrowBlacklisted
+        );
+
+        Ok(())
+    }
+
+    pub fn partial_instant_withdraw(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, LockedVoterError::RedeemAmountZero);
+        let redeemable_amount = self
+            .escrow
+            .redeemable_amount(self.redeemer.eligibility, self.redeemer.cutoff_date);
+        require!(redeemable_amount > 0, LockedVoterError::EscrowTooRecent);
+        require!(
+            amount <= redeemable_amount,
+            LockedVoterError::RedeemAmountTooLarge
+        );
+
+        // the redeemed tokens carry their share of the voting power of the escrow.
+        let ve_sbr_amount = unwrap_int!((self.escrow.voting_power(&self.locker.params)? as u128)
+            .checked_mul(amount.into())
+            .and_then(|power| power.checked_div(self.escrow.amount.into()))
+            .and_then(|power| power.to_u64()));
+        let now = Clock::get()?.unix_timestamp;
+        let redemption_rate = unwrap_int!(self.redeemer.redemption_rate_at(now));
+        let receipt_amount =
+            unwrap_int!(self.redeemer.receipt_amount(ve_sbr_amount, redemption_rate));
+        // redeeming dust would take the tokens without paying anything.
+        require!(receipt_amount > 0, LockedVoterError::ReceiptAmountZero);
+
+        let escrow_seeds: &[&[&[u8]]] = escrow_seeds!(self.escrow);
+        let redeemer_seeds: &[&[&[u8]]] = redeemer_seeds!(self.redeemer);
+
+        // Transfer escrow tokens to treasury
+        anchor_spl::token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                anchor_spl::token::Transfer {
+                    from: self.escrow_tokens.to_account_info(),
+                    to: self.treasury_token_account.to_account_info(),
+                    authority: self.escrow.to_account_info(),
+                },
+            )
+            .with_signer(escrow_seeds),
+            amount,
+        )?;
+
+        // transfer receipt tokens to the user
+        anchor_spl::token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                anchor_spl::token::Transfer {
+                    from: self.redeemer_receipt_account.to_account_info(),
+                    to: self.user_receipt.to_account_info(),
+                    authority: self.redeemer.to_account_info(),
+                },
+            )
+            .with_signer(redeemer_seeds),
+            receipt_amount,
+        )?;
+
+        // Update the redeemer balance
+        self.redeemer.amount = unwrap_int!(self.redeemer.amount.checked_sub(receipt_amount));
+
+        // Update the escrow state
+        let prev_checkpoint = unwrap_int!(self.locker.params.escrow_checkpoint(&self.escrow, now));
+        self.escrow.record_redemption(
+            &mut self.locker,
+            self.redeemer.eligibility,
+            self.redeemer.cutoff_date,
+            amount,
+        )?;
+        self.escrow.record_lock_checkpoint(now);
+        let next_checkpoint = unwrap_int!(self.locker.params.escrow_checkpoint(&self.escrow, now));
+        self.checkpoints
+            .record_escrow_change(prev_checkpoint, next_checkpoint, now)?;
+
+        // Emit an event for the withdrawal
+        emit!(PartialInstantWithdrawEvent {
+            locker: self.locker.key(),
+            escrow: self.escrow.key(),
+            owner: self.escrow_owner.key(),
+            redeemed_amount: amount,
+            amount: receipt_amount,
+            redemption_rate,
+            total_redeemed_amount: self.escrow.redeemed_amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+/// Event emitted when part of an [Escrow] is instantly withdrawn.
+#[event]
+pub struct PartialInstantWithdrawEvent {
+    /// The locker.
+    #[index]
+    pub locker: Pubkey,
+    /// The escrow.
+    #[index]
+    pub escrow: Pubkey,
+    /// The escrow owner.
+    #[index]
+    pub owner: Pubkey,
+    /// The amount of locked tokens redeemed.
+    pub redeemed_amount: u64,
+    /// The amount of receipt tokens paid out.
+    pub amount: u64,
+    /// The redemption rate in effect.
+    pub redemption_rate: u64,
+    /// The amount of locked tokens redeemed from the escrow so far.
+    pub total_redeemed_amount: u64,
+    /// The time of withdrawal.
+    pub timestamp: i64,
+}
@@ -0,0 +1,17 @@
+use crate::*;
+
+/// Accounts for [locked_voter::partial_instant_withdraw_nft].
+#[derive(Accounts)]
+pub struct PartialInstantWithdrawNft<'info> {
+    /// Partial instant withdraw accounts. [PartialInstantWithdraw::escrow_owner] is the holder of the position token.
+    pub partial_instant_withdraw: PartialInstantWithdraw<'info>,
+    /// Token account of the holder containing the position token.
+    pub position_tokens: Box<Account<'info, TokenAccount>>,
+}
+
+impl<'info> PartialInstantWithdrawNft<'info> {
+    pub fn validate(&self) -> Result<()> {
+        self.partial_instant_withdraw
+            .validate_with_position_tokens(Some(&self.position_tokens))
+    }
+}
@@ -14,7 +14,7 @@ pub struct RemoveBlacklistEntry<'info> {
     pub redeemer: Account<'info, LockerRedeemer>,
 
     /// The [Escrow] to remove from blacklist.
-    pub escrow: Account<'info, Escrow>,
+    pub escrow: Box<Account<'info, Escrow>>,
 
     /// The [Blacklist] account to remove.
     #[account(
@@ -0,0 +1,68 @@
+use crate::*;
+use anchor_lang::Discriminator;
+use num_traits::ToPrimitive;
+
+/// Accounts for [locked_voter::resize_redeemer].
+#[derive(Accounts)]
+pub struct ResizeRedeemer<'info> {
+    /// CHECK: The [LockerRedeemer] to resize. Its discriminator is checked in [ResizeRedeemer::validate].
+    #[account(mut, owner = crate::ID)]
+    pub redeemer: UncheckedAccount<'info>,
+
+    /// Payer of the additional rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ResizeRedeemer<'info> {
+    /// Grows a [LockerRedeemer] created with an older layout to [LockerRedeemer::LEN].
+    /// New fields are zero-initialized, which keeps the fixed redemption rate.
+    pub fn resize_redeemer(&mut self) -> Result<()> {
+        let redeemer_info = self.redeemer.to_account_info();
+        let prev_len = redeemer_info.data_len();
+        let next_len = 8 + LockerRedeemer::LEN;
+        if !resize_escrow::grow_account(
+            &redeemer_info,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+            next_len,
+        )? {
+            return Ok(());
+        }
+
+        emit!(ResizeRedeemerEvent {
+            redeemer: redeemer_info.key(),
+            prev_len: unwrap_int!(prev_len.to_u64()),
+            next_len: unwrap_int!(next_len.to_u64()),
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for ResizeRedeemer<'info> {
+    fn validate(&self) -> Result<()> {
+        let data = self.redeemer.try_borrow_data()?;
+        invariant!(data.len() >= 8, "redeemer data too small");
+        invariant!(
+            data[..8] == LockerRedeemer::discriminator(),
+            "account is not a redeemer"
+        );
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [locked_voter::resize_redeemer].
+pub struct ResizeRedeemerEvent {
+    /// The [LockerRedeemer] being resized.
+    #[index]
+    pub redeemer: Pubkey,
+    /// Previous size of the account data.
+    pub prev_len: u64,
+    /// New size of the account data.
+    pub next_len: u64,
+}
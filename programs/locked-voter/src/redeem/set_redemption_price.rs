@@ -0,0 +1,95 @@
+use crate::errors::LockedVoterError;
+use crate::*;
+
+/// Accounts for setting a locker redeemer's redemption price.
+#[derive(Accounts)]
+pub struct SetRedemptionPrice<'info> {
+    /// The locker that is associated with the redeemer.
+    pub locker: Account<'info, Locker>,
+
+    /// The redeemer account to update.
+    #[account(
+        mut,
+        constraint = redeemer.locker == locker.key()
+    )]
+    pub redeemer: Account<'info, LockerRedeemer>,
+
+    /// The [Locker::token_mint].
+    #[account(address = locker.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    /// The receipt token [Mint].
+    #[account(address = redeemer.receipt_mint)]
+    pub receipt_mint: Account<'info, Mint>,
+
+    /// The admin of the redeemer.
+    #[account(
+        constraint = redeemer.admin.key() == payer.key()
+    )]
+    pub payer: Signer<'info>,
+}
+
+impl<'info> SetRedemptionPrice<'info> {
+    /// Sets the price of one whole unit of voting power to `numerator / denominator` whole receipt tokens.
+    ///
+    /// While a price is set, the [LockerRedeemer::redemption_rate] and its schedule do not apply.
+    /// A zero `numerator` and `denominator` remove the price.
+    pub fn set_redemption_price(&mut self, numerator: u64, denominator: u64) -> Result<()> {
+        require!(
+            (numerator > 0) == (denominator > 0),
+            LockedVoterError::InvalidRedemptionPrice
+        );
+        let locker_decimals = self.token_mint.decimals;
+        let receipt_decimals = self.receipt_mint.decimals;
+        require!(
+            decimals_scale(
+                locker_decimals.max(receipt_decimals) - locker_decimals.min(receipt_decimals)
+            )
+            .is_some(),
+            LockedVoterError::InvalidRedemptionPrice
+        );
+
+        let redeemer = &mut self.redeemer;
+        redeemer.price_numerator = numerator;
+        redeemer.price_denominator = denominator;
+        redeemer.locker_decimals = locker_decimals;
+        redeemer.receipt_decimals = receipt_decimals;
+
+        // Emit an event for the price change
+        emit!(SetRedemptionPriceEvent {
+            locker: self.locker.key(),
+            redeemer: redeemer.key(),
+            numerator,
+            denominator,
+            locker_decimals,
+            receipt_decimals,
+            admin: self.payer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+/// Event emitted when the redemption price is set.
+pub struct SetRedemptionPriceEvent {
+    /// The locker.
+    #[index]
+    pub locker: Pubkey,
+    /// The redeemer.
+    #[index]
+    pub redeemer: Pubkey,
+    /// Numerator of the price.
+    pub numerator: u64,
+    /// Denominator of the price.
+    pub denominator: u64,
+    /// Decimals of the locker mint.
+    pub locker_decimals: u8,
+    /// Decimals of the receipt mint.
+    pub receipt_decimals: u8,
+    /// The admin that set the price.
+    pub admin: Pubkey,
+    /// The time of update.
+    pub timestamp: i64,
+}
@@ -0,0 +1,63 @@
+use crate::errors::LockedVoterError;
+use crate::*;
+
+/// Accounts for setting a locker redeemer's redemption rate schedule.
+#[derive(Accounts)]
+pub struct SetRedemptionRateSchedule<'info> {
+    /// The locker that is associated with the redeemer.
+    pub locker: Account<'info, Locker>,
+
+    /// The redeemer account to update.
+    #[account(
+        mut,
+        constraint = redeemer.locker == locker.key()
+    )]
+    pub redeemer: Account<'info, LockerRedeemer>,
+
+    /// The admin of the redeemer.
+    #[account(
+        constraint = redeemer.admin.key() == payer.key()
+    )]
+    pub payer: Signer<'info>,
+}
+
+impl<'info> SetRedemptionRateSchedule<'info> {
+    pub fn set_redemption_rate_schedule(
+        &mut self,
+        rate_schedule: RedemptionRateSchedule,
+    ) -> Result<()> {
+        rate_schedule.validate()?;
+
+        let redeemer = &mut self.redeemer;
+        require!(!redeemer.has_price(), LockedVoterError::RedemptionPriceSet);
+        redeemer.rate_schedule = rate_schedule;
+
+        // Emit an event for the schedule change
+        emit!(SetRedemptionRateScheduleEvent {
+            locker: self.locker.key(),
+            redeemer: redeemer.key(),
+            rate_schedule,
+            admin: self.payer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+/// Event emitted when the redemption rate schedule is set.
+pub struct SetRedemptionRateScheduleEvent {
+    /// The locker.
+    #[index]
+    pub locker: Pubkey,
+    /// The redeemer.
+    #[index]
+    pub redeemer: Pubkey,
+    /// The new schedule.
+    pub rate_schedule: RedemptionRateSchedule,
+    /// The admin that set the schedule.
+    pub admin: Pubkey,
+    /// The time of update.
+    pub timestamp: i64,
+}
@@ -0,0 +1,65 @@
+use crate::*;
+
+/// Accounts for updating how a locker redeemer applies its cutoff date.
+#[derive(Accounts)]
+pub struct UpdateRedemptionEligibility<'info> {
+    /// The locker that is associated with the redeemer.
+    pub locker: Account<'info, Locker>,
+
+    /// The redeemer account to update.
+    #[account(
+        mut,
+        constraint = redeemer.locker == locker.key()
+    )]
+    pub redeemer: Account<'info, LockerRedeemer>,
+
+    /// The admin of the redeemer.
+    #[account(
+        constraint = redeemer.admin.key() == payer.key()
+    )]
+    pub payer: Signer<'info>,
+}
+
+impl<'info> UpdateRedemptionEligibility<'info> {
+    pub fn update_redemption_eligibility(
+        &mut self,
+        eligibility: RedemptionEligibility,
+    ) -> Result<()> {
+        let redeemer = &mut self.redeemer;
+
+        let previous_eligibility = redeemer.eligibility;
+
+        redeemer.eligibility = eligibility;
+
+        // Emit an event for the eligibility change
+        emit!(UpdateRedemptionEligibilityEvent {
+            locker: self.locker.key(),
+            redeemer: redeemer.key(),
+            previous_eligibility,
+            new_eligibility: eligibility,
+            admin: self.payer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+/// Event emitted when the redemption eligibility is updated.
+pub struct UpdateRedemptionEligibilityEvent {
+    /// The locker.
+    #[index]
+    pub locker: Pubkey,
+    /// The redeemer.
+    #[index]
+    pub redeemer: Pubkey,
+    /// Previous eligibility basis.
+    pub previous_eligibility: RedemptionEligibility,
+    /// New eligibility basis.
+    pub new_eligibility: RedemptionEligibility,
+    /// The admin that updated the eligibility.
+    pub admin: Pubkey,
+    /// The time of update.
+    pub timestamp: i64,
+}
@@ -25,6 +25,7 @@ impl<'info> UpdateRedemptionRate<'info> {
     pub fn update_redemption_rate(&mut self, new_rate: u64) -> Result<()> {
         let redeemer = &mut self.redeemer;
 
+        require!(!redeemer.has_price(), LockedVoterError::RedemptionPriceSet);
         require!(new_rate > 0, LockedVoterError::InvalidRedemptionRate);
         require!(
             new_rate != redeemer.redemption_rate,
@@ -4,6 +4,7 @@
 use anchor_lang::solana_program::pubkey::PUBKEY_BYTES;
 
 use crate::*;
+use num_traits::ToPrimitive;
 
 /// blacklist account for redeemer once redeemed
 #[account]
@@ -36,6 +37,7 @@ pub struct LockerRedeemer {
     /// status of the redeemer
     pub status: u8, // 0 = paused, 1 = active
     /// redemption rate multiplier
+    /// Does not apply while a price is set; see [LockerRedeemer::price_numerator].
     pub redemption_rate: u64, // e.g 10000 veSBR = 1 USDC
     /// treasury address
     pub treasury: Pubkey,
@@ -43,6 +45,200 @@ pub struct LockerRedeemer {
     pub amount: u64,
     /// escrows started after this date will be barred from redemption
     pub cutoff_date: i64,
+    /// Which tokens of an [Escrow] were locked before the [LockerRedeemer::cutoff_date].
+    /// Fits in the padding of [LockerRedeemer] accounts created before it existed.
+    pub eligibility: RedemptionEligibility,
+    /// Schedule of the redemption rate. Unless it is [RedemptionRateCurve::Fixed],
+    /// it overrides [LockerRedeemer::redemption_rate].
+    pub rate_schedule: RedemptionRateSchedule,
+    /// Numerator of the price of one whole unit of voting power in whole receipt tokens.
+    /// Voting power has the decimals of the [Locker::token_mint].
+    /// Zero for redeemers without a price, which divide voting power by the redemption rate.
+    pub price_numerator: u64,
+    /// Decimals of the [Locker::token_mint] when the price was set.
+    pub locker_decimals: u8,
+    /// Decimals of the [LockerRedeemer::receipt_mint] when the price was set.
+    pub receipt_decimals: u8,
+    /// Denominator of the price; see [LockerRedeemer::price_numerator].
+    pub price_denominator: u64,
+}
+
+impl LockerRedeemer {
+    /// Number of bytes in a [LockerRedeemer].
+    pub const LEN: usize = PUBKEY_BYTES * 4
+        + 1
+        + 1
+        + 8
+        + PUBKEY_BYTES
+        + 8
+        + 8
+        + 1
+        + RedemptionRateSchedule::LEN
+        + 8
+        + 1
+        + 1
+        + 8;
+
+    /// Gets the redemption rate in effect at `now`.
+    pub fn redemption_rate_at(&self, now: i64) -> Option<u64> {
+        self.rate_schedule.rate_at(now, self.redemption_rate)
+    }
+
+    /// Whether the redeemer has a price set via [crate::locked_voter::set_redemption_price].
+    pub fn has_price(&self) -> bool {
+        self.price_numerator != 0
+    }
+
+    /// Amount of receipt tokens paid out for `voting_power` at `redemption_rate`.
+    ///
+    /// With a price, the redemption rate does not apply and this is
+    /// `voting_power * price_numerator / price_denominator`, converted from
+    /// the decimals of the locker mint to those of the receipt mint.
+    /// The amount is always rounded down, so the redeemer never pays more than the price.
+    /// Returns [None] on overflow.
+    pub fn receipt_amount(&self, voting_power: u64, redemption_rate: u64) -> Option<u64> {
+        if !self.has_price() {
+            return voting_power.checked_div(redemption_rate);
+        }
+        let mut numerator = u128::from(voting_power).checked_mul(self.price_numerator.into())?;
+        let mut denominator = u128::from(self.price_denominator);
+        if self.receipt_decimals >= self.locker_decimals {
+            numerator = numerator.checked_mul(decimals_scale(
+                self.receipt_decimals - self.locker_decimals,
+            )?)?;
+        } else {
+            denominator = denominator.checked_mul(decimals_scale(
+                self.locker_decimals - self.receipt_decimals,
+            )?)?;
+        }
+        numerator.checked_div(denominator)?.to_u64()
+    }
+}
+
+/// Number of base units in a whole token with `decimals` decimals.
+pub fn decimals_scale(decimals: u8) -> Option<u128> {
+    10u128.checked_pow(decimals.into())
+}
+
+/// Shape of a [RedemptionRateSchedule].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RedemptionRateCurve {
+    /// The [LockerRedeemer::redemption_rate] applies.
+    Fixed = 0,
+    /// The rate moves linearly from the start rate to the end rate.
+    Linear = 1,
+    /// The rate moves from the start rate to the end rate in equal steps.
+    Stepped = 2,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for RedemptionRateCurve {
+    fn default() -> Self {
+        RedemptionRateCurve::Fixed
+    }
+}
+
+/// How the redemption rate of a [LockerRedeemer] changes over time.
+///
+/// Before [RedemptionRateSchedule::start_at], the start rate applies, and from
+/// [RedemptionRateSchedule::end_at] on, the end rate applies.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RedemptionRateSchedule {
+    /// Shape of the schedule.
+    pub curve: RedemptionRateCurve,
+    /// When the rate starts to move away from [RedemptionRateSchedule::start_rate].
+    pub start_at: i64,
+    /// When the rate reaches [RedemptionRateSchedule::end_rate].
+    pub end_at: i64,
+    /// Rate at the start of the schedule.
+    pub start_rate: u64,
+    /// Rate at the end of the schedule.
+    pub end_rate: u64,
+    /// Number of steps of a [RedemptionRateCurve::Stepped] schedule.
+    pub steps: u8,
+}
+
+impl RedemptionRateSchedule {
+    /// Number of bytes in a [RedemptionRateSchedule].
+    pub const LEN: usize = 1 + 8 + 8 + 8 + 8 + 1;
+
+    /// Checks that the schedule produces valid rates.
+    pub fn validate(&self) -> Result<()> {
+        if self.curve == RedemptionRateCurve::Fixed {
+            return Ok(());
+        }
+        invariant!(self.start_at < self.end_at, InvalidRedemptionRateSchedule);
+        invariant!(
+            self.start_rate > 0 && self.end_rate > 0,
+            InvalidRedemptionRateSchedule
+        );
+        invariant!(
+            self.curve != RedemptionRateCurve::Stepped || self.steps > 0,
+            InvalidRedemptionRateSchedule
+        );
+        Ok(())
+    }
+
+    /// Gets the rate at `now`. `fixed_rate` applies to [RedemptionRateCurve::Fixed] schedules.
+    pub fn rate_at(&self, now: i64, fixed_rate: u64) -> Option<u64> {
+        if self.curve == RedemptionRateCurve::Fixed {
+            return Some(fixed_rate);
+        }
+        if now <= self.start_at {
+            return Some(self.start_rate);
+        }
+        if now >= self.end_at {
+            return Some(self.end_rate);
+        }
+        let duration: u128 = self.end_at.checked_sub(self.start_at)?.to_u128()?;
+        let elapsed: u128 = now.checked_sub(self.start_at)?.to_u128()?;
+        // the fraction of the schedule that has passed, as `numerator / denominator`.
+        let (numerator, denominator) = match self.curve {
+            RedemptionRateCurve::Stepped => {
+                let steps = u128::from(self.steps);
+                (elapsed.checked_mul(steps)?.checked_div(duration)?, steps)
+            }
+            _ => (elapsed, duration),
+        };
+        let start_rate = u128::from(self.start_rate);
+        let end_rate = u128::from(self.end_rate);
+        let rate = if end_rate >= start_rate {
+            start_rate.checked_add(
+                end_rate
+                    .checked_sub(start_rate)?
+                    .checked_mul(numerator)?
+                    .checked_div(denominator)?,
+            )?
+        } else {
+            start_rate.checked_sub(
+                start_rate
+                    .checked_sub(end_rate)?
+                    .checked_mul(numerator)?
+                    .checked_div(denominator)?,
+            )?
+        };
+        rate.to_u64()
+    }
+}
+
+/// How the [LockerRedeemer::cutoff_date] applies to an [Escrow].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RedemptionEligibility {
+    /// The whole [Escrow] is eligible if it was last locked into before the cutoff.
+    LastLock = 0,
+    /// The whole [Escrow] is eligible if it was first locked into before the cutoff.
+    FirstLock = 1,
+    /// Only the tokens deposited before the cutoff are eligible.
+    ProRata = 2,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for RedemptionEligibility {
+    fn default() -> Self {
+        RedemptionEligibility::LastLock
+    }
 }
 
 /// A group of [Escrow]s.
@@ -54,6 +250,7 @@ pub struct Locker {
     /// Bump seed.
     pub bump: u8,
     /// Mint of the token that must be locked in the [Locker].
+    /// Only mints of the SPL Token program are supported, not Token-2022.
     pub token_mint: Pubkey,
     /// Total number of tokens locked in [Escrow]s.
     pub locked_supply: u64,
@@ -61,11 +258,52 @@ pub struct Locker {
     pub governor: Pubkey,
     /// Mutable parameters of how a [Locker] should behave.
     pub params: LockerParams,
+
+    /// Early exit penalties distributed to the pool per locked token,
+    /// scaled by [crate::locker::PENALTY_REWARD_PRECISION].
+    pub penalty_reward_per_token: u128,
+
+    /// [LockerParams] staged to replace [Locker::params].
+    pub pending_params: LockerParams,
+    /// When the [Locker::pending_params] may be applied. Zero if no params are staged.
+    pub pending_params_activates_at: i64,
+
+    /// Early exit penalties paid while nothing was locked,
+    /// distributed once tokens are locked again.
+    pub undistributed_penalty_rewards: u64,
+
+    /// Bounds on the [Locker::params]. Unset if zero; see [Locker::params_bounds].
+    pub params_bounds: LockerParamsBounds,
 }
 
 impl Locker {
     /// Number of bytes in a [Locker].
-    pub const LEN: usize = PUBKEY_BYTES + 1 + PUBKEY_BYTES + 8 + PUBKEY_BYTES + LockerParams::LEN;
+    pub const LEN: usize = PUBKEY_BYTES
+        + 1
+        + PUBKEY_BYTES
+        + 8
+        + PUBKEY_BYTES
+        + LockerParams::LEN
+        + 16
+        + LockerParams::LEN
+        + 8
+        + 8
+        + LockerParamsBounds::LEN;
+
+    /// Bounds on the [Locker::params]: the [Locker::params_bounds] if set,
+    /// otherwise [LockerParamsBounds::DEFAULT].
+    pub fn params_bounds(&self) -> LockerParamsBounds {
+        if self.params_bounds == LockerParamsBounds::default() {
+            LockerParamsBounds::DEFAULT
+        } else {
+            self.params_bounds
+        }
+    }
+
+    /// Whether [LockerParams] are staged.
+    pub fn has_pending_params(&self) -> bool {
+        self.pending_params_activates_at != 0
+    }
 }
 
 /// Contains parameters for the [Locker].
@@ -82,11 +320,44 @@ pub struct LockerParams {
     pub max_stake_duration: u64,
     /// Minimum number of votes required to activate a proposal.
     pub proposal_activation_min_votes: u64,
+    /// How voting power decays over the remaining lock time.
+    pub voting_power_curve: VotingPowerCurve,
+    /// Minimum voting power of any active lock, in basis points of the locked amount.
+    /// Zero disables the baseline.
+    pub baseline_power_bps: u16,
+    /// Penalty for exiting an [Escrow] with the maximum stake duration remaining, in basis points
+    /// of the locked amount. The penalty scales down linearly with the remaining lock time.
+    /// Zero disables early exits.
+    pub early_exit_penalty_bps: u16,
+    /// Token account receiving early exit penalties.
+    /// If [Pubkey::default], penalties go to a pool claimable by the remaining lockers.
+    pub early_exit_penalty_recipient: Pubkey,
 }
 
 impl LockerParams {
     /// Number of bytes in a [LockerParams].
-    pub const LEN: usize = 1 + 1 + 8 + 8 + 8;
+    pub const LEN: usize = 1 + 1 + 8 + 8 + 8 + 1 + 2 + 2 + PUBKEY_BYTES;
+}
+
+/// Shape of the voting power of an [Escrow] over its remaining lock time.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum VotingPowerCurve {
+    /// Voting power decays linearly with the remaining lock time.
+    Linear = 0,
+    /// Voting power is set by the total lock duration and does not decay until the lock expires.
+    Flat = 1,
+    /// Voting power halves every eighth of the maximum stake duration.
+    Exponential = 2,
+    /// Voting power decays linearly with the remaining lock time, rounded down to whole weeks.
+    SteppedWeekly = 3,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for VotingPowerCurve {
+    fn default() -> Self {
+        VotingPowerCurve::Linear
+    }
 }
 
 /// An entry in the [Locker]'s whitelist.
@@ -94,83 +365,1311 @@ impl LockerParams {
 /// The whitelist controls which programs are allowed to stake tokens into the system.
 #[account]
 #[derive(Copy, Debug, Default)]
-pub struct LockerWhitelistEntry {
+pub struct LockerWhitelistEntry {
+    /// Bump seed.
+    pub bump: u8,
+    /// [Locker] this whitelist entry belongs to.
+    pub locker: Pubkey,
+    /// Key of the program_id allowed to call the `lock` CPI.
+    pub program_id: Pubkey,
+    /// The account authorized to be the [Escrow::owner] with this CPI.
+    /// If set to [anchor_lang::solana_program::system_program::ID],
+    /// all accounts are allowed to be the [Escrow::owner].
+    pub owner: Pubkey,
+
+    /// Limits on locks made through this entry.
+    pub limits: WhitelistEntryLimits,
+    /// Total amount locked through this entry while [WhitelistEntryLimits::max_locked_amount] was set.
+    pub locked_amount: u64,
+}
+
+impl LockerWhitelistEntry {
+    /// Number of bytes in a [LockerWhitelistEntry].
+    pub const LEN: usize = 1 + PUBKEY_BYTES * 3 + WhitelistEntryLimits::LEN + 8;
+
+    /// Whether [LockerWhitelistEntry::locked_amount] is tracked, which requires the entry to be writable.
+    pub fn tracks_locked_amount(&self) -> bool {
+        self.limits.max_locked_amount != 0
+    }
+
+    /// Checks that the entry has not expired at `now`.
+    pub fn check_not_expired(&self, now: i64) -> Result<()> {
+        invariant!(
+            self.limits.expires_at == 0 || now < self.limits.expires_at,
+            WhitelistEntryExpired
+        );
+        Ok(())
+    }
+
+    /// Stake duration bounds of locks made through this entry.
+    ///
+    /// Non-zero [WhitelistEntryLimits] durations replace those of the [LockerParams],
+    /// but may never exceed [LockerParams::max_stake_duration].
+    pub fn stake_duration_bounds(&self, params: &LockerParams) -> (u64, u64) {
+        let min_stake_duration = if self.limits.min_stake_duration == 0 {
+            params.min_stake_duration
+        } else {
+            self.limits.min_stake_duration
+        };
+        let max_stake_duration = if self.limits.max_stake_duration == 0 {
+            params.max_stake_duration
+        } else {
+            self.limits
+                .max_stake_duration
+                .min(params.max_stake_duration)
+        };
+        (min_stake_duration, max_stake_duration)
+    }
+
+    /// Checks that a lock for `duration` seconds is within the [LockerWhitelistEntry::stake_duration_bounds].
+    pub fn check_stake_duration(&self, params: &LockerParams, duration: i64) -> Result<()> {
+        let duration = unwrap_int!(duration.to_u64());
+        let (min_stake_duration, max_stake_duration) = self.stake_duration_bounds(params);
+        invariant!(duration >= min_stake_duration, LockupDurationTooShort);
+        invariant!(duration <= max_stake_duration, LockupDurationTooLong);
+        Ok(())
+    }
+
+    /// Checks a lock of `amount` tokens against the [WhitelistEntryLimits],
+    /// then adds `amount` to [LockerWhitelistEntry::locked_amount] if it is tracked.
+    ///
+    /// The lock duration is checked separately against [LockerWhitelistEntry::stake_duration_bounds].
+    pub fn record_lock(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.check_not_expired(now)?;
+        if self.tracks_locked_amount() {
+            let locked_amount = unwrap_int!(self.locked_amount.checked_add(amount));
+            invariant!(
+                locked_amount <= self.limits.max_locked_amount,
+                WhitelistEntryAmountExceeded
+            );
+            self.locked_amount = locked_amount;
+        }
+        Ok(())
+    }
+
+    /// Removes `amount` tokens that are no longer locked from [LockerWhitelistEntry::locked_amount].
+    pub fn release(&mut self, amount: u64) {
+        self.locked_amount = self.locked_amount.saturating_sub(amount);
+    }
+}
+
+/// Limits on the locks a whitelisted program may make. Zero values are unlimited.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WhitelistEntryLimits {
+    /// Maximum total amount that may be locked through the entry.
+    pub max_locked_amount: u64,
+    /// Minimum lock duration. Replaces [LockerParams::min_stake_duration] if non-zero.
+    pub min_stake_duration: u64,
+    /// Maximum lock duration. Replaces [LockerParams::max_stake_duration] if non-zero,
+    /// up to [LockerParams::max_stake_duration].
+    pub max_stake_duration: u64,
+    /// When the entry stops allowing locks.
+    pub expires_at: i64,
+}
+
+impl WhitelistEntryLimits {
+    /// Number of bytes in a [WhitelistEntryLimits].
+    pub const LEN: usize = 8 + 8 + 8 + 8;
+
+    /// Checks that the duration limits are consistent.
+    pub fn validate(&self) -> Result<()> {
+        invariant!(
+            self.max_stake_duration == 0 || self.min_stake_duration <= self.max_stake_duration,
+            InvalidWhitelistEntryLimits
+        );
+        Ok(())
+    }
+}
+
+/// Locks tokens on behalf of a user.
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct Escrow {
+    /// The [Locker] that this [Escrow] is part of.
+    pub locker: Pubkey,
+    /// The key of the account that is authorized to stake into/withdraw from this [Escrow].
+    pub owner: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+
+    /// The token account holding the escrow tokens.
+    pub tokens: Pubkey,
+    /// Amount of tokens staked.
+    pub amount: u64,
+    /// When the [Escrow::owner] started their escrow.
+    pub escrow_started_at: i64,
+    /// When the escrow unlocks; i.e. the [Escrow::owner] is scheduled to be allowed to withdraw their tokens.
+    pub escrow_ends_at: i64,
+
+    /// Account that is authorized to vote on behalf of this [Escrow].
+    /// Defaults to the [Escrow::owner]. For NFT escrows, the holder of the position token
+    /// votes unless it has set a delegate.
+    pub vote_delegate: Pubkey,
+
+    /// Index of this lock position for the [Escrow::owner].
+    /// Zero is the owner's primary [Escrow]; positions created via
+    /// [crate::locked_voter::new_escrow_position] start at one.
+    pub position_id: u64,
+
+    /// Mint of the 1-of-1 token representing this [Escrow], if it is a transferable position.
+    /// The holder of the token is the authority of the [Escrow], and [Escrow::owner] is set to this mint.
+    /// Defaults to [Pubkey::default] for regular escrows.
+    pub position_mint: Pubkey,
+
+    /// Whether the [Escrow] is in max-lock mode.
+    /// While enabled, the voting power does not decay and the [Escrow] cannot be exited.
+    pub max_lock: bool,
+
+    /// Whether the voting power of this [Escrow] is included in the [LockerCheckpoints].
+    /// Escrows created before the [LockerCheckpoints] existed are added by
+    /// [crate::locked_voter::checkpoint_escrow] or on their next lock.
+    pub checkpointed: bool,
+
+    /// Number of [LockCheckpoint]s ever recorded for this [Escrow].
+    pub lock_checkpoint_count: u64,
+    /// Ring buffer of the most recent [LockCheckpoint]s, indexed by [Escrow::lock_checkpoint_count].
+    pub lock_checkpoints: [LockCheckpoint; 8],
+
+    /// [Locker::penalty_reward_per_token] when the penalty rewards of this [Escrow] were last settled.
+    pub penalty_reward_per_token_paid: u128,
+    /// Settled early exit penalty rewards not yet claimed.
+    pub penalty_rewards_owed: u64,
+
+    /// Share of the voting power delegated through the [VoteDelegation] of this [Escrow],
+    /// in basis points. The rest is voted by the [Escrow::vote_delegate].
+    pub delegated_bps: u16,
+
+    /// Terms of the [Escrow::vote_delegate]. Outside of them, only the owner may vote.
+    pub vote_delegate_terms: DelegationTerms,
+
+    /// When the tokens of a vesting [Escrow] start to unlock.
+    /// Tokens then unlock linearly until [Escrow::escrow_ends_at].
+    pub vesting_cliff_at: i64,
+    /// Amount of tokens locked into the vesting schedule, or zero if the [Escrow] is not vesting.
+    pub vesting_amount: u64,
+
+    /// When the [Escrow] was first locked into since it was last empty.
+    /// Zero if its deposits are not tracked yet; see [Escrow::tracks_deposits].
+    pub first_locked_at: i64,
+    /// Number of entries used in [Escrow::deposits].
+    pub deposit_count: u8,
+    /// The deposits making up the [Escrow::amount], in no particular order.
+    pub deposits: [EscrowDeposit; 8],
+
+    /// Total amount of tokens redeemed from this [Escrow] via a [LockerRedeemer].
+    pub redeemed_amount: u64,
+
+    /// For NFT escrows, the holder of the position token that last set the
+    /// [Escrow::vote_delegate] or the [VoteDelegation]. Delegations only apply
+    /// while that holder still holds the position token.
+    pub delegated_by: Pubkey,
+    /// When [Escrow::delegated_bps] or the [VoteDelegation] last changed.
+    /// The [Escrow] may not vote on proposals activated before then.
+    pub delegation_updated_at: i64,
+
+    /// The [LockerWhitelistEntry] that counts [Escrow::whitelist_locked_amount]
+    /// in its [LockerWhitelistEntry::locked_amount].
+    pub whitelist_entry: Pubkey,
+    /// Amount of tokens locked into this [Escrow] through [Escrow::whitelist_entry]
+    /// and not yet released from it.
+    pub whitelist_locked_amount: u64,
+}
+
+impl Escrow {
+    /// Number of [LockCheckpoint]s kept in [Escrow::lock_checkpoints].
+    pub const MAX_LOCK_CHECKPOINTS: usize = 8;
+    /// Number of [EscrowDeposit]s kept in [Escrow::deposits].
+    pub const MAX_DEPOSITS: usize = 8;
+
+    /// Number of bytes in an [Escrow].
+    pub const LEN: usize = PUBKEY_BYTES * 2
+        + 1
+        + PUBKEY_BYTES
+        + 8
+        + 8
+        + 8
+        + PUBKEY_BYTES
+        + 8
+        + PUBKEY_BYTES
+        + 1
+        + 1
+        + 8
+        + LockCheckpoint::LEN * Escrow::MAX_LOCK_CHECKPOINTS
+        + 16
+        + 8
+        + 2
+        + DelegationTerms::LEN
+        + 8
+        + 8
+        + 8
+        + 1
+        + EscrowDeposit::LEN * Escrow::MAX_DEPOSITS
+        + 8
+        + PUBKEY_BYTES
+        + 8
+        + PUBKEY_BYTES
+        + 8;
+
+    /// Whether this [Escrow] is a transferable position backed by [Escrow::position_mint].
+    pub fn is_nft(&self) -> bool {
+        self.position_mint != Pubkey::default()
+    }
+
+    /// Checks that `authority` may lock into, withdraw from, or delegate this [Escrow].
+    ///
+    /// For regular escrows, `authority` must be the [Escrow::owner].
+    /// For NFT escrows, `position_tokens` must be a token account of `authority` holding the position token.
+    pub fn assert_authority(
+        &self,
+        authority: Pubkey,
+        position_tokens: Option<&TokenAccount>,
+    ) -> Result<()> {
+        if !self.is_nft() {
+            assert_keys_eq!(self.owner, authority);
+            return Ok(());
+        }
+        assert_keys_eq!(
+            self.authority(position_tokens)?,
+            authority,
+            NotPositionHolder
+        );
+        Ok(())
+    }
+
+    /// Gets the account with authority over this [Escrow].
+    ///
+    /// For regular escrows, this is the [Escrow::owner].
+    /// For NFT escrows, `position_tokens` must be the token account holding the position token,
+    /// and its owner is returned.
+    pub fn authority(&self, position_tokens: Option<&TokenAccount>) -> Result<Pubkey> {
+        if !self.is_nft() {
+            return Ok(self.owner);
+        }
+        let position_tokens = unwrap_opt!(position_tokens, MustProvidePositionTokens);
+        assert_keys_eq!(position_tokens.mint, self.position_mint);
+        invariant!(position_tokens.amount == 1, NotPositionHolder);
+        Ok(position_tokens.owner)
+    }
+
+    /// Whether the delegations of this [Escrow] were set by `authority`.
+    /// Delegations of an NFT [Escrow] lapse once its position token changes hands.
+    pub fn delegation_applies(&self, authority: Pubkey) -> bool {
+        !self.is_nft() || self.delegated_by == authority
+    }
+
+    /// Number of bytes of [Escrow::position_id] used in the [Escrow] PDA seeds.
+    /// The primary [Escrow] does not have a position seed.
+    pub fn position_seed_len(&self) -> usize {
+        if self.position_id == 0 {
+            0
+        } else {
+            8
+        }
+    }
+}
+
+impl Escrow {
+    /// Gets the amount of voting power the [Escrow] will have at the given time.
+    pub fn voting_power_at_time(&self, locker: &LockerParams, timestamp: i64) -> Option<u64> {
+        locker.calculate_voter_power(self, timestamp)
+    }
+
+    /// Gets the amount of voting power the [Escrow] currently has.
+    pub fn voting_power(&self, locker: &LockerParams) -> Result<u64> {
+        Ok(unwrap_int!(self.voting_power_at_time(
+            locker,
+            Clock::get()?.unix_timestamp
+        )))
+    }
+
+    /// Records the current lock state of the [Escrow] as of `now`.
+    ///
+    /// Tokens added since the last checkpoint are recorded as deposited at `now`,
+    /// and tokens removed are taken out of [Escrow::deposits].
+    pub fn record_lock_checkpoint(&mut self, now: i64) {
+        self.sync_deposits(now);
+        let index = (self.lock_checkpoint_count % Escrow::MAX_LOCK_CHECKPOINTS as u64) as usize;
+        self.lock_checkpoints[index] = LockCheckpoint {
+            timestamp: now,
+            ..self.current_lock_checkpoint()
+        };
+        self.lock_checkpoint_count = self.lock_checkpoint_count.saturating_add(1);
+    }
+
+    /// Starts the lock history of an [Escrow] locked before lock checkpoints existed.
+    /// Such an escrow has been in its current lock state since [Escrow::escrow_started_at].
+    /// Must be called before the lock state changes.
+    pub fn track_lock_checkpoints(&mut self) {
+        if self.lock_checkpoint_count == 0 && self.escrow_started_at != 0 {
+            let escrow_started_at = self.escrow_started_at;
+            self.record_lock_checkpoint(escrow_started_at);
+        }
+    }
+
+    /// The current lock state of the [Escrow], as of [Escrow::escrow_started_at].
+    fn current_lock_checkpoint(&self) -> LockCheckpoint {
+        LockCheckpoint {
+            timestamp: self.escrow_started_at,
+            amount: self.amount,
+            escrow_started_at: self.escrow_started_at,
+            escrow_ends_at: self.escrow_ends_at,
+            max_lock: self.max_lock,
+            checkpointed: self.checkpointed,
+            vesting_cliff_at: self.vesting_cliff_at,
+            vesting_amount: self.vesting_amount,
+        }
+    }
+
+    /// Gets the lock states the [Escrow] may have had as of `timestamp`.
+    ///
+    /// This is empty if the [Escrow] did not exist yet. If `timestamp` is older than the kept
+    /// [Escrow::lock_checkpoints], this is the oldest kept and the current lock state,
+    /// as if they had already been in effect at `timestamp`.
+    /// Escrows locked before lock checkpoints existed use their current lock state.
+    pub fn lock_checkpoints_at(&self, timestamp: i64) -> Vec<LockCheckpoint> {
+        let count = self.lock_checkpoint_count;
+        if count == 0 {
+            let checkpoint = self.current_lock_checkpoint();
+            if checkpoint.escrow_started_at != 0 && checkpoint.timestamp <= timestamp {
+                return vec![checkpoint];
+            }
+            return vec![];
+        }
+        let kept = count.min(Escrow::MAX_LOCK_CHECKPOINTS as u64);
+        let oldest_kept =
+            self.lock_checkpoints[((count - kept) % Escrow::MAX_LOCK_CHECKPOINTS as u64) as usize];
+        // checkpoints are recorded in time order, so the last match is the latest.
+        let checkpoint = ((count - kept)..count)
+            .map(|i| self.lock_checkpoints[(i % Escrow::MAX_LOCK_CHECKPOINTS as u64) as usize])
+            .filter(|checkpoint| checkpoint.timestamp <= timestamp)
+            .last();
+        match checkpoint {
+            Some(checkpoint) => vec![checkpoint],
+            None if count == kept => vec![],
+            None => [oldest_kept, self.current_lock_checkpoint()]
+                .iter()
+                .map(|checkpoint| LockCheckpoint {
+                    timestamp,
+                    escrow_started_at: checkpoint.escrow_started_at.min(timestamp),
+                    ..*checkpoint
+                })
+                .collect(),
+        }
+    }
+
+    /// This [Escrow] in the lock state of `checkpoint`.
+    fn with_lock_checkpoint(&self, checkpoint: &LockCheckpoint) -> Escrow {
+        Escrow {
+            amount: checkpoint.amount,
+            escrow_started_at: checkpoint.escrow_started_at,
+            escrow_ends_at: checkpoint.escrow_ends_at,
+            max_lock: checkpoint.max_lock,
+            checkpointed: checkpoint.checkpointed,
+            vesting_cliff_at: checkpoint.vesting_cliff_at,
+            vesting_amount: checkpoint.vesting_amount,
+            ..*self
+        }
+    }
+
+    /// Gets the smallest `voting_power` of the [Escrow::lock_checkpoints_at] `timestamp`.
+    fn min_voting_power_as_of(
+        &self,
+        timestamp: i64,
+        voting_power: impl Fn(&Escrow) -> Option<u64>,
+    ) -> Result<u64> {
+        let mut min_voting_power: Option<u64> = None;
+        for checkpoint in self.lock_checkpoints_at(timestamp) {
+            let power = unwrap_int!(voting_power(&self.with_lock_checkpoint(&checkpoint)));
+            min_voting_power = Some(min_voting_power.map_or(power, |min| min.min(power)));
+        }
+        Ok(min_voting_power.unwrap_or(0))
+    }
+
+    /// Gets the voting power the [Escrow] had at `timestamp`, based on its lock state at that time.
+    ///
+    /// If that lock state is no longer kept, this is the smaller of the voting powers
+    /// of the oldest kept and the current lock state.
+    pub fn voting_power_as_of(&self, locker: &LockerParams, timestamp: i64) -> Result<u64> {
+        self.min_voting_power_as_of(timestamp, |escrow| {
+            escrow.voting_power_at_time(locker, timestamp)
+        })
+    }
+
+    /// Gets the voting power the [LockerCheckpoints] counted for the [Escrow] at `timestamp`,
+    /// based on its lock state at that time. See [LockerCheckpoints::tracked_voting_power_at_time].
+    pub fn tracked_voting_power_as_of(&self, locker: &LockerParams, timestamp: i64) -> Result<u64> {
+        self.min_voting_power_as_of(timestamp, |escrow| {
+            locker
+                .escrow_checkpoint(escrow, timestamp)?
+                .voting_power(locker)
+        })
+    }
+
+    /// Gets the account that may vote with this [Escrow] on `governor` at `now`:
+    /// the [Escrow::vote_delegate] within its [Escrow::vote_delegate_terms], otherwise `authority`.
+    ///
+    /// `authority` is the [Escrow::authority]; for NFT escrows, the holder of the position token.
+    pub fn active_vote_delegate(&self, authority: Pubkey, governor: Pubkey, now: i64) -> Pubkey {
+        if self.vote_delegate_terms.covers(governor, now) && self.delegation_applies(authority) {
+            self.vote_delegate
+        } else {
+            authority
+        }
+    }
+
+    /// Whether the delegated shares of this [Escrow] changed at or after `timestamp`.
+    ///
+    /// Votes cast before a change keep their weight, so an [Escrow] whose shares changed
+    /// after a proposal was activated may not vote on it, or some power would be counted twice.
+    pub fn delegation_changed_since(&self, timestamp: i64) -> bool {
+        self.delegation_updated_at >= timestamp
+    }
+
+    /// Share of the voting power voted by the [Escrow::vote_delegate], in basis points.
+    pub fn undelegated_bps(&self) -> Option<u16> {
+        (crate::locker::BPS_PER_UNIT as u16).checked_sub(self.delegated_bps)
+    }
+
+    /// Whether this [Escrow] was locked with [crate::locked_voter::lock_vesting].
+    pub fn is_vesting(&self) -> bool {
+        self.vesting_amount > 0
+    }
+
+    /// Amount of the [Escrow::vesting_amount] unlocked at `now`.
+    pub fn vested_amount_at(&self, now: i64) -> Option<u64> {
+        if now < self.vesting_cliff_at {
+            return Some(0);
+        }
+        if now >= self.escrow_ends_at {
+            return Some(self.vesting_amount);
+        }
+        let elapsed = now.checked_sub(self.vesting_cliff_at)?.to_u64()?;
+        let duration = self
+            .escrow_ends_at
+            .checked_sub(self.vesting_cliff_at)?
+            .to_u64()?;
+        (self.vesting_amount as u128)
+            .checked_mul(elapsed.into())?
+            .checked_div(duration.into())?
+            .to_u64()
+    }
+
+    /// Amount of tokens still locked at `now`. Only these tokens have voting power.
+    ///
+    /// For escrows which are not vesting, this is the [Escrow::amount].
+    pub fn locked_amount_at(&self, now: i64) -> Option<u64> {
+        if !self.is_vesting() {
+            return Some(self.amount);
+        }
+        let unvested = self
+            .vesting_amount
+            .checked_sub(self.vested_amount_at(now)?)?;
+        Some(self.amount.min(unvested))
+    }
+
+    /// Gets the early exit penalty rewards claimable by the [Escrow].
+    pub fn pending_penalty_rewards(&self, locker: &Locker) -> Option<u64> {
+        let accrued = (self.amount as u128)
+            .checked_mul(
+                locker
+                    .penalty_reward_per_token
+                    .checked_sub(self.penalty_reward_per_token_paid)?,
+            )?
+            .checked_div(crate::locker::PENALTY_REWARD_PRECISION)?
+            .to_u64()?;
+        self.penalty_rewards_owed.checked_add(accrued)
+    }
+
+    /// Moves the early exit penalty rewards accrued so far into [Escrow::penalty_rewards_owed].
+    /// Must be called before [Escrow::amount] changes.
+    pub fn settle_penalty_rewards(&mut self, locker: &Locker) -> Result<()> {
+        self.penalty_rewards_owed = unwrap_int!(self.pending_penalty_rewards(locker));
+        self.penalty_reward_per_token_paid = locker.penalty_reward_per_token;
+        Ok(())
+    }
+
+    /// Update the escrow and its locker to account for a lock event.
+    pub fn record_lock_event(
+        &mut self,
+        locker: &mut Locker,
+        lock_amount: u64,
+        next_escrow_started_at: i64,
+        next_escrow_ends_at: i64,
+    ) -> Result<()> {
+        self.settle_penalty_rewards(locker)?;
+        self.track_deposits();
+        self.track_lock_checkpoints();
+        self.amount = unwrap_int!(self.amount.checked_add(lock_amount));
+        self.escrow_started_at = next_escrow_started_at;
+        self.escrow_ends_at = next_escrow_ends_at;
+
+        locker.locked_supply = unwrap_int!(locker.locked_supply.checked_add(lock_amount));
+        // penalties paid while nothing was locked go to the new lockers.
+        unwrap_int!(locker.distribute_penalty_rewards(0));
+
+        Ok(())
+    }
+
+    /// Update the escrow and its locker to account for tokens leaving the escrow.
+    /// The lock times are left unchanged, so the remaining lock never gets shorter.
+    pub fn record_unlock_event(&mut self, locker: &mut Locker, unlock_amount: u64) -> Result<()> {
+        self.settle_penalty_rewards(locker)?;
+        self.track_deposits();
+        self.track_lock_checkpoints();
+        self.amount = unwrap_int!(self.amount.checked_sub(unlock_amount));
+
+        locker.locked_supply = unwrap_int!(locker.locked_supply.checked_sub(unlock_amount));
+
+        Ok(())
+    }
+
+    /// Records `amount` tokens locked through the [LockerWhitelistEntry] `whitelist_entry`,
+    /// so that they can be released from it once they leave the [Escrow].
+    ///
+    /// An [Escrow] counts towards one [LockerWhitelistEntry] at a time.
+    pub fn record_whitelist_lock(&mut self, whitelist_entry: Pubkey, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        if self.whitelist_locked_amount == 0 {
+            self.whitelist_entry = whitelist_entry;
+        }
+        assert_keys_eq!(
+            self.whitelist_entry,
+            whitelist_entry,
+            WhitelistEntryMismatch
+        );
+        self.whitelist_locked_amount =
+            unwrap_int!(self.whitelist_locked_amount.checked_add(amount));
+        Ok(())
+    }
+
+    /// Removes the part of [Escrow::whitelist_locked_amount] above `retained_amount`,
+    /// returning the amount to release from the [Escrow::whitelist_entry].
+    pub fn release_whitelist_lock(&mut self, retained_amount: u64) -> u64 {
+        let released_amount = self.whitelist_locked_amount.saturating_sub(retained_amount);
+        self.whitelist_locked_amount -= released_amount;
+        if self.whitelist_locked_amount == 0 {
+            self.whitelist_entry = Pubkey::default();
+        }
+        released_amount
+    }
+}
+
+impl Escrow {
+    /// Whether [Escrow::deposits] covers the [Escrow::amount].
+    ///
+    /// Escrows locked before the deposit history existed are tracked from their next lock or unlock.
+    pub fn tracks_deposits(&self) -> bool {
+        self.first_locked_at != 0
+    }
+
+    /// Starts tracking the deposits of an [Escrow] locked before the deposit history existed,
+    /// attributing its tokens to [Escrow::escrow_started_at].
+    /// Must be called before [Escrow::amount] changes.
+    pub fn track_deposits(&mut self) {
+        if self.tracks_deposits() || self.amount == 0 {
+            return;
+        }
+        self.first_locked_at = self.escrow_started_at;
+        self.deposits[0] = EscrowDeposit {
+            timestamp: self.escrow_started_at,
+            amount: self.amount,
+        };
+        self.deposit_count = 1;
+    }
+
+    /// When the [Escrow] was first locked into since it was last empty.
+    pub fn first_locked_at(&self) -> i64 {
+        if self.tracks_deposits() {
+            self.first_locked_at
+        } else {
+            self.escrow_started_at
+        }
+    }
+
+    /// Total amount of the [Escrow::deposits].
+    pub fn deposited_amount(&self) -> u64 {
+        self.deposits[..self.deposit_count as usize]
+            .iter()
+            .fold(0, |total, deposit| total.saturating_add(deposit.amount))
+    }
+
+    /// Amount of tokens of the [Escrow] deposited before `timestamp`.
+    pub fn amount_deposited_before(&self, timestamp: i64) -> u64 {
+        if !self.tracks_deposits() {
+            return if self.escrow_started_at < timestamp {
+                self.amount
+            } else {
+                0
+            };
+        }
+        self.deposits[..self.deposit_count as usize]
+            .iter()
+            .filter(|deposit| deposit.timestamp < timestamp)
+            .fold(0, |total: u64, deposit| {
+                total.saturating_add(deposit.amount)
+            })
+            .min(self.amount)
+    }
+
+    /// Amount of tokens of the [Escrow] that may be redeemed with the given `eligibility`
+    /// and `cutoff_date`.
+    pub fn redeemable_amount(&self, eligibility: RedemptionEligibility, cutoff_date: i64) -> u64 {
+        let locked_before_cutoff = match eligibility {
+            RedemptionEligibility::LastLock => self.escrow_started_at < cutoff_date,
+            RedemptionEligibility::FirstLock => self.first_locked_at() < cutoff_date,
+            RedemptionEligibility::ProRata => return self.amount_deposited_before(cutoff_date),
+        };
+        if locked_before_cutoff {
+            self.amount
+        } else {
+            0
+        }
+    }
+
+    /// Adds a deposit to the [Escrow::deposits].
+    ///
+    /// Once the history is full, the two deposits closest in time are merged at the later
+    /// timestamp. This may only make tokens look newer than they are, and repeated small deposits
+    /// merge into each other instead of moving older deposits past a cutoff.
+    pub fn add_deposit(&mut self, deposit: EscrowDeposit) {
+        if deposit.amount == 0 {
+            return;
+        }
+        if self.first_locked_at == 0 || deposit.timestamp < self.first_locked_at {
+            self.first_locked_at = deposit.timestamp;
+        }
+        let count = self.deposit_count as usize;
+        if count < Escrow::MAX_DEPOSITS {
+            self.deposits[count] = deposit;
+            self.deposit_count += 1;
+            return;
+        }
+        let mut deposits = self.deposits.to_vec();
+        deposits.push(deposit);
+        deposits.sort_by_key(|deposit| deposit.timestamp);
+        // on ties, the newest deposits are merged.
+        let merged_index = (1..deposits.len())
+            .rev()
+            .min_by_key(|&i| {
+                deposits[i]
+                    .timestamp
+                    .saturating_sub(deposits[i - 1].timestamp)
+            })
+            .unwrap_or_default();
+        let older = deposits.remove(merged_index - 1);
+        let merged = &mut deposits[merged_index - 1];
+        merged.amount = merged.amount.saturating_add(older.amount);
+        self.deposits.copy_from_slice(&deposits);
+    }
+
+    /// Removes `amount` tokens from the [Escrow::deposits], newest first,
+    /// and returns the deposits they came from.
+    pub fn take_deposits(&mut self, amount: u64) -> Vec<EscrowDeposit> {
+        self.take_deposits_before(i64::MAX, amount)
+    }
+
+    /// Removes up to `amount` tokens deposited before `timestamp` from the [Escrow::deposits],
+    /// newest first, and returns the deposits they came from.
+    pub fn take_deposits_before(&mut self, timestamp: i64, amount: u64) -> Vec<EscrowDeposit> {
+        let mut taken = vec![];
+        let mut remaining = amount;
+        while remaining > 0 {
+            let index = match self.latest_deposit_index_before(timestamp) {
+                Some(index) => index,
+                None => break,
+            };
+            let deposit = &mut self.deposits[index];
+            let take = deposit.amount.min(remaining);
+            deposit.amount -= take;
+            remaining -= take;
+            taken.push(EscrowDeposit {
+                timestamp: deposit.timestamp,
+                amount: take,
+            });
+            if deposit.amount == 0 {
+                let last = self.deposit_count as usize - 1;
+                self.deposits.swap(index, last);
+                self.deposits[last] = EscrowDeposit::default();
+                self.deposit_count -= 1;
+            }
+        }
+        if self.deposit_count == 0 {
+            self.first_locked_at = 0;
+        }
+        taken
+    }
+
+    /// Brings the [Escrow::deposits] in line with the [Escrow::amount].
+    fn sync_deposits(&mut self, now: i64) {
+        self.track_deposits();
+        let deposited = self.deposited_amount();
+        match self.amount.cmp(&deposited) {
+            std::cmp::Ordering::Greater => self.add_deposit(EscrowDeposit {
+                timestamp: now,
+                amount: self.amount - deposited,
+            }),
+            std::cmp::Ordering::Less => {
+                self.take_deposits(deposited - self.amount);
+            }
+            std::cmp::Ordering::Equal => {}
+        }
+    }
+
+    /// Index of the most recent of the [Escrow::deposits] made before `timestamp`.
+    fn latest_deposit_index_before(&self, timestamp: i64) -> Option<usize> {
+        let mut latest: Option<usize> = None;
+        for index in 0..self.deposit_count as usize {
+            let deposit = &self.deposits[index];
+            if deposit.timestamp < timestamp
+                && latest.map_or(true, |latest| {
+                    deposit.timestamp >= self.deposits[latest].timestamp
+                })
+            {
+                latest = Some(index);
+            }
+        }
+        latest
+    }
+
+    /// Update the escrow and its locker to account for `amount` tokens redeemed
+    /// by a [LockerRedeemer] with the given `eligibility` and `cutoff_date`.
+    ///
+    /// Tokens redeemed pro-rata come out of the deposits made before the cutoff.
+    pub fn record_redemption(
+        &mut self,
+        locker: &mut Locker,
+        eligibility: RedemptionEligibility,
+        cutoff_date: i64,
+        amount: u64,
+    ) -> Result<()> {
+        self.record_unlock_event(locker, amount)?;
+        if eligibility == RedemptionEligibility::ProRata {
+            self.take_deposits_before(cutoff_date, amount);
+        }
+        if self.amount == 0 {
+            self.escrow_ends_at = 0;
+            self.escrow_started_at = 0;
+            self.max_lock = false;
+        }
+        self.redeemed_amount = unwrap_int!(self.redeemed_amount.checked_add(amount));
+        Ok(())
+    }
+}
+
+/// Tokens added to an [Escrow] at a point in time.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EscrowDeposit {
+    /// When the tokens were deposited.
+    pub timestamp: i64,
+    /// Amount of tokens deposited that are still in the [Escrow].
+    pub amount: u64,
+}
+
+impl EscrowDeposit {
+    /// Number of bytes in an [EscrowDeposit].
+    pub const LEN: usize = 8 + 8;
+}
+
+/// The lock state of an [Escrow] from [LockCheckpoint::timestamp] until the next [LockCheckpoint].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LockCheckpoint {
+    /// When the lock state was recorded.
+    pub timestamp: i64,
+    /// [Escrow::amount] at the time.
+    pub amount: u64,
+    /// [Escrow::escrow_started_at] at the time.
+    pub escrow_started_at: i64,
+    /// [Escrow::escrow_ends_at] at the time.
+    pub escrow_ends_at: i64,
+    /// [Escrow::max_lock] at the time.
+    pub max_lock: bool,
+    /// [Escrow::checkpointed] at the time, i.e. whether the lock was counted by the [LockerCheckpoints].
+    pub checkpointed: bool,
+    /// [Escrow::vesting_cliff_at] at the time.
+    pub vesting_cliff_at: i64,
+    /// [Escrow::vesting_amount] at the time.
+    pub vesting_amount: u64,
+}
+
+impl LockCheckpoint {
+    /// Number of bytes in a [LockCheckpoint].
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 1 + 1 + 8 + 8;
+}
+
+/// Curve-style checkpoints of the total voting power of a [Locker].
+///
+/// The locked amount times the seconds until expiry, summed over all locks, is tracked as a line
+/// (a [Point]) which changes slope whenever a lock expires. It does not depend on the [LockerParams],
+/// which are applied when the total voting power is read.
+#[account]
+#[derive(Debug, Default)]
+pub struct LockerCheckpoints {
+    /// The [Locker] being checkpointed.
+    pub locker: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Number of [Point]s ever written.
+    pub epoch: u64,
+    /// Ring buffer of the most recent [Point]s, indexed by [LockerCheckpoints::epoch].
+    pub points: Vec<Point>,
+    /// Pending changes in slope, sorted by [SlopeChange::time].
+    pub slope_changes: Vec<SlopeChange>,
+}
+
+impl LockerCheckpoints {
+    /// Maximum number of [Point]s kept in the history.
+    pub const MAX_POINTS: usize = 96;
+    /// Maximum number of distinct pending [SlopeChange]s.
+    ///
+    /// Locks end on week boundaries, so this covers one [SlopeChange] per week of the
+    /// longest [LockerParams::max_stake_duration] allowed by [LockerParams::validate_with_bounds].
+    pub const MAX_SLOPE_CHANGES: usize = 210;
+
+    /// Number of bytes in a [LockerCheckpoints].
+    pub const LEN: usize = PUBKEY_BYTES
+        + 1
+        + 8
+        + 4
+        + Point::LEN * Self::MAX_POINTS
+        + 4
+        + SlopeChange::LEN * Self::MAX_SLOPE_CHANGES;
+}
+
+/// The locks of a [Locker] at [Point::ts].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Point {
+    /// Sum of the locked amount times the seconds until expiry of all decaying locks.
+    pub bias: u128,
+    /// Decrease in [Point::bias] per second, i.e. the amount in decaying locks.
+    pub slope: u128,
+    /// Timestamp of the [Point].
+    pub ts: i64,
+    /// Amount locked in max-lock mode, which does not decay.
+    pub max_locked_amount: u64,
+}
+
+impl Point {
+    /// Number of bytes in a [Point].
+    pub const LEN: usize = 16 + 16 + 8 + 8;
+}
+
+/// A decrease of the total slope when locks expire or vesting tokens unlock at [SlopeChange::time].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SlopeChange {
+    /// When the locks expire.
+    pub time: i64,
+    /// Sum of the slopes of the expiring locks and of the unlocking vesting tokens.
+    pub slope_delta: u128,
+}
+
+impl SlopeChange {
+    /// Number of bytes in a [SlopeChange].
+    pub const LEN: usize = 8 + 16;
+}
+
+/// Distributes deposits of a reward token to the [Escrow]s of a [Locker], week by week.
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct RewardDistributor {
+    /// The [Locker] whose [Escrow]s are rewarded.
+    pub locker: Pubkey,
+    /// Mint of the reward token.
+    pub mint: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Token account holding the rewards. This is the ATA of the [RewardDistributor].
+    pub tokens: Pubkey,
+    /// Total amount of rewards ever deposited.
+    pub total_deposited: u64,
+    /// Total amount of rewards ever claimed.
+    pub total_claimed: u64,
+}
+
+impl RewardDistributor {
+    /// Number of bytes in a [RewardDistributor].
+    pub const LEN: usize = PUBKEY_BYTES + PUBKEY_BYTES + 1 + PUBKEY_BYTES + 8 + 8;
+}
+
+/// Rewards of a [RewardDistributor] for a single week.
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct RewardEpoch {
+    /// The [RewardDistributor].
+    pub distributor: Pubkey,
+    /// Index of the week since the Unix epoch.
+    pub epoch: u64,
+    /// Bump seed.
+    pub bump: u8,
+    /// Amount of rewards deposited for the week.
+    pub amount: u64,
+    /// Amount of rewards claimed so far.
+    pub claimed_amount: u64,
+    /// Total voting power of the [Locker] at the start of the week,
+    /// as tracked by its [LockerCheckpoints].
+    pub total_voting_power: u64,
+    /// Whether [RewardEpoch::total_voting_power] has been recorded, which happens
+    /// in [crate::locked_voter::snapshot_reward_epoch] or on the first claim.
+    pub total_voting_power_recorded: bool,
+}
+
+impl RewardEpoch {
+    /// Number of bytes in a [RewardEpoch].
+    pub const LEN: usize = PUBKEY_BYTES + 8 + 1 + 8 + 8 + 8 + 1;
+
+    /// When the week starts. Voting power is measured at this time.
+    pub fn start_ts(&self) -> Option<i64> {
+        crate::locker::week_start_ts(self.epoch)
+    }
+
+    /// When the week ends. Rewards may be deposited until then, and claimed afterwards.
+    pub fn end_ts(&self) -> Option<i64> {
+        crate::locker::week_end_ts(self.epoch)
+    }
+
+    /// Records the [RewardEpoch::total_voting_power] from `checkpoints`, unless already recorded.
+    ///
+    /// The [LockerCheckpoints] only keep a limited history, so this should be done
+    /// soon after the week starts. Otherwise the rewards are rolled over with
+    /// [crate::locked_voter::roll_over_reward_epoch].
+    pub fn record_total_voting_power(
+        &mut self,
+        checkpoints: &LockerCheckpoints,
+        params: &LockerParams,
+    ) -> Result<()> {
+        if self.total_voting_power_recorded {
+            return Ok(());
+        }
+        let start_ts = unwrap_int!(self.start_ts());
+        self.total_voting_power = unwrap_opt!(
+            checkpoints.tracked_voting_power_at_time(params, start_ts),
+            LockCheckpointUnavailable
+        );
+        self.total_voting_power_recorded = true;
+        Ok(())
+    }
+
+    /// Takes the rewards left in this [RewardEpoch] if none can be claimed, i.e. if the
+    /// [RewardEpoch::total_voting_power] is zero or can no longer be recorded from `checkpoints`.
+    ///
+    pub fn roll_over(
+        &mut self,
+        checkpoints: &LockerCheckpoints,
+        params: &LockerParams,
+    ) -> Result<u64> {
+        if !self.total_voting_power_recorded {
+            let start_ts = unwrap_int!(self.start_ts());
+            // nothing is claimable until the total is recorded, so it may be taken as zero.
+            self.total_voting_power = checkpoints
+                .tracked_voting_power_at_time(params, start_ts)
+                .unwrap_or(0);
+            self.total_voting_power_recorded = true;
+        }
+        invariant!(self.total_voting_power == 0, RewardEpochClaimable);
+        let amount = unwrap_int!(self.amount.checked_sub(self.claimed_amount));
+        self.amount = self.claimed_amount;
+        Ok(amount)
+    }
+
+    /// The rewards of an [Escrow] with `voting_power` at the start of the week,
+    /// as given by [Escrow::tracked_voting_power_as_of].
+    ///
+    /// These voting powers sum to at most the [RewardEpoch::total_voting_power],
+    /// and the share is capped at the rewards left, so no more than [RewardEpoch::amount] is paid.
+    pub fn share_of(&self, voting_power: u64) -> Option<u64> {
+        if self.total_voting_power == 0 {
+            return Some(0);
+        }
+        let share = (self.amount as u128)
+            .checked_mul(voting_power.into())?
+            .checked_div(self.total_voting_power.into())?
+            .to_u64()?;
+        Some(share.min(self.amount.checked_sub(self.claimed_amount)?))
+    }
+}
+
+/// Receipt of an [Escrow] claiming from a [RewardEpoch]. Its existence prevents claiming twice.
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct RewardClaim {
+    /// The [RewardEpoch] claimed from.
+    pub reward_epoch: Pubkey,
+    /// The [Escrow] that claimed.
+    pub escrow: Pubkey,
+    /// Amount of rewards claimed.
+    pub amount: u64,
+    /// When the rewards were claimed.
+    pub claimed_at: i64,
+}
+
+impl RewardClaim {
+    /// Number of bytes in a [RewardClaim].
+    pub const LEN: usize = PUBKEY_BYTES + PUBKEY_BYTES + 8 + 8;
+}
+
+/// Registry of the [Gauge]s of a [Locker].
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct GaugeRegistry {
+    /// The [Locker] whose voting power is allocated to the [Gauge]s.
+    pub locker: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Number of [Gauge]s ever created.
+    pub num_gauges: u32,
+}
+
+impl GaugeRegistry {
+    /// Number of bytes in a [GaugeRegistry].
+    pub const LEN: usize = PUBKEY_BYTES + 1 + 4;
+}
+
+/// A destination of emissions, e.g. a quarry, which [Escrow]s allocate voting power to.
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct Gauge {
+    /// The [GaugeRegistry].
+    pub registry: Pubkey,
+    /// The account receiving emissions according to the weight of this [Gauge].
+    pub target: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Index of the [Gauge] in the [GaugeRegistry].
+    pub index: u32,
+    /// Disabled [Gauge]s do not accept committed votes.
+    pub is_disabled: bool,
+}
+
+impl Gauge {
+    /// Number of bytes in a [Gauge].
+    pub const LEN: usize = PUBKEY_BYTES + PUBKEY_BYTES + 1 + 4 + 1;
+}
+
+/// Weight allocations of an [Escrow] across [Gauge]s.
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct GaugeVoter {
+    /// The [GaugeRegistry].
+    pub registry: Pubkey,
+    /// The [Escrow] allocating voting power.
+    pub escrow: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Sum of the [GaugeVote::weight]s of the [Escrow].
+    pub total_weight: u32,
+}
+
+impl GaugeVoter {
+    /// Number of bytes in a [GaugeVoter].
+    pub const LEN: usize = PUBKEY_BYTES + PUBKEY_BYTES + 1 + 4;
+}
+
+/// The weight an [Escrow] allocates to a [Gauge].
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct GaugeVote {
+    /// The [GaugeVoter].
+    pub gauge_voter: Pubkey,
+    /// The [Gauge].
+    pub gauge: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Share of the voting power of the [Escrow] allocated to the [Gauge],
+    /// relative to [GaugeVoter::total_weight].
+    pub weight: u32,
+}
+
+impl GaugeVote {
+    /// Number of bytes in a [GaugeVote].
+    pub const LEN: usize = PUBKEY_BYTES + PUBKEY_BYTES + 1 + 4;
+}
+
+/// The committed weight of a [Gauge] for a week. Emissions programs read [EpochGauge::total_power].
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct EpochGauge {
+    /// The [Gauge].
+    pub gauge: Pubkey,
+    /// Index of the week since the Unix epoch.
+    pub epoch: u64,
+    /// Bump seed.
+    pub bump: u8,
+    /// Total voting power committed to the [Gauge] for the week.
+    pub total_power: u64,
+}
+
+impl EpochGauge {
+    /// Number of bytes in an [EpochGauge].
+    pub const LEN: usize = PUBKEY_BYTES + 8 + 1 + 8;
+}
+
+/// The voting power of a [GaugeVoter] for a week.
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct EpochGaugeVoter {
+    /// The [GaugeVoter].
+    pub gauge_voter: Pubkey,
+    /// Index of the week since the Unix epoch.
+    pub epoch: u64,
     /// Bump seed.
     pub bump: u8,
-    /// [Locker] this whitelist entry belongs to.
-    pub locker: Pubkey,
-    /// Key of the program_id allowed to call the `lock` CPI.
-    pub program_id: Pubkey,
-    /// The account authorized to be the [Escrow::owner] with this CPI.
-    /// If set to [anchor_lang::solana_program::system_program::ID],
-    /// all accounts are allowed to be the [Escrow::owner].
-    pub owner: Pubkey,
+    /// Voting power of the [Escrow] at the start of the week.
+    pub voting_power: u64,
+    /// Voting power committed to [Gauge]s so far. Never exceeds [EpochGaugeVoter::voting_power].
+    pub allocated_power: u64,
 }
 
-impl LockerWhitelistEntry {
-    /// Number of bytes in a [LockerWhitelistEntry].
-    pub const LEN: usize = 1 + PUBKEY_BYTES * 3;
+impl EpochGaugeVoter {
+    /// Number of bytes in an [EpochGaugeVoter].
+    pub const LEN: usize = PUBKEY_BYTES + 8 + 1 + 8 + 8;
+
+    /// The voting power to commit to a [Gauge] with `weight` out of `total_weight`.
+    ///
+    /// The power is capped at what is left, in case the weights changed during the week.
+    pub fn power_for_weight(&self, weight: u32, total_weight: u32) -> Option<u64> {
+        if total_weight == 0 {
+            return Some(0);
+        }
+        let power = (self.voting_power as u128)
+            .checked_mul(weight.into())?
+            .checked_div(total_weight.into())?
+            .to_u64()?;
+        Some(power.min(self.voting_power.checked_sub(self.allocated_power)?))
+    }
 }
 
-/// Locks tokens on behalf of a user.
+/// Receipt of a [GaugeVote] committed for a week. Its existence prevents committing twice.
 #[account]
 #[derive(Copy, Debug, Default)]
-pub struct Escrow {
-    /// The [Locker] that this [Escrow] is part of.
-    pub locker: Pubkey,
-    /// The key of the account that is authorized to stake into/withdraw from this [Escrow].
-    pub owner: Pubkey,
+pub struct EpochGaugeVote {
+    /// The [GaugeVote].
+    pub gauge_vote: Pubkey,
+    /// Index of the week since the Unix epoch.
+    pub epoch: u64,
+    /// Voting power committed.
+    pub allocated_power: u64,
+}
+
+impl EpochGaugeVote {
+    /// Number of bytes in an [EpochGaugeVote].
+    pub const LEN: usize = PUBKEY_BYTES + 8 + 8;
+}
+
+/// Splits the voting power of an [Escrow] across several delegates.
+#[account]
+#[derive(Debug, Default)]
+pub struct VoteDelegation {
+    /// The [Escrow].
+    pub escrow: Pubkey,
     /// Bump seed.
     pub bump: u8,
+    /// Terms of the delegation. Outside of them, the shares are voted by the [Escrow::vote_delegate].
+    pub terms: DelegationTerms,
+    /// Delegates and their shares of the voting power.
+    pub shares: Vec<DelegateShare>,
+}
 
-    /// The token account holding the escrow tokens.
-    pub tokens: Pubkey,
-    /// Amount of tokens staked.
-    pub amount: u64,
-    /// When the [Escrow::owner] started their escrow.
-    pub escrow_started_at: i64,
-    /// When the escrow unlocks; i.e. the [Escrow::owner] is scheduled to be allowed to withdraw their tokens.
-    pub escrow_ends_at: i64,
+impl VoteDelegation {
+    /// Maximum number of [DelegateShare]s.
+    pub const MAX_SHARES: usize = 8;
 
-    /// Account that is authorized to vote on behalf of this [Escrow].
-    /// Defaults to the [Escrow::owner].
-    pub vote_delegate: Pubkey,
+    /// Number of bytes in a [VoteDelegation].
+    pub const LEN: usize = PUBKEY_BYTES + 1 + 4 + DelegateShare::LEN * VoteDelegation::MAX_SHARES;
+
+    /// Gets the share of `delegate`, in basis points.
+    pub fn bps_of(&self, delegate: Pubkey) -> Option<u16> {
+        self.shares
+            .iter()
+            .find(|share| share.delegate == delegate)
+            .map(|share| share.bps)
+    }
+
+    /// Gets the voter of the [govern::Vote]s cast by `delegate` for `escrow`.
+    ///
+    /// Each delegate votes through its own [govern::Vote], so delegates of the same [Escrow]
+    /// may vote on different sides.
+    pub fn delegate_voter(escrow: Pubkey, delegate: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"DelegateVoter".as_ref(),
+                escrow.as_ref(),
+                delegate.as_ref(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
 }
 
-impl Escrow {
-    /// Number of bytes in an [Escrow].
-    pub const LEN: usize = PUBKEY_BYTES * 2 + 1 + PUBKEY_BYTES + 8 + 8 + 8 + PUBKEY_BYTES;
+/// When and where a delegation of voting power applies.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DelegationTerms {
+    /// When the delegation expires. Zero never expires.
+    pub expires_at: i64,
+    /// The only [Governor] the delegation applies to. [Pubkey::default] applies to all governors.
+    pub governor: Pubkey,
 }
 
-impl Escrow {
-    /// Gets the amount of voting power the [Escrow] will have at the given time.
-    pub fn voting_power_at_time(&self, locker: &LockerParams, timestamp: i64) -> Option<u64> {
-        locker.calculate_voter_power(self, timestamp)
+impl DelegationTerms {
+    /// Number of bytes in a [DelegationTerms].
+    pub const LEN: usize = 8 + PUBKEY_BYTES;
+
+    /// Whether the delegation has expired at `now`.
+    pub fn has_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
     }
 
-    /// Gets the amount of voting power the [Escrow] currently has.
-    pub fn voting_power(&self, locker: &LockerParams) -> Result<u64> {
-        Ok(unwrap_int!(self.voting_power_at_time(
-            locker,
-            Clock::get()?.unix_timestamp
-        )))
+    /// Whether the delegation applies to `governor` at `now`.
+    pub fn covers(&self, governor: Pubkey, now: i64) -> bool {
+        !self.has_expired(now) && (self.governor == Pubkey::default() || self.governor == governor)
     }
 
-    /// Update the escrow and its locker to account for a lock event.
-    pub fn record_lock_event(
-        &mut self,
-        locker: &mut Locker,
-        lock_amount: u64,
-        next_escrow_started_at: i64,
-        next_escrow_ends_at: i64,
-    ) -> Result<()> {
-        self.amount = unwrap_int!(self.amount.checked_add(lock_amount));
-        self.escrow_started_at = next_escrow_started_at;
-        self.escrow_ends_at = next_escrow_ends_at;
+    /// Checks that the terms have not already expired at `now`.
+    pub fn validate(&self, now: i64) -> Result<()> {
+        invariant!(!self.has_expired(now), DelegationInactive);
+        Ok(())
+    }
+}
 
-        locker.locked_supply = unwrap_int!(locker.locked_supply.checked_add(lock_amount));
+/// A delegate's share of the voting power of an [Escrow].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DelegateShare {
+    /// The delegate.
+    pub delegate: Pubkey,
+    /// Share of the voting power, in basis points.
+    pub bps: u16,
+}
 
-        Ok(())
+impl DelegateShare {
+    /// Number of bytes in a [DelegateShare].
+    pub const LEN: usize = PUBKEY_BYTES + 2;
+
+    /// Validates `shares` and returns their total, in basis points.
+    pub fn total_bps(shares: &[DelegateShare]) -> Result<u16> {
+        invariant!(shares.len() <= VoteDelegation::MAX_SHARES, TooManyDelegates);
+        let mut total: u64 = 0;
+        for (i, share) in shares.iter().enumerate() {
+            invariant!(share.delegate != Pubkey::default(), InvalidDelegateShare);
+            invariant!(share.bps > 0, InvalidDelegateShare);
+            invariant!(
+                !shares[..i]
+                    .iter()
+                    .any(|other| other.delegate == share.delegate),
+                InvalidDelegateShare
+            );
+            total = unwrap_int!(total.checked_add(share.bps.into()));
+        }
+        invariant!(total <= crate::locker::BPS_PER_UNIT, InvalidDelegateShare);
+        Ok(unwrap_int!(total.to_u16()))
+    }
+
+    /// Gets `bps` basis points of `voting_power`, rounded down.
+    pub fn apply_bps(voting_power: u64, bps: u16) -> Option<u64> {
+        (voting_power as u128)
+            .checked_mul(bps.into())?
+            .checked_div(crate::locker::BPS_PER_UNIT.into())?
+            .to_u64()
     }
 }
 
@@ -277,6 +1776,96 @@ mod tests {
         assert_escrow(&locker.params, alice, current_ts, 0.0);
     }
 
+    #[test]
+    fn test_voting_powers_split() {
+        let locker = &mut Locker {
+            params: LockerParams {
+                max_stake_duration: MAX_TIME as u64,
+                max_stake_vote_multiplier: DEFAULT_STAKE_MULTIPLIER,
+                ..LockerParams::default()
+            },
+            ..Locker::default()
+        };
+        let alice = &mut Escrow::default();
+        let bob = &mut Escrow::default();
+
+        let mut current_ts = CANONICAL_START_TIME;
+        alice
+            .record_lock_event(
+                locker,
+                DEFAULT_LOCK_AMOUNT,
+                current_ts,
+                current_ts + 2 * WEEK,
+            )
+            .unwrap();
+        let measured_at = current_ts + WEEK;
+        let power_before = alice
+            .voting_power_at_time(&locker.params, measured_at)
+            .unwrap();
+
+        current_ts += DAY;
+        // Alice gives a quarter of her lock to Bob
+        let split_amount = DEFAULT_LOCK_AMOUNT / 4;
+        alice.record_unlock_event(locker, split_amount).unwrap();
+        bob.record_lock_event(
+            locker,
+            split_amount,
+            alice.escrow_started_at,
+            alice.escrow_ends_at,
+        )
+        .unwrap();
+
+        assert_eq!(locker.locked_supply, DEFAULT_LOCK_AMOUNT);
+        assert_eq!(alice.amount + bob.amount, DEFAULT_LOCK_AMOUNT);
+        assert_eq!(alice.escrow_ends_at, bob.escrow_ends_at);
+        assert!(alice.escrow_ends_at > current_ts);
+
+        // splitting does not change the total voting power, up to rounding
+        let power_after = alice
+            .voting_power_at_time(&locker.params, measured_at)
+            .unwrap()
+            + bob
+                .voting_power_at_time(&locker.params, measured_at)
+                .unwrap();
+        assert!(power_before - power_after <= 1);
+    }
+
+    #[test]
+    fn test_escrow_seeds_match_pda() {
+        let locker = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        for position_id in [0_u64, 1, 42] {
+            let (expected, bump) = if position_id == 0 {
+                Pubkey::find_program_address(
+                    &[b"Escrow", locker.as_ref(), owner.as_ref()],
+                    &crate::ID,
+                )
+            } else {
+                Pubkey::find_program_address(
+                    &[
+                        b"Escrow",
+                        locker.as_ref(),
+                        owner.as_ref(),
+                        &position_id.to_le_bytes(),
+                    ],
+                    &crate::ID,
+                )
+            };
+            let escrow = Escrow {
+                locker,
+                owner,
+                bump,
+                position_id,
+                ..Escrow::default()
+            };
+            let seeds: &[&[&[u8]]] = escrow_seeds!(escrow);
+            assert_eq!(
+                Pubkey::create_program_address(seeds[0], &crate::ID).unwrap(),
+                expected
+            );
+        }
+    }
+
     // Test voting power in the following scenario:
     // Alice:
     // ~~~~~~~
@@ -375,4 +1964,660 @@ mod tests {
         assert_eq!(locker.locked_supply, 0);
         assert_escrow(&locker.params, bob, current_ts, 0.0);
     }
+
+    #[test]
+    fn test_voting_power_as_of() {
+        let params = &LockerParams {
+            max_stake_vote_multiplier: DEFAULT_STAKE_MULTIPLIER,
+            max_stake_duration: MAX_TIME as u64,
+            ..LockerParams::default()
+        };
+        let locker = &mut Locker::default();
+        let escrow = &mut Escrow::default();
+        let created_at = CANONICAL_START_TIME;
+        escrow.record_lock_checkpoint(created_at);
+
+        let locked_at = created_at + DAY;
+        escrow
+            .record_lock_event(locker, DEFAULT_LOCK_AMOUNT, locked_at, locked_at + MAX_TIME)
+            .unwrap();
+        escrow.record_lock_checkpoint(locked_at);
+
+        // a proposal activated between the two locks only sees the first lock.
+        let activated_at = locked_at + WEEK;
+        let power_at_activation = escrow.voting_power_at_time(params, activated_at).unwrap();
+        let relocked_at = activated_at + HOUR;
+        escrow
+            .record_lock_event(
+                locker,
+                DEFAULT_LOCK_AMOUNT,
+                relocked_at,
+                relocked_at + MAX_TIME,
+            )
+            .unwrap();
+        escrow.record_lock_checkpoint(relocked_at);
+
+        assert_eq!(
+            escrow.voting_power_as_of(params, activated_at).unwrap(),
+            power_at_activation
+        );
+        assert_eq!(
+            escrow.voting_power_as_of(params, created_at - 1).unwrap(),
+            0
+        );
+        assert_eq!(escrow.voting_power_as_of(params, created_at).unwrap(), 0);
+        assert_eq!(
+            escrow.voting_power_as_of(params, relocked_at).unwrap(),
+            escrow.voting_power_at_time(params, relocked_at).unwrap()
+        );
+
+        // once the history has been overwritten, the smaller of the oldest kept
+        // and the current lock state is used.
+        for i in 0..Escrow::MAX_LOCK_CHECKPOINTS as i64 {
+            escrow
+                .record_lock_event(locker, 1, relocked_at + i + 1, relocked_at + MAX_TIME)
+                .unwrap();
+            escrow.record_lock_checkpoint(relocked_at + i + 1);
+        }
+        let oldest_kept = Escrow {
+            amount: 2 * DEFAULT_LOCK_AMOUNT + 1,
+            escrow_started_at: activated_at,
+            escrow_ends_at: relocked_at + MAX_TIME,
+            ..*escrow
+        };
+        assert_eq!(
+            escrow.voting_power_as_of(params, activated_at).unwrap(),
+            oldest_kept
+                .voting_power_at_time(params, activated_at)
+                .unwrap()
+        );
+
+        // escrows locked before lock checkpoints existed keep their power from their last lock.
+        let legacy = &mut Escrow {
+            amount: DEFAULT_LOCK_AMOUNT,
+            escrow_started_at: locked_at,
+            escrow_ends_at: locked_at + MAX_TIME,
+            ..Escrow::default()
+        };
+        let power_at_activation = legacy.voting_power_at_time(params, activated_at).unwrap();
+        assert!(power_at_activation > 0);
+        assert_eq!(
+            legacy.voting_power_as_of(params, activated_at).unwrap(),
+            power_at_activation
+        );
+        assert_eq!(legacy.voting_power_as_of(params, locked_at - 1).unwrap(), 0);
+        legacy
+            .record_lock_event(
+                locker,
+                DEFAULT_LOCK_AMOUNT,
+                relocked_at,
+                relocked_at + MAX_TIME,
+            )
+            .unwrap();
+        legacy.record_lock_checkpoint(relocked_at);
+        assert_eq!(
+            legacy.voting_power_as_of(params, activated_at).unwrap(),
+            power_at_activation
+        );
+    }
+
+    #[test]
+    fn test_reward_epoch_share() {
+        let reward_epoch = &mut RewardEpoch {
+            epoch: 2,
+            amount: 1_000,
+            total_voting_power: 4_000,
+            ..RewardEpoch::default()
+        };
+        assert_eq!(reward_epoch.start_ts(), Some(2 * WEEK));
+        assert_eq!(reward_epoch.end_ts(), Some(3 * WEEK));
+
+        assert_eq!(reward_epoch.share_of(1_000), Some(250));
+        assert_eq!(reward_epoch.share_of(0), Some(0));
+
+        // shares never exceed what is left.
+        reward_epoch.claimed_amount = 900;
+        assert_eq!(reward_epoch.share_of(1_000), Some(100));
+
+        reward_epoch.total_voting_power = 0;
+        assert_eq!(reward_epoch.share_of(1_000), Some(0));
+    }
+
+    #[test]
+    fn test_reward_epoch_total_voting_power() {
+        let checkpoints = &mut LockerCheckpoints::default();
+        let params = &LockerParams {
+            max_stake_vote_multiplier: 1,
+            max_stake_duration: 10 * WEEK as u64,
+            ..LockerParams::default()
+        };
+        checkpoints
+            .record_escrow_change(
+                EscrowCheckpoint::default(),
+                EscrowCheckpoint {
+                    max_locked_amount: 500,
+                    ..EscrowCheckpoint::default()
+                },
+                WEEK,
+            )
+            .unwrap();
+        let reward_epoch = &mut RewardEpoch {
+            epoch: 2,
+            ..RewardEpoch::default()
+        };
+        reward_epoch
+            .record_total_voting_power(checkpoints, params)
+            .unwrap();
+        assert_eq!(reward_epoch.total_voting_power, 500);
+        assert!(reward_epoch.total_voting_power_recorded);
+
+        // once recorded, the total no longer depends on the checkpoints.
+        reward_epoch
+            .record_total_voting_power(&LockerCheckpoints::default(), params)
+            .unwrap();
+        assert_eq!(reward_epoch.total_voting_power, 500);
+
+        // rewards with voting power to claim them stay.
+        reward_epoch.amount = 1_000;
+        assert!(reward_epoch.roll_over(checkpoints, params).is_err());
+
+        // without any voting power, the rewards left are rolled over.
+        let empty = &mut RewardEpoch {
+            epoch: 2,
+            amount: 1_000,
+            total_voting_power_recorded: true,
+            ..RewardEpoch::default()
+        };
+        assert_eq!(empty.roll_over(checkpoints, params).unwrap(), 1_000);
+        assert_eq!(empty.amount, 0);
+        assert_eq!(empty.roll_over(checkpoints, params).unwrap(), 0);
+
+        // as are the rewards of a week whose total is no longer kept.
+        let full = &mut LockerCheckpoints::default();
+        for now in 1..=(LockerCheckpoints::MAX_POINTS as i64 + 1) {
+            full.checkpoint(10 * WEEK + now).unwrap();
+        }
+        let unavailable = &mut RewardEpoch {
+            epoch: 2,
+            amount: 1_000,
+            ..RewardEpoch::default()
+        };
+        assert!(unavailable.record_total_voting_power(full, params).is_err());
+        assert_eq!(unavailable.roll_over(full, params).unwrap(), 1_000);
+        assert!(unavailable.total_voting_power_recorded);
+        assert_eq!(unavailable.share_of(1_000), Some(0));
+    }
+
+    #[test]
+    fn test_epoch_gauge_voter_power_for_weight() {
+        let epoch_gauge_voter = &mut EpochGaugeVoter {
+            voting_power: 1_000,
+            ..EpochGaugeVoter::default()
+        };
+        assert_eq!(epoch_gauge_voter.power_for_weight(1, 4), Some(250));
+        assert_eq!(epoch_gauge_voter.power_for_weight(3, 4), Some(750));
+        assert_eq!(epoch_gauge_voter.power_for_weight(3, 0), Some(0));
+
+        // a weight change during the week cannot over-allocate.
+        epoch_gauge_voter.allocated_power = 750;
+        assert_eq!(epoch_gauge_voter.power_for_weight(4, 4), Some(250));
+    }
+
+    #[test]
+    fn test_delegate_shares() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        let share = |delegate, bps| DelegateShare { delegate, bps };
+
+        assert_eq!(DelegateShare::total_bps(&[]).unwrap(), 0);
+        assert_eq!(
+            DelegateShare::total_bps(&[share(a, 6_000), share(b, 4_000)]).unwrap(),
+            10_000
+        );
+        assert!(DelegateShare::total_bps(&[share(a, 6_000), share(b, 4_001)]).is_err());
+        assert!(DelegateShare::total_bps(&[share(a, 1), share(a, 1)]).is_err());
+        assert!(DelegateShare::total_bps(&[share(a, 0)]).is_err());
+        assert!(DelegateShare::total_bps(&[share(Pubkey::default(), 1)]).is_err());
+        let too_many = vec![share(a, 1); VoteDelegation::MAX_SHARES + 1];
+        assert!(DelegateShare::total_bps(&too_many).is_err());
+
+        assert_eq!(DelegateShare::apply_bps(1_000, 2_500), Some(250));
+        assert_eq!(DelegateShare::apply_bps(3, 5_000), Some(1));
+        assert_eq!(DelegateShare::apply_bps(u64::MAX, 10_000), Some(u64::MAX));
+
+        let escrow = Escrow {
+            delegated_bps: 6_000,
+            ..Escrow::default()
+        };
+        assert_eq!(escrow.undelegated_bps(), Some(4_000));
+
+        // changing the shares locks the escrow out of proposals activated before.
+        let escrow = Escrow {
+            delegation_updated_at: 1_000,
+            ..escrow
+        };
+        assert!(escrow.delegation_changed_since(1_000));
+        assert!(!escrow.delegation_changed_since(1_001));
+        assert!(!Escrow::default().delegation_changed_since(1));
+    }
+
+    #[test]
+    fn test_delegation_terms() {
+        let governor = Pubkey::new_unique();
+        let other_governor = Pubkey::new_unique();
+        let escrow = Escrow {
+            owner: Pubkey::new_unique(),
+            vote_delegate: Pubkey::new_unique(),
+            ..Escrow::default()
+        };
+
+        // legacy delegations never expire.
+        assert_eq!(
+            escrow.active_vote_delegate(escrow.owner, governor, i64::MAX),
+            escrow.vote_delegate
+        );
+
+        let escrow = Escrow {
+            vote_delegate_terms: DelegationTerms {
+                expires_at: 1_000,
+                governor,
+            },
+            ..escrow
+        };
+        assert_eq!(
+            escrow.active_vote_delegate(escrow.owner, governor, 999),
+            escrow.vote_delegate
+        );
+        assert_eq!(
+            escrow.active_vote_delegate(escrow.owner, governor, 1_000),
+            escrow.owner
+        );
+        assert_eq!(
+            escrow.active_vote_delegate(escrow.owner, other_governor, 999),
+            escrow.owner
+        );
+
+        assert!(escrow.vote_delegate_terms.validate(999).is_ok());
+        assert!(escrow.vote_delegate_terms.validate(1_000).is_err());
+
+        // delegations of an NFT escrow lapse once the position token is transferred.
+        let holder = Pubkey::new_unique();
+        let buyer = Pubkey::new_unique();
+        let escrow = Escrow {
+            position_mint: escrow.owner,
+            delegated_by: holder,
+            ..escrow
+        };
+        assert!(escrow.delegation_applies(holder));
+        assert_eq!(
+            escrow.active_vote_delegate(holder, governor, 999),
+            escrow.vote_delegate
+        );
+        assert!(!escrow.delegation_applies(buyer));
+        assert_eq!(escrow.active_vote_delegate(buyer, governor, 999), buyer);
+    }
+
+    #[test]
+    fn test_whitelist_entry_limits() {
+        let entry = &mut LockerWhitelistEntry {
+            limits: WhitelistEntryLimits {
+                max_locked_amount: 1_000,
+                min_stake_duration: 100,
+                max_stake_duration: 200,
+                expires_at: 10_000,
+            },
+            ..LockerWhitelistEntry::default()
+        };
+        assert!(entry.limits.validate().is_ok());
+
+        entry.record_lock(600, 0).unwrap();
+        assert!(entry.record_lock(401, 0).is_err());
+        entry.record_lock(400, 0).unwrap();
+        assert_eq!(entry.locked_amount, 1_000);
+        entry.release(300);
+        assert_eq!(entry.locked_amount, 700);
+        entry.release(u64::MAX);
+        assert_eq!(entry.locked_amount, 0);
+
+        entry.limits.max_locked_amount = 0;
+        assert!(entry.record_lock(1, 10_000).is_err());
+        entry.record_lock(u64::MAX, 9_999).unwrap();
+        assert_eq!(entry.locked_amount, 0);
+
+        // the entry durations replace those of the locker, up to its max.
+        let params = &LockerParams {
+            min_stake_duration: 500,
+            max_stake_duration: 1_000,
+            ..LockerParams::default()
+        };
+        assert_eq!(entry.stake_duration_bounds(params), (100, 200));
+        assert!(entry.check_stake_duration(params, 99).is_err());
+        assert!(entry.check_stake_duration(params, 150).is_ok());
+        assert!(entry.check_stake_duration(params, 201).is_err());
+        entry.limits.max_stake_duration = 2_000;
+        assert_eq!(entry.stake_duration_bounds(params), (100, 1_000));
+
+        // legacy entries use the locker durations and are unlimited.
+        let legacy = &mut LockerWhitelistEntry::default();
+        legacy.record_lock(u64::MAX, i64::MAX).unwrap();
+        assert_eq!(legacy.stake_duration_bounds(params), (500, 1_000));
+        assert!(legacy.check_stake_duration(params, -1).is_err());
+
+        // escrows remember what to release from their entry.
+        let escrow = &mut Escrow::default();
+        let entry_key = Pubkey::new_unique();
+        escrow.record_whitelist_lock(entry_key, 0).unwrap();
+        assert_eq!(escrow.whitelist_entry, Pubkey::default());
+        escrow.record_whitelist_lock(entry_key, 300).unwrap();
+        assert!(escrow
+            .record_whitelist_lock(Pubkey::new_unique(), 1)
+            .is_err());
+        assert_eq!(escrow.release_whitelist_lock(500), 0);
+        assert_eq!(escrow.release_whitelist_lock(100), 200);
+        assert_eq!(escrow.whitelist_entry, entry_key);
+        assert_eq!(escrow.release_whitelist_lock(0), 100);
+        assert_eq!(escrow.whitelist_entry, Pubkey::default());
+        escrow
+            .record_whitelist_lock(Pubkey::new_unique(), 1)
+            .unwrap();
+
+        assert!(WhitelistEntryLimits {
+            min_stake_duration: 2,
+            max_stake_duration: 1,
+            ..WhitelistEntryLimits::default()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_vesting_schedule() {
+        let params = &LockerParams {
+            max_stake_duration: 10 * WEEK as u64,
+            max_stake_vote_multiplier: 1,
+            ..LockerParams::default()
+        };
+        let escrow = &mut Escrow {
+            amount: 1_000,
+            escrow_started_at: WEEK,
+            escrow_ends_at: 11 * WEEK,
+            vesting_cliff_at: 6 * WEEK,
+            vesting_amount: 1_000,
+            ..Escrow::default()
+        };
+        assert!(escrow.is_vesting());
+
+        // nothing unlocks before the cliff.
+        assert_eq!(escrow.vested_amount_at(6 * WEEK - 1), Some(0));
+        assert_eq!(escrow.locked_amount_at(6 * WEEK - 1), Some(1_000));
+        // tokens count until the start of the week they unlock in: 800 + 600 + 400 + 200 for a week each.
+        assert_eq!(escrow.voting_power_at_time(params, 6 * WEEK), Some(200));
+
+        // tokens unlock linearly until the end.
+        let halfway = 8 * WEEK + WEEK / 2;
+        assert_eq!(escrow.vested_amount_at(halfway), Some(500));
+        assert_eq!(escrow.locked_amount_at(halfway), Some(500));
+        // 400 for half a week, then 200 for a week.
+        assert_eq!(escrow.voting_power_at_time(params, halfway), Some(40));
+        assert_eq!(escrow.vested_amount_at(11 * WEEK), Some(1_000));
+        assert_eq!(escrow.locked_amount_at(11 * WEEK), Some(0));
+
+        // other curves follow the locked amount.
+        let flat = &LockerParams {
+            voting_power_curve: VotingPowerCurve::Flat,
+            ..*params
+        };
+        assert_eq!(escrow.voting_power_at_time(flat, halfway), Some(500));
+
+        // withdrawing the unlocked tokens does not change the locked amount.
+        escrow.amount = 500;
+        assert_eq!(escrow.locked_amount_at(halfway), Some(500));
+        assert_eq!(escrow.locked_amount_at(10 * WEEK), Some(200));
+        assert_eq!(escrow.voting_power_at_time(params, halfway), Some(40));
+
+        // regular escrows are fully locked.
+        let regular = Escrow {
+            vesting_amount: 0,
+            ..*escrow
+        };
+        assert!(!regular.is_vesting());
+        assert_eq!(regular.locked_amount_at(10 * WEEK), Some(500));
+
+        // a cliff at the end unlocks everything at once.
+        let cliff_only = Escrow {
+            vesting_cliff_at: 11 * WEEK,
+            ..*escrow
+        };
+        assert_eq!(cliff_only.vested_amount_at(11 * WEEK - 1), Some(0));
+        assert_eq!(cliff_only.vested_amount_at(11 * WEEK), Some(1_000));
+    }
+
+    #[test]
+    fn test_deposit_history() {
+        let locker = &mut Locker::default();
+        let escrow = &mut Escrow::default();
+        let cutoff = 1_000;
+
+        escrow.record_lock_event(locker, 100, 500, 2_000).unwrap();
+        escrow.record_lock_checkpoint(500);
+        assert_eq!(escrow.first_locked_at(), 500);
+
+        // a top-up after the cutoff restarts the lock, but not the first lock.
+        escrow.record_lock_event(locker, 1, 1_500, 3_000).unwrap();
+        escrow.record_lock_checkpoint(1_500);
+        assert_eq!(escrow.first_locked_at(), 500);
+        assert_eq!(escrow.deposited_amount(), 101);
+        assert_eq!(
+            escrow.redeemable_amount(RedemptionEligibility::LastLock, cutoff),
+            0
+        );
+        assert_eq!(
+            escrow.redeemable_amount(RedemptionEligibility::FirstLock, cutoff),
+            101
+        );
+        assert_eq!(
+            escrow.redeemable_amount(RedemptionEligibility::ProRata, cutoff),
+            100
+        );
+
+        // withdrawals take the newest deposits first.
+        escrow.record_unlock_event(locker, 51).unwrap();
+        escrow.record_lock_checkpoint(1_600);
+        assert_eq!(escrow.amount_deposited_before(cutoff), 50);
+        assert_eq!(escrow.deposit_count, 1);
+
+        // emptying the escrow resets the first lock.
+        escrow.record_unlock_event(locker, 50).unwrap();
+        escrow.record_lock_checkpoint(1_700);
+        assert!(!escrow.tracks_deposits());
+        assert_eq!(escrow.deposit_count, 0);
+
+        // a full history merges the deposits closest in time.
+        for i in 0..(Escrow::MAX_DEPOSITS as i64 + 2) {
+            escrow.add_deposit(EscrowDeposit {
+                timestamp: 10 + i,
+                amount: 1,
+            });
+        }
+        assert_eq!(escrow.deposit_count as usize, Escrow::MAX_DEPOSITS);
+        assert_eq!(escrow.deposited_amount(), Escrow::MAX_DEPOSITS as u64 + 2);
+        assert_eq!(
+            escrow.take_deposits(3),
+            vec![EscrowDeposit {
+                timestamp: 19,
+                amount: 3
+            }]
+        );
+        assert_eq!(escrow.first_locked_at, 10);
+
+        // deposits after the cutoff do not move older deposits past it.
+        let escrow = &mut Escrow {
+            amount: 90,
+            ..Escrow::default()
+        };
+        for i in 0..(Escrow::MAX_DEPOSITS as i64) {
+            escrow.add_deposit(EscrowDeposit {
+                timestamp: 100 + i * 100,
+                amount: 10,
+            });
+        }
+        for i in 0..10 {
+            escrow.add_deposit(EscrowDeposit {
+                timestamp: 2_000 + i,
+                amount: 1,
+            });
+        }
+        assert_eq!(escrow.deposit_count as usize, Escrow::MAX_DEPOSITS);
+        assert_eq!(escrow.amount_deposited_before(cutoff), 80);
+        assert_eq!(escrow.deposited_amount(), 90);
+
+        // escrows locked before the history existed use their last lock.
+        let legacy = &mut Escrow {
+            amount: 100,
+            escrow_started_at: 900,
+            ..Escrow::default()
+        };
+        assert_eq!(legacy.amount_deposited_before(cutoff), 100);
+        legacy.record_lock_event(locker, 10, 1_200, 3_000).unwrap();
+        legacy.record_lock_checkpoint(1_200);
+        assert_eq!(legacy.first_locked_at(), 900);
+        assert_eq!(legacy.amount_deposited_before(cutoff), 100);
+    }
+
+    #[test]
+    fn test_record_redemption() {
+        let locker = &mut Locker::default();
+        let escrow = &mut Escrow::default();
+        let cutoff = 1_000;
+        escrow.record_lock_event(locker, 100, 500, 2_000).unwrap();
+        escrow.record_lock_checkpoint(500);
+        escrow.record_lock_event(locker, 1, 1_500, 3_000).unwrap();
+        escrow.record_lock_checkpoint(1_500);
+
+        // redeeming pro-rata uses up the deposits made before the cutoff.
+        escrow
+            .record_redemption(locker, RedemptionEligibility::ProRata, cutoff, 60)
+            .unwrap();
+        escrow.record_lock_checkpoint(1_600);
+        assert_eq!(escrow.amount, 41);
+        assert_eq!(escrow.redeemed_amount, 60);
+        assert_eq!(
+            escrow.redeemable_amount(RedemptionEligibility::ProRata, cutoff),
+            40
+        );
+
+        escrow
+            .record_redemption(locker, RedemptionEligibility::ProRata, cutoff, 40)
+            .unwrap();
+        escrow.record_lock_checkpoint(1_700);
+        assert_eq!(escrow.redeemed_amount, 100);
+        assert_eq!(
+            escrow.redeemable_amount(RedemptionEligibility::ProRata, cutoff),
+            0
+        );
+        assert_eq!(escrow.escrow_ends_at, 3_000);
+
+        // redeeming everything unlocks the escrow.
+        escrow
+            .record_redemption(locker, RedemptionEligibility::FirstLock, cutoff, 1)
+            .unwrap();
+        assert_eq!(escrow.amount, 0);
+        assert_eq!(escrow.escrow_ends_at, 0);
+        assert_eq!(locker.locked_supply, 0);
+    }
+
+    #[test]
+    fn test_redemption_rate_schedule() {
+        let redeemer = &mut LockerRedeemer {
+            redemption_rate: 7,
+            ..LockerRedeemer::default()
+        };
+        assert_eq!(redeemer.redemption_rate_at(1_000), Some(7));
+
+        redeemer.rate_schedule = RedemptionRateSchedule {
+            curve: RedemptionRateCurve::Linear,
+            start_at: 100,
+            end_at: 200,
+            start_rate: 1_000,
+            end_rate: 2_000,
+            steps: 0,
+        };
+        redeemer.rate_schedule.validate().unwrap();
+        assert_eq!(redeemer.redemption_rate_at(0), Some(1_000));
+        assert_eq!(redeemer.redemption_rate_at(150), Some(1_500));
+        assert_eq!(redeemer.redemption_rate_at(199), Some(1_990));
+        assert_eq!(redeemer.redemption_rate_at(200), Some(2_000));
+
+        // falling rates work the same way.
+        redeemer.rate_schedule.start_rate = 3_000;
+        assert_eq!(redeemer.redemption_rate_at(125), Some(2_750));
+
+        redeemer.rate_schedule.curve = RedemptionRateCurve::Stepped;
+        redeemer.rate_schedule.steps = 4;
+        assert_eq!(redeemer.redemption_rate_at(124), Some(3_000));
+        assert_eq!(redeemer.redemption_rate_at(125), Some(2_750));
+        assert_eq!(redeemer.redemption_rate_at(199), Some(2_250));
+        assert_eq!(redeemer.redemption_rate_at(200), Some(2_000));
+
+        redeemer.rate_schedule.steps = 0;
+        assert!(redeemer.rate_schedule.validate().is_err());
+        redeemer.rate_schedule.steps = 4;
+        redeemer.rate_schedule.end_at = 100;
+        assert!(redeemer.rate_schedule.validate().is_err());
+        redeemer.rate_schedule.end_at = 200;
+        redeemer.rate_schedule.end_rate = 0;
+        assert!(redeemer.rate_schedule.validate().is_err());
+    }
+
+    #[test]
+    fn test_redemption_price() {
+        let redeemer = &mut LockerRedeemer {
+            redemption_rate: 10_000,
+            ..LockerRedeemer::default()
+        };
+        // without a price, voting power is divided by the rate.
+        assert_eq!(redeemer.receipt_amount(25_000, 10_000), Some(2));
+
+        // rates below one: 1 unit of voting power = 5/2 receipt tokens.
+        redeemer.price_numerator = 5;
+        redeemer.price_denominator = 2;
+        redeemer.locker_decimals = 6;
+        redeemer.receipt_decimals = 6;
+        assert_eq!(redeemer.receipt_amount(1_000_000, 10_000), Some(2_500_000));
+        assert_eq!(redeemer.receipt_amount(3, 10_000), Some(7));
+        // the redemption rate and its schedule do not move the price.
+        assert_eq!(redeemer.receipt_amount(3, 1), Some(7));
+
+        // decimals mismatch: 1 locked token (6 decimals) = 1/4 receipt token (9 decimals).
+        redeemer.price_numerator = 1;
+        redeemer.price_denominator = 4;
+        redeemer.receipt_decimals = 9;
+        assert_eq!(redeemer.receipt_amount(1_000_000, 4), Some(250_000_000));
+        // and the other way around, with 9 locked decimals and 6 receipt decimals.
+        redeemer.locker_decimals = 9;
+        redeemer.receipt_decimals = 6;
+        assert_eq!(redeemer.receipt_amount(1_000_000_000, 4), Some(250_000));
+
+        // dust rounds down to zero.
+        assert_eq!(redeemer.receipt_amount(3_999, 4), Some(0));
+        assert_eq!(redeemer.receipt_amount(4_000, 4), Some(1));
+
+        // overflow of the result or of the decimal scaling fails.
+        redeemer.price_numerator = u64::MAX;
+        redeemer.price_denominator = u64::MAX;
+        redeemer.locker_decimals = 0;
+        redeemer.receipt_decimals = 0;
+        assert_eq!(redeemer.receipt_amount(u64::MAX, 1), Some(u64::MAX));
+        redeemer.price_denominator = 1;
+        assert_eq!(redeemer.receipt_amount(u64::MAX, 1), None);
+        redeemer.price_denominator = u64::MAX;
+        redeemer.receipt_decimals = 18;
+        assert_eq!(redeemer.receipt_amount(u64::MAX, 1), None);
+        redeemer.price_numerator = 1;
+        redeemer.price_denominator = 1;
+        redeemer.receipt_decimals = 39;
+        assert_eq!(redeemer.receipt_amount(1, 1), None);
+        assert!(decimals_scale(38).is_some());
+        assert!(decimals_scale(39).is_none());
+    }
 }
@@ -5,6 +5,10 @@ const { BN } = anchor;
 import { PublicKey, SystemProgram } from '@solana/web3.js';
 import { TOKEN_PROGRAM_ID } from '@solana/spl-token';
 
+const SECONDS_PER_WEEK = 7 * 24 * 60 * 60;
+const BPS_PER_UNIT = 10000;
+const EXPONENTIAL_HALVINGS = 8;
+
 /**
  * Tribeca SDK
  * @class
@@ -59,9 +63,9 @@ export class TribecaSDK {
         threshold = new BN(1),
         minimumDelay = new BN(0),
         votingDelay = new BN(0),
-        votingPeriod = new BN(0),
+        votingPeriod = new BN(60 * 60),
         quorumVotes = new BN(10),
-        timelockDelaySeconds = new BN(0),
+        timelockDelaySeconds = new BN(60 * 60),
         electorate = payer.publicKey,
       } = options;
 
@@ -137,8 +141,9 @@ export class TribecaSDK {
    * @param baseKey - The base keypair for deriving PDAs (must be a signer)
    * @param governanceToken - The governance token mint
    * @param governor - The governor account
-   * @param options - Configuration options
-   * @returns - The instruction to create a new locker instance
+   * @param options - Configuration options. The voting power curve defaults
+   * to linear, without a baseline power or an early exit penalty.
+   * @returns - The instructions to create a new locker instance and its checkpoints
    */
   async createNewLocker(
     payer: PublicKey,
@@ -151,6 +156,10 @@ export class TribecaSDK {
       maxStakeDuration: any;
       minStakeDuration: any;
       proposalActivationMinVotes: any;
+      votingPowerCurve?: any;
+      baselinePowerBps?: number;
+      earlyExitPenaltyBps?: number;
+      earlyExitPenaltyRecipient?: PublicKey;
     }
   ) {
     try {
@@ -160,6 +169,10 @@ export class TribecaSDK {
         minStakeDuration,
         maxStakeDuration,
         proposalActivationMinVotes,
+        votingPowerCurve = { linear: {} },
+        baselinePowerBps = 0,
+        earlyExitPenaltyBps = 0,
+        earlyExitPenaltyRecipient = PublicKey.default,
       } = options;
 
       const [lockerPDA, lockerBump] = PublicKey.findProgramAddressSync(
@@ -174,6 +187,10 @@ export class TribecaSDK {
           minStakeDuration,
           maxStakeDuration,
           proposalActivationMinVotes,
+          votingPowerCurve,
+          baselinePowerBps,
+          earlyExitPenaltyBps,
+          earlyExitPenaltyRecipient,
         })
         .accounts({
           base: baseKey,
@@ -183,11 +200,28 @@ export class TribecaSDK {
           payer: payer,
           systemProgram: SystemProgram.programId,
         })
+        // the early exit penalty recipient must be passed if it is set
+        .remainingAccounts(
+          earlyExitPenaltyRecipient.equals(PublicKey.default)
+            ? []
+            : [
+                {
+                  pubkey: earlyExitPenaltyRecipient,
+                  isWritable: false,
+                  isSigner: false,
+                },
+              ]
+        )
         .instruction();
 
+      const { createLockerCheckpointsInstruction, checkpointsPDA } =
+        await this.createLockerCheckpoints(payer, lockerPDA);
+
       return {
         createLockerInstruction,
+        createLockerCheckpointsInstruction,
         lockerPDA,
+        checkpointsPDA,
       };
     } catch (error) {
       console.error('Error creating new locker', error);
@@ -256,6 +290,7 @@ export class TribecaSDK {
       const lock = {
         locker: locker,
         escrow: escrow,
+        checkpoints: this.findLockerCheckpoints(locker),
         escrowTokens: escrowTokens,
         escrowOwner: payer,
         sourceTokens: sourceTokens,
@@ -287,7 +322,6 @@ export class TribecaSDK {
    * @param escrowOwner - The owner of the escrow
    * @param escrowTokens - The escrow token account
    * @param destinationTokens - The destination token account
-   * @param tokenMint - The mint of the token to unlock
    * @returns - The instruction to unlock tokens
    */
   async exitEscrow(
@@ -296,8 +330,7 @@ export class TribecaSDK {
     escrow: PublicKey,
     escrowOwner: PublicKey,
     escrowTokens: PublicKey,
-    destinationTokens: PublicKey,
-    tokenMint: PublicKey
+    destinationTokens: PublicKey
   ) {
     try {
       // Create the instruction
@@ -306,11 +339,12 @@ export class TribecaSDK {
         .accounts({
           locker: locker,
           escrow: escrow,
+          checkpoints: this.findLockerCheckpoints(locker),
+          escrowOwner: escrowOwner,
           escrowTokens: escrowTokens,
           destinationTokens: destinationTokens,
-          tokenMint: tokenMint,
+          payer: payer,
           tokenProgram: TOKEN_PROGRAM_ID,
-          owner: escrowOwner,
         })
         .instruction();
 
@@ -495,6 +529,7 @@ export class TribecaSDK {
           locker: locker,
           redeemer: redeemer,
           escrow: escrow,
+          checkpoints: this.findLockerCheckpoints(locker),
           escrowOwner: escrowOwner,
           blacklist: blacklistPDA,
           receiptMint: receiptMint,
@@ -768,10 +803,182 @@ export class TribecaSDK {
   }
 
   /**
-   * Get the voting power of an escrow
+   * Find the locker checkpoints PDA of a locker
+   * @param locker - The locker account
+   * @returns - The locker checkpoints PDA
+   */
+  findLockerCheckpoints(locker: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('LockerCheckpoints'), locker.toBuffer()],
+      this.tribecaProgram.programId
+    )[0];
+  }
+
+  /**
+   * Resize a locker created before new fields were added to it
+   * @param payer - The payer of the additional rent
+   * @param locker - The locker account
+   * @returns - The instruction to resize the locker
+   */
+  async resizeLocker(payer: PublicKey, locker: PublicKey) {
+    try {
+      const resizeLockerInstruction = await this.tribecaProgram.methods
+        .resizeLocker()
+        .accounts({
+          locker: locker,
+          payer: payer,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+
+      return {
+        resizeLockerInstruction,
+      };
+    } catch (error) {
+      console.error('Error resizing locker', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Create the checkpoints tracking the total voting power of a locker
+   * @param payer - The payer of the transaction
+   * @param locker - The locker account
+   * @returns - The instruction to create the locker checkpoints
+   */
+  async createLockerCheckpoints(payer: PublicKey, locker: PublicKey) {
+    try {
+      const checkpointsPDA = this.findLockerCheckpoints(locker);
+
+      const createLockerCheckpointsInstruction = await this.tribecaProgram.methods
+        .newLockerCheckpoints()
+        .accounts({
+          locker: locker,
+          checkpoints: checkpointsPDA,
+          payer: payer,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+
+      return {
+        createLockerCheckpointsInstruction,
+        checkpointsPDA,
+      };
+    } catch (error) {
+      console.error('Error creating locker checkpoints', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Resize an escrow created before new fields were added to it
+   * @param payer - The payer of the additional rent
+   * @param escrow - The escrow account
+   * @returns - The instruction to resize the escrow
+   */
+  async resizeEscrow(payer: PublicKey, escrow: PublicKey) {
+    try {
+      const resizeEscrowInstruction = await this.tribecaProgram.methods
+        .resizeEscrow()
+        .accounts({
+          escrow: escrow,
+          payer: payer,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+
+      return {
+        resizeEscrowInstruction,
+      };
+    } catch (error) {
+      console.error('Error resizing escrow', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Add an escrow created before the locker checkpoints to them
+   * @param locker - The locker account
+   * @param escrow - The escrow account
+   * @returns - The instruction to checkpoint the escrow
+   */
+  async checkpointEscrow(locker: PublicKey, escrow: PublicKey) {
+    try {
+      const checkpointEscrowInstruction = await this.tribecaProgram.methods
+        .checkpointEscrow()
+        .accounts({
+          locker: locker,
+          checkpoints: this.findLockerCheckpoints(locker),
+          escrow: escrow,
+        })
+        .instruction();
+
+      return {
+        checkpointEscrowInstruction,
+      };
+    } catch (error) {
+      console.error('Error checkpointing escrow', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Resize a whitelist entry created before new fields were added to it
+   * @param payer - The payer of the additional rent
+   * @param whitelistEntry - The whitelist entry account
+   * @returns - The instruction to resize the whitelist entry
+   */
+  async resizeWhitelistEntry(payer: PublicKey, whitelistEntry: PublicKey) {
+    try {
+      const resizeWhitelistEntryInstruction = await this.tribecaProgram.methods
+        .resizeWhitelistEntry()
+        .accounts({
+          whitelistEntry: whitelistEntry,
+          payer: payer,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+
+      return {
+        resizeWhitelistEntryInstruction,
+      };
+    } catch (error) {
+      console.error('Error resizing whitelist entry', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Resize a locker redeemer created before new fields were added to it
+   * @param payer - The payer of the additional rent
+   * @param redeemer - The redeemer account
+   * @returns - The instruction to resize the locker redeemer
+   */
+  async resizeRedeemer(payer: PublicKey, redeemer: PublicKey) {
+    try {
+      const resizeRedeemerInstruction = await this.tribecaProgram.methods
+        .resizeRedeemer()
+        .accounts({
+          redeemer: redeemer,
+          payer: payer,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+
+      return {
+        resizeRedeemerInstruction,
+      };
+    } catch (error) {
+      console.error('Error resizing locker redeemer', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Get the voting power of an escrow under the voting power curve of its locker
    * @param escrow - The escrow account public key
    * @param locker - The locker account public key
-   * @returns - The voting power of the escrow (as a BN)
+   * @returns - The voting power of the escrow (in whole tokens)
    */
   async getVotingPower(escrow: PublicKey, locker: PublicKey): Promise<any> {
     try {
@@ -779,35 +986,74 @@ export class TribecaSDK {
         await this.tribecaProgram.account.escrow.fetch(escrow);
       const lockerData: any =
         await this.tribecaProgram.account.locker.fetch(locker);
+      const params = lockerData.params;
+      const maxStakeDuration = params.maxStakeDuration.toNumber();
 
       const calculateVotingPower = (timestampSeconds: number) => {
         if (escrowData.escrowStartedAt.eq(new BN(0))) {
-          return new BN(0);
+          return 0;
         }
 
         if (
           timestampSeconds < escrowData.escrowStartedAt.toNumber() ||
-          timestampSeconds >= escrowData.escrowEndsAt.toNumber()
+          (timestampSeconds >= escrowData.escrowEndsAt.toNumber() &&
+            !escrowData.maxLock)
         ) {
-          return new BN(0);
+          return 0;
         }
 
-        const secondsUntilLockupExpiry = escrowData.escrowEndsAt
-          .sub(new BN(timestampSeconds))
-          .toNumber();
-
-        const relevantSecondsUntilLockupExpiry = Math.min(
-          secondsUntilLockupExpiry,
-          lockerData.params.maxStakeDuration.toNumber()
-        );
+        // a max lock behaves as if it always had the max stake duration remaining
+        const relevantSecondsUntilLockupExpiry = escrowData.maxLock
+          ? maxStakeDuration
+          : Math.min(
+              escrowData.escrowEndsAt.toNumber() - timestampSeconds,
+              maxStakeDuration
+            );
 
         const powerIfMaxLockup = escrowData.amount.mul(
-          new BN(lockerData.params.maxStakeVoteMultiplier)
+          new BN(params.maxStakeVoteMultiplier)
         );
+        const fractionOfMaxPower = (seconds: number) =>
+          powerIfMaxLockup.mul(new BN(seconds)).div(params.maxStakeDuration);
 
-        const result = powerIfMaxLockup
-          .mul(new BN(relevantSecondsUntilLockupExpiry))
-          .div(lockerData.params.maxStakeDuration);
+        let result: any;
+        if (params.votingPowerCurve.flat) {
+          const lockupDuration = escrowData.maxLock
+            ? maxStakeDuration
+            : Math.min(
+                escrowData.escrowEndsAt
+                  .sub(escrowData.escrowStartedAt)
+                  .toNumber(),
+                maxStakeDuration
+              );
+          result = fractionOfMaxPower(lockupDuration);
+        } else if (params.votingPowerCurve.exponential) {
+          const elapsed = maxStakeDuration - relevantSecondsUntilLockupExpiry;
+          const halvingPeriod = Math.max(
+            Math.floor(maxStakeDuration / EXPONENTIAL_HALVINGS),
+            1
+          );
+          const halvings = Math.floor(elapsed / halvingPeriod);
+          const start =
+            halvings >= 64 ? new BN(0) : powerIfMaxLockup.shrn(halvings);
+          const decay = start
+            .sub(start.shrn(1))
+            .mul(new BN(elapsed % halvingPeriod))
+            .div(new BN(halvingPeriod));
+          result = start.sub(decay);
+        } else if (params.votingPowerCurve.steppedWeekly) {
+          result = fractionOfMaxPower(
+            relevantSecondsUntilLockupExpiry -
+              (relevantSecondsUntilLockupExpiry % SECONDS_PER_WEEK)
+          );
+        } else {
+          result = fractionOfMaxPower(relevantSecondsUntilLockupExpiry);
+        }
+
+        const baselinePower = escrowData.amount
+          .mul(new BN(params.baselinePowerBps))
+          .div(new BN(BPS_PER_UNIT));
+        result = BN.max(result, baselinePower);
 
         return result.toNumber() / 10 ** 6;
       };
@@ -818,4 +1064,60 @@ export class TribecaSDK {
       throw error;
     }
   }
+
+  /**
+   * Get the total voting power of a locker from its checkpoints.
+   * Only tracked for lockers with the linear curve and no baseline power.
+   * @param locker - The locker account public key
+   * @returns - The total voting power of the locker (in whole tokens)
+   */
+  async getTotalVotingPower(locker: PublicKey): Promise<any> {
+    try {
+      const lockerData: any =
+        await this.tribecaProgram.account.locker.fetch(locker);
+      const checkpointsData: any =
+        await this.tribecaProgram.account.lockerCheckpoints.fetch(
+          this.findLockerCheckpoints(locker)
+        );
+      const params = lockerData.params;
+
+      if (checkpointsData.epoch.eq(new BN(0))) {
+        return 0;
+      }
+
+      const now = Math.floor(Date.now() / 1000);
+      const lastPoint =
+        checkpointsData.points[
+          checkpointsData.epoch.subn(1).modn(checkpointsData.points.length)
+        ];
+
+      // bring the last point forward, applying the slope changes that have passed
+      let bias = lastPoint.bias;
+      let slope = lastPoint.slope;
+      let ts = lastPoint.ts.toNumber();
+      for (const change of checkpointsData.slopeChanges) {
+        if (change.time.toNumber() > now) {
+          break;
+        }
+        bias = BN.max(
+          bias.sub(slope.mul(new BN(change.time.toNumber() - ts))),
+          new BN(0)
+        );
+        slope = BN.max(slope.sub(change.slopeDelta), new BN(0));
+        ts = change.time.toNumber();
+      }
+      bias = BN.max(bias.sub(slope.mul(new BN(now - ts))), new BN(0));
+
+      const multiplier = new BN(params.maxStakeVoteMultiplier);
+      const result = bias
+        .mul(multiplier)
+        .div(params.maxStakeDuration)
+        .add(lastPoint.maxLockedAmount.mul(multiplier));
+
+      return result.toNumber() / 10 ** 6;
+    } catch (error) {
+      console.error('Error getting total voting power', error);
+      throw error;
+    }
+  }
 }
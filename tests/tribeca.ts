@@ -44,6 +44,8 @@ let TRIBECA_GOVERNOR_BUMP: number;
 let LOCKER_PDA: anchor.web3.PublicKey;
 let LOCKER_BUMP: number;
 
+let CHECKPOINTS_PDA: anchor.web3.PublicKey;
+
 let ESCROW_PDA: anchor.web3.PublicKey;
 let ESCROW_BUMP: number;
 
@@ -144,9 +146,9 @@ describe('tribeca test', () => {
 
       const electorate = payer.publicKey;
       const votingDelay = new BN(0);
-      const votingPeriod = new BN(0);
+      const votingPeriod = new BN(60 * 60);
       const quorumVotes = new BN(10);
-      const timelockDelaySeconds = new BN(0);
+      const timelockDelaySeconds = new BN(60 * 60);
 
       const {
         createSmartWalletInstruction,
@@ -197,7 +199,12 @@ describe('tribeca test', () => {
       const minStakeDuration = new BN(7 * 24 * 60 * 60); // 1 week in seconds
       const proposalActivationMinVotes = new BN(2000 * Math.pow(10, 6));
 
-      const { createLockerInstruction, lockerPDA } = await sdk.createNewLocker(
+      const {
+        createLockerInstruction,
+        createLockerCheckpointsInstruction,
+        lockerPDA,
+        checkpointsPDA,
+      } = await sdk.createNewLocker(
         payer.publicKey,
         BASE_KEY.publicKey,
         SBR_MINT,
@@ -212,11 +219,13 @@ describe('tribeca test', () => {
       );
 
       LOCKER_PDA = lockerPDA;
+      CHECKPOINTS_PDA = checkpointsPDA;
 
       console.log('Locker PDA', LOCKER_PDA.toBase58());
 
       const transaction = new Transaction();
       transaction.add(createLockerInstruction);
+      transaction.add(createLockerCheckpointsInstruction);
       transaction.feePayer = payer.publicKey;
       transaction.recentBlockhash = (
         await connection.getLatestBlockhash()
@@ -228,12 +237,113 @@ describe('tribeca test', () => {
       ]);
 
       console.log('Transaction sent and confirmed', tx);
+
+      // the locker defaults to the linear curve, so its total voting power is tracked
+      const locker = await sdk.tribecaProgram.account.locker.fetch(LOCKER_PDA);
+      expect(locker.params.votingPowerCurve).to.have.property('linear');
+
+      const checkpoints =
+        await sdk.tribecaProgram.account.lockerCheckpoints.fetch(
+          CHECKPOINTS_PDA
+        );
+      expect(checkpoints.locker.toBase58()).to.equal(LOCKER_PDA.toBase58());
+      expect(await sdk.getTotalVotingPower(LOCKER_PDA)).to.equal(0);
     } catch (err) {
       console.error('Error creating locker', err);
       throw err;
     }
   });
 
+  it('Creates a locker with a flat voting power curve', async () => {
+    try {
+      const baseKey = Keypair.generate();
+
+      const {
+        createLockerInstruction,
+        createLockerCheckpointsInstruction,
+        lockerPDA,
+      } = await sdk.createNewLocker(
+        payer.publicKey,
+        baseKey.publicKey,
+        SBR_MINT,
+        TRIBECA_GOVERNOR_PDA,
+        {
+          whitelistEnabled: false,
+          maxStakeVoteMultiplier: 10,
+          maxStakeDuration: new BN(52 * 7 * 24 * 60 * 60), // 52 weeks in seconds
+          minStakeDuration: new BN(7 * 24 * 60 * 60), // 1 week in seconds
+          proposalActivationMinVotes: new BN(2000 * Math.pow(10, 6)),
+          votingPowerCurve: { flat: {} },
+          baselinePowerBps: 1000, // 10% of the locked amount
+        }
+      );
+
+      const transaction = new Transaction();
+      transaction.add(createLockerInstruction);
+      transaction.add(createLockerCheckpointsInstruction);
+      transaction.feePayer = payer.publicKey;
+      transaction.recentBlockhash = (
+        await connection.getLatestBlockhash()
+      ).blockhash;
+
+      const tx = await sendAndConfirmTransaction(connection, transaction, [
+        payer.payer,
+        baseKey,
+      ]);
+
+      console.log('Transaction sent and confirmed', tx);
+
+      const locker = await sdk.tribecaProgram.account.locker.fetch(lockerPDA);
+      expect(locker.params.votingPowerCurve).to.have.property('flat');
+      expect(locker.params.baselinePowerBps).to.equal(1000);
+    } catch (err) {
+      console.error('Error creating locker with a flat curve', err);
+      throw err;
+    }
+  });
+
+  it('Rejects a locker whose min stake duration exceeds the max', async () => {
+    try {
+      const baseKey = Keypair.generate();
+
+      const { createLockerInstruction } = await sdk.createNewLocker(
+        payer.publicKey,
+        baseKey.publicKey,
+        SBR_MINT,
+        TRIBECA_GOVERNOR_PDA,
+        {
+          whitelistEnabled: false,
+          maxStakeVoteMultiplier: 10,
+          maxStakeDuration: new BN(4 * 7 * 24 * 60 * 60), // 4 weeks in seconds
+          minStakeDuration: new BN(4 * 7 * 24 * 60 * 60), // 4 weeks in seconds
+          proposalActivationMinVotes: new BN(2000 * Math.pow(10, 6)),
+        }
+      );
+
+      const transaction = new Transaction();
+      transaction.add(createLockerInstruction);
+      transaction.feePayer = payer.publicKey;
+      transaction.recentBlockhash = (
+        await connection.getLatestBlockhash()
+      ).blockhash;
+
+      const failed = await sendAndConfirmTransaction(connection, transaction, [
+        payer.payer,
+        baseKey,
+      ]).then(
+        () => false,
+        err => {
+          expect(err.message).to.include('0x1791'); // InvalidMinStakeDuration
+          return true;
+        }
+      );
+      expect(failed).to.equal(true);
+    } catch (err) {
+      console.error('Error rejecting locker params', err);
+      throw err;
+    }
+  });
+
   it('Creates a locker redeemer', async () => {
     try {
       const { address: treasuryTokenAccount } =
@@ -357,6 +467,55 @@ describe('tribeca test', () => {
     }
   });
 
+  it('Tracks the total voting power of the locker', async () => {
+    try {
+      // the escrow is the only one in the locker
+      const votingPower = await sdk.getVotingPower(ESCROW_PDA, LOCKER_PDA);
+      const totalVotingPower = await sdk.getTotalVotingPower(LOCKER_PDA);
+
+      console.log('Total voting power', totalVotingPower);
+
+      expect(totalVotingPower).to.be.closeTo(votingPower, 1);
+
+      const escrow = await sdk.tribecaProgram.account.escrow.fetch(ESCROW_PDA);
+      expect(escrow.checkpointed).to.equal(true);
+    } catch (err) {
+      console.error('Error tracking total voting power', err);
+      throw err;
+    }
+  });
+
+  // This will fail which is expected
+  it('Attempts to checkpoint an escrow twice', async () => {
+    try {
+      const { checkpointEscrowInstruction } = await sdk.checkpointEscrow(
+        LOCKER_PDA,
+        ESCROW_PDA
+      );
+
+      const transaction = new Transaction();
+      transaction.add(checkpointEscrowInstruction);
+      transaction.feePayer = payer.publicKey;
+      transaction.recentBlockhash = (
+        await connection.getLatestBlockhash()
+      ).blockhash;
+
+      const failed = await sendAndConfirmTransaction(connection, transaction, [
+        payer.payer,
+      ]).then(
+        () => false,
+        err => {
+          expect(err.message).to.include('0x1783'); // EscrowAlreadyCheckpointed
+          return true;
+        }
+      );
+      expect(failed).to.equal(true);
+    } catch (err) {
+      console.error('Error checkpointing escrow', err);
+      throw err;
+    }
+  });
+
   it('Unlocks tokens from escrow', async () => {
     try {
       const { address: escrowTokenAccount } =
@@ -382,8 +541,7 @@ describe('tribeca test', () => {
         ESCROW_PDA,
         payer.publicKey,
         escrowTokenAccount,
-        destinationTokenAccount,
-        SBR_MINT
+        destinationTokenAccount
       );
 
       const transaction = new Transaction();
@@ -579,6 +737,9 @@ describe('tribeca test', () => {
 
       console.log('Transaction sent and confirmed', tx);
 
+      // all of the escrow's tokens were redeemed, so it no longer counts
+      expect(await sdk.getTotalVotingPower(LOCKER_PDA)).to.equal(0);
+
       // // get the escrow account
       // const escrow = await sdk.tribecaProgram.account.escrow.fetch(ESCROW_PDA);
       // console.log('Escrow', escrow);